@@ -0,0 +1,16 @@
+-- 删除初始表结构
+
+drop table if exists sys_operate_log;
+drop table if exists sys_login_log;
+drop table if exists sys_notice;
+drop table if exists sys_dict_data;
+drop table if exists sys_dict_type;
+drop table if exists sys_user_post;
+drop table if exists sys_post;
+drop table if exists sys_role_dept;
+drop table if exists sys_dept;
+drop table if exists sys_role_menu;
+drop table if exists sys_menu;
+drop table if exists sys_user_role;
+drop table if exists sys_role;
+drop table if exists sys_user;
@@ -0,0 +1,217 @@
+-- 初始表结构
+
+create table sys_user
+(
+    id              bigint auto_increment comment '主键'
+        primary key,
+    mobile          char(11)     default ''                not null comment '手机号码',
+    user_name       varchar(50)                            not null comment '用户账号',
+    nick_name       varchar(30)                            not null comment '用户昵称',
+    user_type       varchar(2)   default '00'              not null comment '用户类型（00系统用户）',
+    avatar          varchar(100) default ''                not null comment '头像路径',
+    email           varchar(50)  default ''                not null comment '用户邮箱',
+    password        varchar(64)                            not null comment '密码',
+    status          tinyint      default 1                 not null comment '状态(1:正常，0:禁用)',
+    dept_id         bigint       default 1                 not null comment '部门ID',
+    login_ip        varchar(128) default ''                not null comment '最后登录IP',
+    login_date      datetime comment '最后登录时间',
+    login_browser   varchar(50)  default ''                not null comment '浏览器类型',
+    login_os        varchar(50)  default ''                not null comment '操作系统',
+    pwd_update_date datetime comment '密码最后更新时间',
+    remark          varchar(255) null comment '备注',
+    del_flag        tinyint      default 1                 not null comment '删除标志（0代表删除 1代表存在）',
+    create_time     datetime     default CURRENT_TIMESTAMP not null comment '创建时间',
+    update_time     datetime     default CURRENT_TIMESTAMP not null on update CURRENT_TIMESTAMP comment '修改时间',
+    constraint AK_phone
+        unique (mobile)
+) comment '用户信息';
+
+create table sys_role
+(
+    id          bigint auto_increment comment '主键'
+        primary key,
+    role_name   varchar(50)                            not null comment '名称',
+    role_key    varchar(100) default ''                not null comment '角色权限字符串',
+    data_scope  tinyint      default 1                 not null comment '数据范围（1：全部数据权限 2：自定数据权限 3：本部门数据权限 4：本部门及以下数据权限）',
+    status      tinyint      default 1                 not null comment '状态(1:正常，0:禁用)',
+    remark      varchar(255)                           not null comment '备注',
+    del_flag    tinyint      default 1                 not null comment '删除标志（0代表删除 1代表存在）',
+    create_time datetime     default CURRENT_TIMESTAMP not null comment '创建时间',
+    update_time datetime     default CURRENT_TIMESTAMP not null on update CURRENT_TIMESTAMP comment '修改时间',
+    constraint role_name
+        unique (role_name)
+) comment '角色信息';
+
+create index name_status_index
+    on sys_role (role_name, status);
+
+create table sys_user_role
+(
+    id          bigint auto_increment comment '主键'
+        primary key,
+    user_id     bigint   default 0                 not null comment '用户ID',
+    role_id     bigint                             not null comment '角色ID',
+    create_time datetime default CURRENT_TIMESTAMP not null comment '创建时间'
+)
+    comment '角色用户关联表';
+
+create table sys_menu
+(
+    id          bigint auto_increment comment '主键'
+        primary key,
+    menu_name   varchar(50)                            not null comment '菜单名称',
+    menu_type   tinyint      default 1                 not null comment '菜单类型(1：目录   2：菜单   3：按钮)',
+    visible     tinyint      default 1                 not null comment '显示状态（0:隐藏, 显示:1）',
+    `status`    tinyint      default 1                 not null comment '菜单状态(1:正常，0:禁用)',
+    sort        int          default 1                 not null comment '排序',
+    parent_id   bigint       default 0                 not null comment '父ID',
+    menu_url    varchar(255) default ''                not null comment '路由路径',
+    api_url     varchar(255) default ''                not null comment '接口URL',
+    menu_icon   varchar(255) default ''                not null comment '菜单图标',
+    remark      varchar(255) default ''                not null comment '备注',
+    create_time datetime     default CURRENT_TIMESTAMP not null comment '创建时间',
+    update_time datetime     default CURRENT_TIMESTAMP not null on update CURRENT_TIMESTAMP comment '修改时间',
+    constraint menu_name
+        unique (menu_name)
+)
+    comment '菜单信息';
+
+create table sys_role_menu
+(
+    id          bigint auto_increment comment '主键'
+        primary key,
+    role_id     bigint                             not null comment '角色ID',
+    menu_id     bigint                             not null comment '菜单ID',
+    create_time datetime default CURRENT_TIMESTAMP not null comment '创建时间'
+)
+    comment '菜单角色关联表';
+
+create table sys_dept
+(
+    id          bigint auto_increment comment '部门id'
+        primary key,
+    parent_id   bigint(20)  default 0                 not null comment '父部门id',
+    ancestors   varchar(50) default ''                not null comment '祖级列表',
+    dept_name   varchar(30) default ''                not null comment '部门名称',
+    sort        int(4)      default 0                 not null comment '显示顺序',
+    leader      varchar(20) default ''                not null comment '负责人',
+    phone       varchar(11) default ''                not null comment '联系电话',
+    email       varchar(50) default ''                not null comment '邮箱',
+    status      tinyint     default 0                 not null comment '部门状态（0：停用，1:正常）',
+    del_flag    tinyint     default 1                 not null comment '删除标志（0代表删除 1代表存在）',
+    create_time datetime    default CURRENT_TIMESTAMP not null comment '创建时间',
+    update_time datetime    default CURRENT_TIMESTAMP not null on update CURRENT_TIMESTAMP comment '修改时间'
+) comment = '部门表';
+
+create table sys_role_dept
+(
+    role_id bigint not null comment '角色id',
+    dept_id bigint not null comment '部门id',
+    primary key (role_id, dept_id)
+) comment = '角色和部门关联表';
+
+create table sys_post
+(
+    id          bigint auto_increment comment '岗位id'
+        primary key,
+    post_code   varchar(64)                            not null comment '岗位编码',
+    post_name   varchar(50)                            not null comment '岗位名称',
+    sort        int          default 0                 not null comment '显示顺序',
+    status      tinyint      default 0                 not null comment '岗位状态（0：停用，1:正常）',
+    remark      varchar(500) default ''                not null comment '备注',
+    create_time datetime     default CURRENT_TIMESTAMP not null comment '创建时间',
+    update_time datetime     default CURRENT_TIMESTAMP not null on update CURRENT_TIMESTAMP comment '更新时间'
+) comment = '岗位信息表';
+
+create table sys_user_post
+(
+    user_id bigint not null comment '用户id',
+    post_id bigint not null comment '岗位id',
+    primary key (user_id, post_id)
+) comment = '用户与岗位关联表';
+
+create table sys_dict_type
+(
+    id     bigint                                 not null auto_increment comment '字典主键',
+    dict_name   varchar(100) default ''                not null comment '字典名称',
+    dict_type   varchar(100) default ''                not null comment '字典类型',
+    status      tinyint      default 0                 not null comment '状态（0：停用，1:正常）',
+    remark      varchar(500) default ''                not null comment '备注',
+    create_time datetime     default CURRENT_TIMESTAMP not null comment '创建时间',
+    update_time datetime     default CURRENT_TIMESTAMP not null on update CURRENT_TIMESTAMP comment '修改时间',
+    primary key (id),
+    unique (dict_type)
+) comment = '字典类型表';
+
+create table sys_dict_data
+(
+    id   bigint                                 not null auto_increment    comment '字典编码',
+    dict_sort   int          default 0                 not null comment '字典排序',
+    dict_label  varchar(100) default ''                not null comment '字典标签',
+    dict_value  varchar(100) default ''                not null comment '字典键值',
+    dict_type   varchar(100) default ''                not null comment '字典类型',
+    css_class   varchar(100) default ''                not null comment '样式属性（其他样式扩展）',
+    list_class  varchar(100) default ''                not null comment '表格回显样式',
+    is_default  char(1)      default 'N'               not null comment '是否默认（Y是 N否）',
+    status      tinyint      default 0                 not null comment '状态（0：停用，1:正常）',
+    remark      varchar(500) default ''                not null comment '备注',
+    create_time datetime     default CURRENT_TIMESTAMP not null comment '创建时间',
+    update_time datetime     default CURRENT_TIMESTAMP not null on update CURRENT_TIMESTAMP comment '修改时间',
+    primary key (id)
+)comment = '字典数据表';
+
+create table sys_notice
+(
+    id             bigint auto_increment comment '公告ID'
+        primary key,
+    notice_title   varchar(50)                            not null comment '公告标题',
+    notice_type    tinyint      default 1                 not null comment '公告类型（1:通知,2:公告）',
+    notice_content varchar(255) default ''                not null comment '公告内容',
+    status         tinyint      default 0                 not null comment '公告状态（0:关闭,1:正常 ）',
+    remark         varchar(255) default ''                not null comment '备注',
+    create_time    datetime     default CURRENT_TIMESTAMP not null comment '创建时间',
+    update_time    datetime     default CURRENT_TIMESTAMP not null on update CURRENT_TIMESTAMP comment '修改时间'
+) comment '通知公告表';
+
+create table sys_login_log
+(
+    id             bigint auto_increment comment '访问ID'
+        primary key,
+    login_name     varchar(50)  default ''                not null comment '登录账号',
+    ipaddr         varchar(128) default ''                not null comment '登录IP地址',
+    login_location varchar(255) default ''                not null comment '登录地点',
+    platform       varchar(50)  default ''                not null comment '平台信息',
+    browser        varchar(50)  default ''                not null comment '浏览器类型',
+    version        varchar(50)  default ''                not null comment '浏览器版本',
+    os             varchar(50)  default ''                not null comment '操作系统',
+    arch           varchar(50)  default ''                not null comment '体系结构信息',
+    engine         varchar(50)  default ''                not null comment '渲染引擎信息',
+    engine_details varchar(50)  default ''                not null comment '渲染引擎详细信息',
+    extra          varchar(50)  default ''                not null comment '其他信息（可选）',
+    status         tinyint      default 0                 not null comment '登录状态(0:失败,1:成功)',
+    msg            varchar(255) default ''                not null comment '提示消息',
+    login_time     datetime     default CURRENT_TIMESTAMP not null comment '访问时间'
+) comment = '系统访问记录';
+
+create table sys_operate_log
+(
+    id               bigint auto_increment comment '日志主键'
+        primary key,
+    title            varchar(50)   default '' comment '模块标题',
+    business_type    tinyint       default 0 comment '业务类型（0其它 1新增 2修改 3删除）',
+    method           varchar(200)  default '' comment '方法名称',
+    request_method   varchar(10)   default '' comment '请求方式',
+    operator_type    tinyint       default 0 comment '操作类别（0其它 1后台用户 2手机端用户）',
+    operate_name     varchar(50)   default '' comment '操作人员',
+    dept_name        varchar(50)   default '' comment '部门名称',
+    operate_url      varchar(255)  default '' comment '请求URL',
+    operate_ip       varchar(128)  default '' comment '主机地址',
+    operate_location varchar(255)  default '' comment '操作地点',
+    operate_param    varchar(2000) default '' comment '请求参数',
+    json_result      varchar(2000) default '' comment '返回参数',
+    status           tinyint       default 0 comment '操作状态(0:异常,正常)',
+    error_msg        varchar(2000) default '' comment '错误消息',
+    operate_time     datetime      default CURRENT_TIMESTAMP not null comment '操作时间',
+    cost_time        bigint(20)    default 0 comment '消耗时间'
+
+) comment = '操作日志记录';
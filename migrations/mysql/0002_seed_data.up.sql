@@ -0,0 +1,139 @@
+-- 初始数据(默认账号admin/123456,登录后会自动升级为Argon2id密码)
+
+-- 超级管理员和演示用户
+INSERT INTO sys_user (id, mobile, user_name, nick_name, avatar, email, password, status, remark) VALUES (1, '18613030111', 'admin','admin', 'https://gw.alipayobjects.com/zos/antfincdn/XAosXuNZyF/BiazfanxmamNRoxxVxka.png','xx@qq.com','123456', 1,  '超级管理员');
+INSERT INTO sys_user (id, mobile, user_name, nick_name, avatar, email, password, status, remark) VALUES (2, '18613030222', 'test', 'test', 'https://gw.alipayobjects.com/zos/antfincdn/XAosXuNZyF/BiazfanxmamNRoxxVxka.png','123@qq.com','123456', 1, '演示权限');
+
+-- 超级管理员角色和演示角色
+INSERT INTO sys_role (id, role_name, role_key, status, remark) VALUES (1, '超级管理员', 'admin',1, '全部权限');
+INSERT INTO sys_role (id, role_name, role_key, status, remark) VALUES (2, '演示角色', 'query',1,  '仅有查看功能');
+INSERT INTO sys_role (id, role_name, role_key, status, remark) VALUES (3, '121', 'dev',0, '121211');
+
+-- 超级管理员的角色
+INSERT INTO sys_user_role (user_id, role_id) VALUES (1, 1);
+
+-- 基础菜单和按钮权限
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (1, '首页', 1, 1, 1, 0, '/home', '', 'DashboardOutlined', '首页');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (2, '权限管理', 1, 1, 2, 0, '/system', '', 'SettingOutlined', '权限管理');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (3, '用户管理', 2, 1, 1, 2, '/system/user', '', 'UserOutlined', '用户信息管理');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (4, '添加用户信息', 3, 1, 1, 3, '', '/api/system/user/addUser', '', '添加用户信息');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (5, '删除用户信息', 3, 1, 2, 3, '', '/api/system/user/deleteUser', '', '删除用户信息');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (6, '更新用户信息', 3, 1, 3, 3, '', '/api/system/user/updateUser', '', '更新用户信息');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (7, '更新用户信息状态', 3, 1, 4, 3, '', '/api/system/user/updateUserStatus', '', '更新用户信息状态');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (8, '更新用户密码', 3, 1, 4, 3, '', '/api/system/user/updateUserPassword', '', '更新用户密码');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (9, '查询用户信息详情', 3, 1, 5, 3, '', '/api/system/user/queryUserDetail', '', '查询用户信息详情');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (10, '查询用户信息列表', 3, 1, 6, 3, '', '/api/system/user/queryUserList', '', '查询用户信息列表');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (11, '用户登录', 3, 1, 7, 3, '', '/api/system/user/login', '', '用户登录');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (12, '查询用户菜单列表', 3, 1, 8, 3, '', '/api/system/user/queryUserMenu', '', '查询用户菜单列表');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (13, '查询用户角色信息', 3, 1, 9, 3, '', '/api/system/user/queryUserRole', '', '查询用户角色信息');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (14, '更新用户角色信息', 3, 1, 10, 3, '', '/api/system/user/updateUserRole', '', '更新用户角色信息');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (15, '角色管理', 2, 1, 2, 2, '/system/role', '', 'UsergroupAddOutlined', '角色信息管理');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (16, '添加角色信息', 3, 1, 1, 15, '', '/api/system/role/addRole', '', '添加角色信息');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (17, '删除角色信息', 3, 1, 2, 15, '', '/api/system/role/deleteRole', '', '删除角色信息');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (18, '更新角色信息', 3, 1, 3, 15, '', '/api/system/role/updateRole', '', '更新角色信息');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (19, '更新角色信息状态', 3, 1, 4, 15, '', '/api/system/role/updateRoleStatus', '', '更新角色信息状态');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (20, '查询角色信息详情', 3, 1, 5, 15, '', '/api/system/role/queryRoleDetail', '', '查询角色信息详情');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (21, '查询角色信息列表', 3, 1, 6, 15, '', '/api/system/role/queryRoleList', '', '查询角色信息列表');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (22, '查询角色菜单列表', 3, 1, 7, 15, '', '/api/system/role/queryRoleMenu', '', '查询角色菜单列表');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (23, '更新角色菜单信息', 3, 1, 8, 15, '', '/api/system/role/updateRoleMenu', '', '更新角色菜单信息');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (24, '查询已分配用户角色', 3, 1, 8, 15, '', '/api/system/role/queryAllocatedList', '', '查询已分配用户角色');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (25, '查询未分配用户角色', 3, 1, 8, 15, '', '/api/system/role/queryUnallocatedList', '', '查询未分配用户角色');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (26, '取消授权用户', 3, 1, 8, 15, '', '/api/system/role/cancelAuthUser', '', '取消授权用户');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (27, '批量取消授权用户', 3, 1, 8, 15, '', '/api/system/role/batchCancelAuthUser', '', '批量取消授权用户');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (28, '批量选择用户授权', 3, 1, 8, 15, '', '/api/system/role/batchAuthUser', '', '批量选择用户授权');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (29, '菜单管理', 2, 1, 3, 2, '/system/menu', '', 'MenuOutlined', '菜单信息管理');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (30, '添加菜单', 3, 1, 1, 29, '', '/api/system/menu/addMenu', '', '添加菜单');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (31, '删除菜单', 3, 1, 2, 29, '', '/api/system/menu/deleteMenu', '', '删除菜单');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (32, '更新菜单', 3, 1, 3, 29, '', '/api/system/menu/updateMenu', '', '更新菜单');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (33, '更新菜单状态', 3, 1, 4, 29, '', '/api/system/menu/updateMenuStatus', '', '更新菜单状态');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (34, '查询菜单详情', 3, 1, 5, 29, '', '/api/system/menu/queryMenuDetail', '', '查询菜单详情');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (35, '查询菜单列表', 3, 1, 6, 29, '', '/api/system/menu/queryMenuList', '', '查询菜单列表');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (36, '查询菜单树', 3, 1, 6, 29, '', '/api/system/menu/queryMenuListSimple', '', '查询菜单树');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (37, '部门管理', 2, 1, 4, 2, '/system/dept', '', 'ApartmentOutlined', '部门管理');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (38, '添加部门', 3, 1, 1, 37, '', '/api/system/dept/addDept', '', '添加部门');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (39, '删除部门', 3, 1, 2, 37, '', '/api/system/dept/deleteDept', '', '删除部门');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (40, '更新部门', 3, 1, 3, 37, '', '/api/system/dept/updateDept', '', '更新部门');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (41, '更新部门状态', 3, 1, 4, 37, '', '/api/system/dept/updateDeptStatus', '', '更新部门状态');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (42, '查询部门详情', 3, 1, 5, 37, '', '/api/system/dept/queryDeptDetail', '', '查询部门详情');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (43, '查询部门列', 3, 1, 6, 37, '', '/api/system/dept/queryDeptList', '', '查询部门列');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (44, '岗位管理', 2, 1, 5, 2, '/system/post', '', 'AuditOutlined', '岗位管理');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (45, '添加岗位', 3, 1, 1, 44, '', '/api/system/post/addPost', '', '添加岗位');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (46, '删除岗位', 3, 1, 2, 44, '', '/api/system/post/deletePost', '', '删除岗位');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (47, '更新岗位', 3, 1, 3, 44, '', '/api/system/post/updatePost', '', '更新岗位');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (48, '更新岗位状态', 3, 1, 4, 44, '', '/api/system/post/updatePostStatus', '', '更新岗位状态');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (49, '查询岗位详情', 3, 1, 5, 44, '', '/api/system/post/queryPostDetail', '', '查询岗位详情');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (50, '查询岗位列', 3, 1, 6, 44, '', '/api/system/post/queryPostList', '', '查询岗位列');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (51, '字典类型', 2, 1, 6, 2, '/system/dictType', '', 'TableOutlined', '字典类型管理');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (52, '添加字典类型', 3, 1, 1, 51, '', '/api/system/dictType/addDictType', '', '添加字典类型');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (53, '删除字典类型', 3, 1, 2, 51, '', '/api/system/dictType/deleteDictType', '', '删除字典类型');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (54, '更新字典类型', 3, 1, 3, 51, '', '/api/system/dictType/updateDictType', '', '更新字典类型');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (55, '更新字典类型状态', 3, 1, 4, 51, '', '/api/system/dictType/updateDictTypeStatus', '', '更新字典类型状态');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (56, '查询字典类型详情', 3, 1, 5, 51, '', '/api/system/dictType/queryDictTypeDetail', '', '查询字典类型详情');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (57, '查询字典类型列', 3, 1, 6, 51, '', '/api/system/dictType/queryDictTypeList', '', '查询字典类型列');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, visible, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (58, '字典数据', 2, 1, 0,7, 2, '/system/dictData', '', 'UngroupOutlined', '字典数据管理');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (59, '添加字典数据', 3, 1, 1, 58, '', '/api/system/dictData/addDictData', '', '添加字典数据');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (60, '删除字典数据', 3, 1, 2, 58, '', '/api/system/dictData/deleteDictData', '', '删除字典数据');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (61, '更新字典数据', 3, 1, 3, 58, '', '/api/system/dictData/updateDictData', '', '更新字典数据');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (62, '更新字典数据状态', 3, 1, 4, 58, '', '/api/system/dictData/updateDictDataStatus', '', '更新字典数据状态');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (63, '查询字典数据详情', 3, 1, 5, 58, '', '/api/system/dictData/queryDictDataDetail', '', '查询字典数据详情');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (64, '查询字典数据列', 3, 1, 6, 58, '', '/api/system/dictData/queryDictDataList', '', '查询字典数据列');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (65, '通知公告', 2, 1, 8, 2, '/system/notice', '', 'MessageOutlined', '通知公告管理');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (66, '添加通知公告', 3, 1, 1, 65, '', '/api/system/notice/addNotice', '', '添加通知公告');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (67, '删除通知公告', 3, 1, 2, 65, '', '/api/system/notice/deleteNotice', '', '删除通知公告');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (68, '更新通知公告', 3, 1, 3, 65, '', '/api/system/notice/updateNotice', '', '更新通知公告');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (69, '更新通知公告状态', 3, 1, 4, 65, '', '/api/system/notice/updateNoticeStatus', '', '更新通知公告状态');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (70, '查询通知公告详情', 3, 1, 5, 65, '', '/api/system/notice/queryNoticeDetail', '', '查询通知公告详情');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (71, '查询通知公告列', 3, 1, 6, 65, '', '/api/system/notice/queryNoticeList', '', '查询通知公告列');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (72, '日志管理', 1, 1, 2, 0, '/log', '', 'FilterOutlined', '日志管理');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (73, '登录日志', 2, 1, 9, 72, '/log/loginLog', '', 'DeleteOutlined', '系统访问记录管理');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (74, '添加系统访问记录', 3, 1, 1, 73, '', '/api/system/loginLog/addLoginLog', '', '添加系统访问记录');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (75, '删除系统访问记录', 3, 1, 2, 73, '', '/api/system/loginLog/deleteLoginLog', '', '删除系统访问记录');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (76, '清空系统登录日志', 3, 1, 3, 73, '', '/api/system/loginLog/cleanLoginLog', '', '清空系统登录日志');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (77, '更新系统访问记录状态', 3, 1, 4, 73, '', '/api/system/loginLog/updateLoginLogStatus', '', '更新系统访问记录状态');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (78, '查询系统访问记录详情', 3, 1, 5, 73, '', '/api/system/loginLog/queryLoginLogDetail', '', '查询系统访问记录详情');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (79, '查询系统访问记录列', 3, 1, 6, 73, '', '/api/system/loginLog/queryLoginLogList', '', '查询系统访问记录列');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (80, '操作日志', 2, 1, 10, 72, '/log/operateLog', '', 'ClearOutlined', '操作日志记录管理');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (81, '添加操作日志记录', 3, 1, 1, 80, '', '/api/system/operateLog/addOperateLog', '', '添加操作日志记录');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (82, '删除操作日志记录', 3, 1, 2, 80, '', '/api/system/operateLog/deleteOperateLog', '', '删除操作日志记录');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (83, '清空操作日志记录', 3, 1, 3, 80, '', '/api/system/operateLog/cleanOperateLog', '', '清空操作日志记录');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (84, '更新操作日志记录状态', 3, 1, 4, 80, '', '/api/system/operateLog/updateOperateLogStatus', '', '更新操作日志记录状态');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (85, '查询操作日志记录详情', 3, 1, 5, 80, '', '/api/system/operateLog/queryOperateLogDetail', '', '查询操作日志记录详情');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (86, '查询操作日志记录列', 3, 1, 6, 80, '', '/api/system/operateLog/queryOperateLogList', '', '查询操作日志记录列');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (87, '其他', 1, 1, 3, 0, '/other', '', 'AudioOutlined', '其他');
+
+-- 部门
+INSERT INTO sys_dept (parent_id, ancestors, dept_name, sort, leader, phone, email, status, del_flag) VALUES (0, '0', '测试科技', 1, 'admin', '18613030352', '1002219331@qq.com', 1, 1);
+INSERT INTO sys_dept (parent_id, ancestors, dept_name, sort, leader, phone, email, status, del_flag) VALUES (1, '0,1', '深圳总公司', 1, '1', '1', 'xx@qq.com', 1, 1);
+INSERT INTO sys_dept (parent_id, ancestors, dept_name, sort, leader, phone, email, status, del_flag) VALUES (1, '0,1', '长沙分公司', 2, '1', '1', 'xx@qq.com', 1, 1);
+INSERT INTO sys_dept (parent_id, ancestors, dept_name, sort, leader, phone, email, status, del_flag) VALUES (2, '0,1,2', '研发部门', 1, '1', '1', 'xx@qq.com', 1, 1);
+INSERT INTO sys_dept (parent_id, ancestors, dept_name, sort, leader, phone, email, status, del_flag) VALUES (2, '0,1,2', '市场部门', 2, '1', '1', 'xx@qq.com', 1, 1);
+INSERT INTO sys_dept (parent_id, ancestors, dept_name, sort, leader, phone, email, status, del_flag) VALUES (2, '0,1,2', '测试部门', 3, '1', '1', 'xx@qq.com', 1, 1);
+INSERT INTO sys_dept (parent_id, ancestors, dept_name, sort, leader, phone, email, status, del_flag) VALUES (2, '0,1,2', '财务部门', 4, '1', '1', 'xx@qq.com', 1, 1);
+INSERT INTO sys_dept (parent_id, ancestors, dept_name, sort, leader, phone, email, status, del_flag) VALUES (2, '0,1,2', '运维部门', 5, '1', '1', 'xx@qq.com', 1, 1);
+INSERT INTO sys_dept (parent_id, ancestors, dept_name, sort, leader, phone, email, status, del_flag) VALUES (3, '0,1,3', '市场部门1', 6, '1', '1', 'xx@qq.com', 1, 1);
+INSERT INTO sys_dept (parent_id, ancestors, dept_name, sort, leader, phone, email, status, del_flag) VALUES (3, '0,1,3', '财务部门1', 1, '1', '1', 'xx@qq.com', 1, 1);
+
+-- 岗位
+INSERT INTO sys_post (post_code, post_name, sort, status, remark) VALUES ('ceo', '董事长', 1, 1, '');
+INSERT INTO sys_post (post_code, post_name, sort, status, remark) VALUES ('se', '项目经理', 2, 1, '');
+INSERT INTO sys_post (post_code, post_name, sort, status, remark) VALUES ('hr', '人力资源', 3, 1, '');
+INSERT INTO sys_post (post_code, post_name, sort, status, remark) VALUES ('user', '普通员工', 1, 1, '');
+
+-- 用户岗位
+insert into sys_user_post values ('1', '1');
+insert into sys_user_post values ('2', '2');
+
+-- 字典类型
+INSERT INTO sys_dict_type (dict_name, dict_type, status, remark) VALUES ('用户性别', 'sys_user_sex', 1, '用户性别列表');
+INSERT INTO sys_dict_type (dict_name, dict_type, status, remark) VALUES ('通知类型', 'sys_notice_type', 1, '通知类型列表');
+
+-- 字典数据
+INSERT INTO sys_dict_data (dict_sort, dict_label, dict_value, dict_type, css_class, list_class, is_default, status, remark) VALUES (1, '男', '0', 'sys_user_sex', '1', '1', 'N', 1, '性别男');
+INSERT INTO sys_dict_data (dict_sort, dict_label, dict_value, dict_type, css_class, list_class, is_default, status, remark) VALUES (2, '女', '1', 'sys_user_sex', '1', '1', 'N', 1, '性别女');
+INSERT INTO sys_dict_data (dict_sort, dict_label, dict_value, dict_type, css_class, list_class, is_default, status, remark) VALUES (3, '未知', '2', 'sys_user_sex', '1', '1', 'N', 1, '性别未知');
+INSERT INTO sys_dict_data (dict_sort, dict_label, dict_value, dict_type, css_class, list_class, is_default, status, remark) VALUES (1, '通知', '1', 'sys_notice_type', '1', '1', 'N', 1, '通知');
+INSERT INTO sys_dict_data (dict_sort, dict_label, dict_value, dict_type, css_class, list_class, is_default, status, remark) VALUES (2, '公告', '2', 'sys_notice_type', '1', '1', 'N', 1, '公告');
+
+-- 通知公告
+INSERT INTO sys_notice (notice_title, notice_type, notice_content, status) VALUES ('测试通知1', 1, '这是一条测试通知内容', 1);
+INSERT INTO sys_notice (notice_title, notice_type, notice_content, status) VALUES ('测试公告2', 2, '这是一条测试公告内容', 1);
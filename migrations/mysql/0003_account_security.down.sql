@@ -0,0 +1,17 @@
+-- 删除账号安全的表,字段和按钮,恢复手机号码的唯一约束(存在重复的手机号码时需要先修改)
+
+delete from sys_role_menu where menu_id between 88 and 98;
+delete from sys_menu where id between 88 and 98;
+
+drop table if exists sys_api_token;
+drop table if exists sys_user_password_history;
+drop table if exists sys_user_identity;
+drop table if exists sys_user_mfa;
+drop table if exists sys_user_session;
+
+alter table sys_operate_log drop column impersonate_name;
+alter table sys_role modify column data_scope tinyint default 1 not null comment '数据范围（1：全部数据权限 2：自定数据权限 3：本部门数据权限 4：本部门及以下数据权限）';
+drop index idx_mobile on sys_user;
+alter table sys_user add constraint AK_phone unique (mobile);
+alter table sys_user drop column auth_source;
+alter table sys_user modify column mobile char(11) default '' not null comment '手机号码';
@@ -0,0 +1,4 @@
+-- 删除多语言的字段
+
+alter table sys_dict_data drop column label_i18n;
+alter table sys_user drop column locale;
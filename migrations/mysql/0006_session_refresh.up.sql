@@ -0,0 +1,3 @@
+-- refresh token轮换状态保存在会话中,服务重启和多实例部署时refresh token仍然有效
+
+alter table sys_user_session add column refresh_jti varchar(64) default '' not null comment '当前有效的refresh token编号(每次刷新后轮换)';
@@ -0,0 +1,3 @@
+-- 恢复密码字段的长度(已经升级为Argon2id哈希的密码超过64个字符,需要先重置为明文密码)
+
+alter table sys_user modify column password varchar(64) not null comment '密码';
@@ -0,0 +1,3 @@
+-- 密码改为保存Argon2id哈希(PHC格式约97个字符),加长密码字段,历史明文密码在登录后自动升级
+
+alter table sys_user modify column password varchar(255) not null comment '密码(Argon2id PHC格式,历史明文登录后自动升级)';
@@ -0,0 +1,15 @@
+-- 删除账号安全的表,字段和按钮,恢复手机号码的唯一约束(存在重复的手机号码时需要先修改)
+
+delete from sys_role_menu where menu_id between 88 and 98;
+delete from sys_menu where id between 88 and 98;
+
+drop table if exists sys_api_token;
+drop table if exists sys_user_password_history;
+drop table if exists sys_user_identity;
+drop table if exists sys_user_mfa;
+drop table if exists sys_user_session;
+
+alter table sys_operate_log drop column impersonate_name;
+drop index idx_mobile;
+alter table sys_user add constraint AK_phone unique (mobile);
+alter table sys_user drop column auth_source;
@@ -0,0 +1,6 @@
+-- 删除回收站的按钮,恢复角色名称的唯一约束(存在同名的角色时需要先彻底删除回收站中的角色)
+
+delete from sys_role_menu where menu_id between 99 and 107;
+delete from sys_menu where id between 99 and 107;
+
+alter table sys_role add constraint role_name unique (role_name);
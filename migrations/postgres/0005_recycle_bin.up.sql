@@ -0,0 +1,16 @@
+-- 回收站: 用户,角色和部门改为逻辑删除(del_flag = 0),角色名称只需要和未删除的角色不重复
+
+alter table sys_role drop constraint role_name;
+
+-- 回收站的按钮
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (99, '查询已删除用户', 3, 1, 15, 3, '', '/api/system/user/queryDeletedUserList', '', '查询回收站中的用户');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (100, '恢复已删除用户', 3, 1, 16, 3, '', '/api/system/user/restoreUser', '', '从回收站恢复用户');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (101, '彻底删除用户', 3, 1, 17, 3, '', '/api/system/user/purgeUser', '', '彻底删除回收站中的用户');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (102, '查询已删除角色', 3, 1, 10, 15, '', '/api/system/role/queryDeletedRoleList', '', '查询回收站中的角色');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (103, '恢复已删除角色', 3, 1, 11, 15, '', '/api/system/role/restoreRole', '', '从回收站恢复角色');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (104, '彻底删除角色', 3, 1, 12, 15, '', '/api/system/role/purgeRole', '', '彻底删除回收站中的角色');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (105, '查询已删除部门', 3, 1, 7, 37, '', '/api/system/dept/queryDeletedDeptList', '', '查询回收站中的部门');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (106, '恢复已删除部门', 3, 1, 8, 37, '', '/api/system/dept/restoreDept', '', '从回收站恢复部门');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (107, '彻底删除部门', 3, 1, 9, 37, '', '/api/system/dept/purgeDept', '', '彻底删除回收站中的部门');
+
+select setval(pg_get_serial_sequence('sys_menu', 'id'), (select max(id) from sys_menu));
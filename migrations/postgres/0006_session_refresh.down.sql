@@ -0,0 +1,3 @@
+-- 删除会话的refresh token编号
+
+alter table sys_user_session drop column refresh_jti;
@@ -0,0 +1,5 @@
+-- 恢复密码字段的长度(已经升级为Argon2id哈希的密码超过64个字符,需要先重置为明文密码)
+
+alter table sys_user alter column password type varchar(64);
+
+comment on column sys_user.password is '密码';
@@ -0,0 +1,227 @@
+-- 初始表结构(sqlite),字段说明见 migrations/mysql/0001_init_schema.up.sql
+-- 时间字段保存为本地时间的文本(与驱动写入DateTime的格式一致,保证按时间比较和排序正确)
+-- update_time 由程序在修改时写入,sqlite中不使用触发器
+
+create table sys_user
+(
+    id              integer primary key autoincrement,
+    mobile          char(11)     default ''                not null, -- 手机号码
+    user_name       varchar(50)                            not null, -- 用户账号
+    nick_name       varchar(30)                            not null, -- 用户昵称
+    user_type       varchar(2)   default '00'              not null, -- 用户类型（00系统用户）
+    avatar          varchar(100) default ''                not null, -- 头像路径
+    email           varchar(50)  default ''                not null, -- 用户邮箱
+    password        varchar(64)                            not null, -- 密码
+    status          smallint     default 1                 not null, -- 状态(1:正常，0:禁用)
+    dept_id         bigint       default 1                 not null, -- 部门ID
+    login_ip        varchar(128) default ''                not null, -- 最后登录IP
+    login_date      datetime ,                                       -- 最后登录时间
+    login_browser   varchar(50)  default ''                not null, -- 浏览器类型
+    login_os        varchar(50)  default ''                not null, -- 操作系统
+    pwd_update_date datetime ,                                       -- 密码最后更新时间
+    remark          varchar(255) null,                               -- 备注
+    del_flag        smallint     default 1                 not null, -- 删除标志（0代表删除 1代表存在）
+    create_time     datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 创建时间
+    update_time     datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 修改时间
+    constraint AK_phone unique (mobile)
+);
+
+-- sys_user: 用户信息
+
+create table sys_role
+(
+    id          integer primary key autoincrement,
+    role_name   varchar(50)                            not null, -- 名称
+    role_key    varchar(100) default ''                not null, -- 角色权限字符串
+    data_scope  smallint     default 1                 not null, -- 数据范围（1：全部数据权限 2：自定数据权限 3：本部门数据权限 4：本部门及以下数据权限）
+    status      smallint     default 1                 not null, -- 状态(1:正常，0:禁用)
+    remark      varchar(255)                           not null, -- 备注
+    del_flag    smallint     default 1                 not null, -- 删除标志（0代表删除 1代表存在）
+    create_time datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 创建时间
+    update_time datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 修改时间
+    constraint role_name unique (role_name)
+);
+
+create index name_status_index on sys_role (role_name, status);
+
+-- sys_role: 角色信息
+
+create table sys_user_role
+(
+    id          integer primary key autoincrement,
+    user_id     bigint    default 0                 not null, -- 用户ID
+    role_id     bigint                              not null, -- 角色ID
+    create_time datetime default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null  -- 创建时间
+);
+
+-- sys_user_role: 角色用户关联表
+
+create table sys_menu
+(
+    id          integer primary key autoincrement,
+    menu_name   varchar(50)                            not null, -- 菜单名称
+    menu_type   smallint     default 1                 not null, -- 菜单类型(1：目录   2：菜单   3：按钮)
+    visible     smallint     default 1                 not null, -- 显示状态（0:隐藏, 显示:1）
+    status      smallint     default 1                 not null, -- 菜单状态(1:正常，0:禁用)
+    sort        int          default 1                 not null, -- 排序
+    parent_id   bigint       default 0                 not null, -- 父ID
+    menu_url    varchar(255) default ''                not null, -- 路由路径
+    api_url     varchar(255) default ''                not null, -- 接口URL
+    menu_icon   varchar(255) default ''                not null, -- 菜单图标
+    remark      varchar(255) default ''                not null, -- 备注
+    create_time datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 创建时间
+    update_time datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 修改时间
+    constraint menu_name unique (menu_name)
+);
+
+-- sys_menu: 菜单信息
+
+create table sys_role_menu
+(
+    id          integer primary key autoincrement,
+    role_id     bigint                              not null, -- 角色ID
+    menu_id     bigint                              not null, -- 菜单ID
+    create_time datetime default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null  -- 创建时间
+);
+
+-- sys_role_menu: 菜单角色关联表
+
+create table sys_dept
+(
+    id          integer primary key autoincrement,
+    parent_id   bigint      default 0                 not null, -- 父部门id
+    ancestors   varchar(50) default ''                not null, -- 祖级列表
+    dept_name   varchar(30) default ''                not null, -- 部门名称
+    sort        int         default 0                 not null, -- 显示顺序
+    leader      varchar(20) default ''                not null, -- 负责人
+    phone       varchar(11) default ''                not null, -- 联系电话
+    email       varchar(50) default ''                not null, -- 邮箱
+    status      smallint    default 0                 not null, -- 部门状态（0：停用，1:正常）
+    del_flag    smallint    default 1                 not null, -- 删除标志（0代表删除 1代表存在）
+    create_time datetime    default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 创建时间
+    update_time datetime    default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null  -- 修改时间
+);
+
+-- sys_dept: 部门表
+
+create table sys_role_dept
+(
+    role_id bigint not null, -- 角色id
+    dept_id bigint not null, -- 部门id
+    primary key (role_id, dept_id)
+);
+
+-- sys_role_dept: 角色和部门关联表
+
+create table sys_post
+(
+    id          integer primary key autoincrement,
+    post_code   varchar(64)                            not null, -- 岗位编码
+    post_name   varchar(50)                            not null, -- 岗位名称
+    sort        int          default 0                 not null, -- 显示顺序
+    status      smallint     default 0                 not null, -- 岗位状态（0：停用，1:正常）
+    remark      varchar(500) default ''                not null, -- 备注
+    create_time datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 创建时间
+    update_time datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null  -- 更新时间
+);
+
+-- sys_post: 岗位信息表
+
+create table sys_user_post
+(
+    user_id bigint not null, -- 用户id
+    post_id bigint not null, -- 岗位id
+    primary key (user_id, post_id)
+);
+
+-- sys_user_post: 用户与岗位关联表
+
+create table sys_dict_type
+(
+    id          integer primary key autoincrement,
+    dict_name   varchar(100) default ''                not null, -- 字典名称
+    dict_type   varchar(100) default ''                not null, -- 字典类型
+    status      smallint     default 0                 not null, -- 状态（0：停用，1:正常）
+    remark      varchar(500) default ''                not null, -- 备注
+    create_time datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 创建时间
+    update_time datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 修改时间
+    unique (dict_type)
+);
+
+-- sys_dict_type: 字典类型表
+
+create table sys_dict_data
+(
+    id          integer primary key autoincrement,
+    dict_sort   int          default 0                 not null, -- 字典排序
+    dict_label  varchar(100) default ''                not null, -- 字典标签
+    dict_value  varchar(100) default ''                not null, -- 字典键值
+    dict_type   varchar(100) default ''                not null, -- 字典类型
+    css_class   varchar(100) default ''                not null, -- 样式属性（其他样式扩展）
+    list_class  varchar(100) default ''                not null, -- 表格回显样式
+    is_default  char(1)      default 'N'               not null, -- 是否默认（Y是 N否）
+    status      smallint     default 0                 not null, -- 状态（0：停用，1:正常）
+    remark      varchar(500) default ''                not null, -- 备注
+    create_time datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 创建时间
+    update_time datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null  -- 修改时间
+);
+
+-- sys_dict_data: 字典数据表
+
+create table sys_notice
+(
+    id             integer primary key autoincrement,
+    notice_title   varchar(50)                            not null, -- 公告标题
+    notice_type    smallint     default 1                 not null, -- 公告类型（1:通知,2:公告）
+    notice_content varchar(255) default ''                not null, -- 公告内容
+    status         smallint     default 0                 not null, -- 公告状态（0:关闭,1:正常 ）
+    remark         varchar(255) default ''                not null, -- 备注
+    create_time    datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 创建时间
+    update_time    datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null  -- 修改时间
+);
+
+-- sys_notice: 通知公告表
+
+create table sys_login_log
+(
+    id             integer primary key autoincrement,
+    login_name     varchar(50)  default ''                not null, -- 登录账号
+    ipaddr         varchar(128) default ''                not null, -- 登录IP地址
+    login_location varchar(255) default ''                not null, -- 登录地点
+    platform       varchar(50)  default ''                not null, -- 平台信息
+    browser        varchar(50)  default ''                not null, -- 浏览器类型
+    version        varchar(50)  default ''                not null, -- 浏览器版本
+    os             varchar(50)  default ''                not null, -- 操作系统
+    arch           varchar(50)  default ''                not null, -- 体系结构信息
+    engine         varchar(50)  default ''                not null, -- 渲染引擎信息
+    engine_details varchar(50)  default ''                not null, -- 渲染引擎详细信息
+    extra          varchar(50)  default ''                not null, -- 其他信息（可选）
+    status         smallint     default 0                 not null, -- 登录状态(0:失败,1:成功)
+    msg            varchar(255) default ''                not null, -- 提示消息
+    login_time     datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null  -- 访问时间
+);
+
+-- sys_login_log: 系统访问记录
+
+create table sys_operate_log
+(
+    id               integer primary key autoincrement,
+    title            varchar(50)   default '',                       -- 模块标题
+    business_type    smallint      default 0,                        -- 业务类型（0其它 1新增 2修改 3删除）
+    method           varchar(200)  default '',                       -- 方法名称
+    request_method   varchar(10)   default '',                       -- 请求方式
+    operator_type    smallint      default 0,                        -- 操作类别（0其它 1后台用户 2手机端用户）
+    operate_name     varchar(50)   default '',                       -- 操作人员
+    dept_name        varchar(50)   default '',                       -- 部门名称
+    operate_url      varchar(255)  default '',                       -- 请求URL
+    operate_ip       varchar(128)  default '',                       -- 主机地址
+    operate_location varchar(255)  default '',                       -- 操作地点
+    operate_param    varchar(2000) default '',                       -- 请求参数
+    json_result      varchar(2000) default '',                       -- 返回参数
+    status           smallint      default 0,                        -- 操作状态(0:异常,正常)
+    error_msg        varchar(2000) default '',                       -- 错误消息
+    operate_time     datetime      default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 操作时间
+    cost_time        bigint        default 0                         -- 消耗时间
+);
+
+-- sys_operate_log: 操作日志记录
@@ -0,0 +1,12 @@
+-- 删除初始数据(按初始数据的id删除,不影响后来新增的数据)
+
+delete from sys_notice where id in (1, 2);
+delete from sys_dict_data where id between 1 and 5;
+delete from sys_dict_type where id in (1, 2);
+delete from sys_user_post where user_id in (1, 2);
+delete from sys_post where id between 1 and 4;
+delete from sys_dept where id between 1 and 10;
+delete from sys_menu where id between 1 and 87;
+delete from sys_user_role where user_id = 1 and role_id = 1;
+delete from sys_role where id in (1, 2, 3);
+delete from sys_user where id in (1, 2);
@@ -0,0 +1,41 @@
+-- 删除账号安全的表,字段和按钮,恢复手机号码的唯一约束(存在重复的手机号码时需要先修改)
+
+delete from sys_role_menu where menu_id between 88 and 98;
+delete from sys_menu where id between 88 and 98;
+
+drop table if exists sys_api_token;
+drop table if exists sys_user_password_history;
+drop table if exists sys_user_identity;
+drop table if exists sys_user_mfa;
+drop table if exists sys_user_session;
+
+alter table sys_operate_log drop column impersonate_name;
+
+create table sys_user_new
+(
+    id              integer primary key autoincrement,
+    mobile          char(11)     default ''                not null, -- 手机号码
+    user_name       varchar(50)                            not null, -- 用户账号
+    nick_name       varchar(30)                            not null, -- 用户昵称
+    user_type       varchar(2)   default '00'              not null, -- 用户类型（00系统用户）
+    avatar          varchar(100) default ''                not null, -- 头像路径
+    email           varchar(50)  default ''                not null, -- 用户邮箱
+    password        varchar(64)                            not null, -- 密码
+    status          smallint     default 1                 not null, -- 状态(1:正常，0:禁用)
+    dept_id         bigint       default 1                 not null, -- 部门ID
+    login_ip        varchar(128) default ''                not null, -- 最后登录IP
+    login_date      datetime ,                                       -- 最后登录时间
+    login_browser   varchar(50)  default ''                not null, -- 浏览器类型
+    login_os        varchar(50)  default ''                not null, -- 操作系统
+    pwd_update_date datetime ,                                       -- 密码最后更新时间
+    remark          varchar(255) null,                               -- 备注
+    del_flag        smallint     default 1                 not null, -- 删除标志（0代表删除 1代表存在）
+    create_time     datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 创建时间
+    update_time     datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 修改时间
+    constraint AK_phone unique (mobile)
+);
+
+insert into sys_user_new (id, mobile, user_name, nick_name, user_type, avatar, email, password, status, dept_id, login_ip, login_date, login_browser, login_os, pwd_update_date, remark, del_flag, create_time, update_time)
+select id, mobile, user_name, nick_name, user_type, avatar, email, password, status, dept_id, login_ip, login_date, login_browser, login_os, pwd_update_date, remark, del_flag, create_time, update_time from sys_user;
+drop table sys_user;
+alter table sys_user_new rename to sys_user;
@@ -0,0 +1,130 @@
+-- 账号安全: 在线会话,两步验证,第三方身份,历史密码,API令牌,模拟登录和数据权限(初始表结构之后新增的表和字段)
+
+-- sqlite不支持删除约束,重建用户表(手机号码不再唯一,新增认证方式)
+create table sys_user_new
+(
+    id              integer primary key autoincrement,
+    mobile          char(11)     default ''                not null, -- 手机号码(单点登录自动创建的用户可以为空)
+    user_name       varchar(50)                            not null, -- 用户账号
+    nick_name       varchar(30)                            not null, -- 用户昵称
+    user_type       varchar(2)   default '00'              not null, -- 用户类型（00系统用户）
+    avatar          varchar(100) default ''                not null, -- 头像路径
+    email           varchar(50)  default ''                not null, -- 用户邮箱
+    password        varchar(64)                            not null, -- 密码
+    auth_source     varchar(20)  default ''                not null, -- 认证方式(local:本地密码,ldap:LDAP,oidc:单点登录)
+    status          smallint     default 1                 not null, -- 状态(1:正常，0:禁用)
+    dept_id         bigint       default 1                 not null, -- 部门ID
+    login_ip        varchar(128) default ''                not null, -- 最后登录IP
+    login_date      datetime ,                                       -- 最后登录时间
+    login_browser   varchar(50)  default ''                not null, -- 浏览器类型
+    login_os        varchar(50)  default ''                not null, -- 操作系统
+    pwd_update_date datetime ,                                       -- 密码最后更新时间
+    remark          varchar(255) null,                               -- 备注
+    del_flag        smallint     default 1                 not null, -- 删除标志（0代表删除 1代表存在）
+    create_time     datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 创建时间
+    update_time     datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null  -- 修改时间
+);
+
+insert into sys_user_new (id, mobile, user_name, nick_name, user_type, avatar, email, password, status, dept_id, login_ip, login_date, login_browser, login_os, pwd_update_date, remark, del_flag, create_time, update_time)
+select id, mobile, user_name, nick_name, user_type, avatar, email, password, status, dept_id, login_ip, login_date, login_browser, login_os, pwd_update_date, remark, del_flag, create_time, update_time from sys_user;
+drop table sys_user;
+alter table sys_user_new rename to sys_user;
+create index idx_mobile on sys_user (mobile);
+
+alter table sys_operate_log add column impersonate_name varchar(50) default ''; -- 模拟登录的管理员(管理员模拟该用户操作时记录)
+
+create table sys_user_session
+(
+    id               integer primary key autoincrement,
+    session_id       varchar(64)  default ''                not null, -- 会话编号
+    user_id          bigint                                 not null, -- 用户ID
+    user_name        varchar(50)  default ''                not null, -- 用户账号
+    ipaddr           varchar(128) default ''                not null, -- 登录IP地址
+    login_location   varchar(255) default ''                not null, -- 登录地点
+    browser          varchar(50)  default ''                not null, -- 浏览器类型
+    os               varchar(50)  default ''                not null, -- 操作系统
+    status           smallint     default 1                 not null, -- 会话状态(0:已下线,1:在线)
+    login_time       datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 登录时间
+    last_access_time datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 最后访问时间
+    expire_time      datetime                               not null, -- 过期时间
+    constraint AK_session_id unique (session_id)
+);
+
+create index idx_user_id on sys_user_session (user_id);
+
+-- sys_user_session: 在线用户会话
+
+create table sys_user_mfa
+(
+    id             integer primary key autoincrement,
+    user_id        bigint                                 not null, -- 用户ID
+    secret         varchar(64)  default ''                not null, -- TOTP密钥(base32)
+    status         smallint     default 0                 not null, -- 状态(0:待确认,1:已启用)
+    recovery_codes text                                   not null, -- 恢复码哈希(多个用逗号分隔,使用后删除)
+    last_used_step bigint       default 0                 not null, -- 最后使用的动态码时间步(防止动态码重放)
+    create_time    datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 创建时间
+    update_time    datetime                               null,     -- 修改时间
+    constraint AK_user_id unique (user_id)
+);
+
+-- sys_user_mfa: 用户两步验证
+
+create table sys_user_identity
+(
+    id          integer primary key autoincrement,
+    user_id     bigint                                 not null, -- 用户ID
+    issuer      varchar(255)                           not null, -- 身份提供方(ID token的iss)
+    subject     varchar(255)                           not null, -- 用户在身份提供方的唯一标识(ID token的sub)
+    email       varchar(50)  default ''                not null, -- 绑定时的邮箱
+    create_time datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 创建时间
+    update_time datetime                               null,     -- 修改时间
+    constraint AK_issuer_subject unique (issuer, subject)
+);
+
+-- sys_user_identity: 用户第三方身份(单点登录绑定)
+
+create table sys_user_password_history
+(
+    id          integer primary key autoincrement,
+    user_id     bigint                                 not null, -- 用户ID
+    password    varchar(255)                           not null, -- 密码哈希
+    create_time datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null  -- 创建时间
+);
+
+create index idx_password_history_user_id on sys_user_password_history (user_id);
+
+-- sys_user_password_history: 用户历史密码
+
+create table sys_api_token
+(
+    id             integer primary key autoincrement,
+    user_id        bigint                                 not null, -- 所属用户ID
+    name           varchar(50)                            not null, -- 令牌名称
+    token_prefix   varchar(20)                            not null, -- 令牌标识(令牌的前缀部分,明文保存)
+    token_hash     char(64)                               not null, -- 令牌哈希(sha256)
+    permissions    text                                   not null, -- 令牌的权限(所属用户权限的子集,多个用逗号分隔)
+    status         smallint     default 1                 not null, -- 状态(0:已吊销,1:正常)
+    expire_time    datetime                               null,     -- 过期时间(为空表示永久有效)
+    last_used_time datetime                               null,     -- 最后使用时间
+    last_used_ip   varchar(128) default ''                not null, -- 最后使用IP
+    create_time    datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 创建时间
+    update_time    datetime                               null,     -- 修改时间
+    constraint AK_token_prefix unique (token_prefix)
+);
+
+create index idx_api_token_user_id on sys_api_token (user_id);
+
+-- sys_api_token: API令牌
+
+-- 在线用户,解锁,两步验证,API令牌,模拟登录和角色数据权限的按钮
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (88, '在线用户', 2, 1, 11, 72, '/log/online', '', 'UserSwitchOutlined', '在线用户管理');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (89, '强制下线会话', 3, 1, 1, 88, '', '/api/system/online/forceLogout', '', '强制下线会话');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (90, '强制下线用户', 3, 1, 2, 88, '', '/api/system/online/forceLogoutUser', '', '强制下线用户的全部会话');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (91, '查询在线用户详情', 3, 1, 3, 88, '', '/api/system/online/queryOnlineDetail', '', '查询在线用户详情');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (92, '查询在线用户列表', 3, 1, 4, 88, '', '/api/system/online/queryOnlineList', '', '查询在线用户列表');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (93, '解锁用户', 3, 1, 11, 3, '', '/api/system/user/unlockUser', '', '解锁登录失败次数过多被锁定的用户或者IP');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (94, '重置用户两步验证', 3, 1, 12, 3, '', '/api/system/user/resetUserMfa', '', '用户丢失身份验证器和恢复码时重置两步验证');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (95, '吊销用户API令牌', 3, 1, 13, 3, '', '/api/system/user/revokeUserApiToken', '', '吊销用户的全部API令牌');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (96, '模拟登录用户', 3, 1, 14, 3, '', '/api/system/user/impersonateUser', '', '以用户的身份登录,排查用户看到的菜单和数据');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (97, '查询角色数据权限', 3, 1, 9, 15, '', '/api/system/role/queryRoleDept', '', '查询角色数据权限');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (98, '更新角色数据权限', 3, 1, 9, 15, '', '/api/system/role/updateRoleDept', '', '更新角色数据权限');
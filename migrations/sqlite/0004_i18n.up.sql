@@ -0,0 +1,11 @@
+-- 多语言: 用户的语言偏好和字典标签的多语言翻译
+
+alter table sys_user add column locale varchar(10) default '' not null; -- 语言偏好(zh-CN, en-US),为空时根据请求头Accept-Language选择语言
+alter table sys_dict_data add column label_i18n varchar(500) default '' not null; -- 字典标签的多语言翻译(json,如: {"en-US":"Male"}),没有对应语言时使用dict_label
+
+-- 初始字典数据的英文标签
+update sys_dict_data set label_i18n = '{"en-US":"Male"}' where dict_type = 'sys_user_sex' and dict_value = '0';
+update sys_dict_data set label_i18n = '{"en-US":"Female"}' where dict_type = 'sys_user_sex' and dict_value = '1';
+update sys_dict_data set label_i18n = '{"en-US":"Unknown"}' where dict_type = 'sys_user_sex' and dict_value = '2';
+update sys_dict_data set label_i18n = '{"en-US":"Notice"}' where dict_type = 'sys_notice_type' and dict_value = '1';
+update sys_dict_data set label_i18n = '{"en-US":"Announcement"}' where dict_type = 'sys_notice_type' and dict_value = '2';
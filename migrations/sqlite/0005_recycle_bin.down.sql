@@ -0,0 +1,24 @@
+-- 删除回收站的按钮,恢复角色名称的唯一约束(存在同名的角色时需要先彻底删除回收站中的角色)
+
+delete from sys_role_menu where menu_id between 99 and 107;
+delete from sys_menu where id between 99 and 107;
+
+create table sys_role_new
+(
+    id          integer primary key autoincrement,
+    role_name   varchar(50)                            not null, -- 名称
+    role_key    varchar(100) default ''                not null, -- 角色权限字符串
+    data_scope  smallint     default 1                 not null, -- 数据范围（1：全部数据权限 2：自定数据权限 3：本部门数据权限 4：本部门及以下数据权限 5：仅本人数据权限）
+    status      smallint     default 1                 not null, -- 状态(1:正常，0:禁用)
+    remark      varchar(255)                           not null, -- 备注
+    del_flag    smallint     default 1                 not null, -- 删除标志（0代表删除 1代表存在）
+    create_time datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 创建时间
+    update_time datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 修改时间
+    constraint role_name unique (role_name)
+);
+
+insert into sys_role_new (id, role_name, role_key, data_scope, status, remark, del_flag, create_time, update_time)
+select id, role_name, role_key, data_scope, status, remark, del_flag, create_time, update_time from sys_role;
+drop table sys_role;
+alter table sys_role_new rename to sys_role;
+create index name_status_index on sys_role (role_name, status);
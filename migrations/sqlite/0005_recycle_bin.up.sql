@@ -0,0 +1,32 @@
+-- 回收站: 用户,角色和部门改为逻辑删除(del_flag = 0),角色名称只需要和未删除的角色不重复
+
+-- sqlite不支持删除约束,重建角色表
+create table sys_role_new
+(
+    id          integer primary key autoincrement,
+    role_name   varchar(50)                            not null, -- 名称
+    role_key    varchar(100) default ''                not null, -- 角色权限字符串
+    data_scope  smallint     default 1                 not null, -- 数据范围（1：全部数据权限 2：自定数据权限 3：本部门数据权限 4：本部门及以下数据权限 5：仅本人数据权限）
+    status      smallint     default 1                 not null, -- 状态(1:正常，0:禁用)
+    remark      varchar(255)                           not null, -- 备注
+    del_flag    smallint     default 1                 not null, -- 删除标志（0代表删除 1代表存在）
+    create_time datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null, -- 创建时间
+    update_time datetime     default (strftime('%Y-%m-%dT%H:%M:%S', 'now', 'localtime')) not null -- 修改时间
+);
+
+insert into sys_role_new (id, role_name, role_key, data_scope, status, remark, del_flag, create_time, update_time)
+select id, role_name, role_key, data_scope, status, remark, del_flag, create_time, update_time from sys_role;
+drop table sys_role;
+alter table sys_role_new rename to sys_role;
+create index name_status_index on sys_role (role_name, status);
+
+-- 回收站的按钮
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (99, '查询已删除用户', 3, 1, 15, 3, '', '/api/system/user/queryDeletedUserList', '', '查询回收站中的用户');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (100, '恢复已删除用户', 3, 1, 16, 3, '', '/api/system/user/restoreUser', '', '从回收站恢复用户');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (101, '彻底删除用户', 3, 1, 17, 3, '', '/api/system/user/purgeUser', '', '彻底删除回收站中的用户');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (102, '查询已删除角色', 3, 1, 10, 15, '', '/api/system/role/queryDeletedRoleList', '', '查询回收站中的角色');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (103, '恢复已删除角色', 3, 1, 11, 15, '', '/api/system/role/restoreRole', '', '从回收站恢复角色');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (104, '彻底删除角色', 3, 1, 12, 15, '', '/api/system/role/purgeRole', '', '彻底删除回收站中的角色');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (105, '查询已删除部门', 3, 1, 7, 37, '', '/api/system/dept/queryDeletedDeptList', '', '查询回收站中的部门');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (106, '恢复已删除部门', 3, 1, 8, 37, '', '/api/system/dept/restoreDept', '', '从回收站恢复部门');
+INSERT INTO sys_menu (id, menu_name, menu_type, status, sort, parent_id, menu_url, api_url, menu_icon, remark) VALUES (107, '彻底删除部门', 3, 1, 9, 37, '', '/api/system/dept/purgeDept', '', '彻底删除回收站中的部门');
@@ -0,0 +1 @@
+-- sqlite不限制varchar的长度,不需要修改字段
@@ -0,0 +1,2 @@
+-- 密码改为保存Argon2id哈希(PHC格式约97个字符)
+-- sqlite不限制varchar的长度,不需要修改字段
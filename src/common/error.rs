@@ -1,5 +1,10 @@
+use crate::common::error_code::ErrorCode;
+use crate::common::i18n::{current_locale, t, translate, Locale};
+use crate::common::validate::FieldError;
+use crate::middleware::request_id::current_request_id;
 use actix_web::http::StatusCode;
 use actix_web::HttpResponse;
+use std::borrow::Cow;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -18,24 +23,162 @@ pub enum AppError {
     #[error("数据库错误: {0}")]
     DbError(#[from] rbatis::Error),
 
-    #[error("业务异常: {0}")]
-    BusinessError(&'static str),
+    #[error("业务异常: {msg}")]
+    BusinessError { code: ErrorCode, msg: Cow<'static, str>, args: Vec<String> },
+
+    #[error("密码加密异常: {0}")]
+    PasswordError(String),
+
+    #[error("密码不符合要求: {0}")]
+    PasswordPolicyError(String),
+
+    #[error("图片生成异常: {0}")]
+    ImageError(String),
+
+    #[error("认证服务异常: {0}")]
+    AuthError(String),
+
+    #[error("配置错误: {0}")]
+    ConfigError(String),
+
+    #[error("命令参数错误: {0}")]
+    CommandError(String),
+
+    #[error("参数校验失败: {}", .0.iter().map(|x| format!("{} {}", x.field, x.msg).trim().to_string()).collect::<Vec<_>>().join(", "))]
+    ValidationError(Vec<FieldError>),
 }
 pub type AppResult<T> = Result<T, AppError>;
 
+impl From<ErrorCode> for AppError {
+    fn from(code: ErrorCode) -> Self {
+        AppError::BusinessError {
+            code,
+            msg: Cow::Owned(code.msg()),
+            args: vec![],
+        }
+    }
+}
+
+impl AppError {
+    /// 业务异常,使用更具体的中文错误信息(其他语言使用错误码对应的错误信息)
+    pub fn business(code: ErrorCode, msg: impl Into<Cow<'static, str>>) -> Self {
+        AppError::BusinessError { code, msg: msg.into(), args: vec![] }
+    }
+
+    /// 业务异常,错误信息带参数(语言包中的{0},{1}依次替换为参数),如: AppError::with_args(ErrorCode::DuplicateMobile, &[&mobile])
+    pub fn with_args(code: ErrorCode, args: &[&str]) -> Self {
+        let args = args.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        let msg = translate(Locale::default(), code.as_str(), &args);
+        AppError::BusinessError { code, msg: Cow::Owned(msg), args }
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AppError::BusinessError { code, .. } => *code,
+            AppError::JwtTokenError(_) => ErrorCode::TokenInvalid,
+            AppError::DbError(_) => ErrorCode::DatabaseError,
+            AppError::PasswordPolicyError(_) => ErrorCode::PasswordPolicyViolation,
+            AppError::AuthError(_) => ErrorCode::AuthFailed,
+            AppError::ConfigError(_) | AppError::CommandError(_) => ErrorCode::ConfigError,
+            AppError::ValidationError(_) => ErrorCode::ValidationFailed,
+            AppError::DiskCacheRead { .. } | AppError::PasswordError(_) | AppError::ImageError(_) => ErrorCode::InternalError,
+        }
+    }
+
+    // 返回给前端的错误信息(按当前请求的语言),服务端内部的错误(数据库,加密等)只返回错误码对应的错误信息,详细的错误信息记录到日志
+    fn client_msg(&self) -> String {
+        let locale = current_locale();
+        let zh = locale == Locale::default();
+        match self {
+            AppError::BusinessError { msg, .. } if zh => msg.to_string(),
+            AppError::BusinessError { code, args, .. } => translate(locale, code.as_str(), args),
+            AppError::JwtTokenError(msg) if zh && !msg.is_empty() => msg.clone(),
+            AppError::AuthError(_) if zh => self.to_string(),
+            AppError::PasswordPolicyError(msg) => format!("{}: {}", t(self.code().as_str()), msg),
+            AppError::ValidationError(errors) => {
+                let fields = errors.iter().map(|x| format!("{} {}", x.field, x.msg).trim().to_string()).collect::<Vec<_>>();
+                format!("{}: {}", t(self.code().as_str()), fields.join(", "))
+            }
+            AppError::ConfigError(_) | AppError::CommandError(_) => self.to_string(),
+            _ => t(self.code().as_str()),
+        }
+    }
+}
+
 impl actix_web::error::ResponseError for AppError {
+    fn status_code(&self) -> StatusCode {
+        self.code().status()
+    }
+
     fn error_response(&self) -> HttpResponse {
-        // let status = match *self {
-        //     AppError::NotFound => StatusCode::NOT_FOUND,
-        //     AppError::InternalServerError => StatusCode::INTERNAL_SERVER_ERROR,
-        // };
-
-        // 返回JSON格式的错误信息
-        let body = serde_json::json!({
-            "msg": self.to_string(),
-            "code": 1,
+        let code = self.code();
+        let request_id = current_request_id().unwrap_or_default();
+        if code.status().is_server_error() {
+            log::error!("request {} failed: {}", request_id, self);
+        }
+
+        // 返回JSON格式的错误信息: code为2时前端需要重新登录,errorCode为错误码,requestId用于在日志中查找该请求
+        // 参数校验失败时data为不通过的字段列表,前端据此标记对应的输入框
+        let mut body = serde_json::json!({
+            "msg": self.client_msg(),
+            "code": if code.status() == StatusCode::UNAUTHORIZED { 2 } else { 1 },
+            "errorCode": code.as_str(),
+            "requestId": request_id,
         });
+        if let AppError::ValidationError(errors) = self {
+            body["data"] = serde_json::json!(errors);
+        }
+
+        HttpResponse::build(code.status()).json(body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::error::AppError;
+    use crate::common::error_code::ErrorCode;
+    use crate::common::i18n::{with_locale, Locale};
+    use actix_web::body::to_bytes;
+    use actix_web::http::StatusCode;
+    use actix_web::ResponseError;
+    use serde_json::Value;
+
+    async fn body(err: AppError) -> (StatusCode, Value) {
+        let res = err.error_response();
+        let status = res.status();
+        let bytes = to_bytes(res.into_body()).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[actix_web::test]
+    async fn test_error_response() {
+        let (status, json) = body(AppError::with_args(ErrorCode::DuplicateMobile, &["18613030111"])).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(json["code"], 1);
+        assert_eq!(json["errorCode"], "DUPLICATE_MOBILE");
+        assert_eq!(json["msg"], "手机号码18613030111已存在");
+
+        let (status, json) = body(ErrorCode::TokenInvalid.into()).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(json["code"], 2);
+
+        //数据库错误不返回给前端
+        let (status, json) = body(AppError::DbError(rbatis::Error::from("Duplicate entry 'admin' for key 'sys_user.user_name'"))).await;
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(json["errorCode"], "DATABASE_ERROR");
+        assert_eq!(json["msg"], ErrorCode::DatabaseError.msg());
+    }
+
+    #[actix_web::test]
+    async fn test_error_response_locale() {
+        let (_, json) = with_locale(Locale::EnUs, body(AppError::with_args(ErrorCode::DuplicateMobile, &["18613030111"]))).await;
+        assert_eq!(json["errorCode"], "DUPLICATE_MOBILE");
+        assert_eq!(json["msg"], "Mobile number 18613030111 already exists");
 
-        HttpResponse::build(StatusCode::OK).json(body)
+        //业务异常的中文信息只在中文环境下返回,其他语言使用错误码对应的翻译
+        let (_, json) = with_locale(Locale::EnUs, body(AppError::business(ErrorCode::SuperAdminProtected, "不允许操作超级管理员用户"))).await;
+        assert_eq!(json["msg"], "The super administrator cannot be modified");
+        let (_, json) = body(AppError::business(ErrorCode::SuperAdminProtected, "不允许操作超级管理员用户")).await;
+        assert_eq!(json["msg"], "不允许操作超级管理员用户");
     }
 }
@@ -0,0 +1,117 @@
+use crate::common::i18n::{translate, Locale};
+use actix_web::http::StatusCode;
+
+// 定义错误码: 名称 => (错误码, HTTP状态码),错误信息在locales目录的语言包中,key为错误码
+macro_rules! error_codes {
+    ($($name:ident => ($code:literal, $status:ident),)*) => {
+        /*
+         *错误码,前端根据错误码处理错误(如: 跳转到登录页,标记重复的字段),不依赖错误信息的文字
+         *错误码发布后不能修改,新的错误添加新的错误码
+         *author：刘飞华
+         *date：2026/10/19 14:02:36
+         */
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum ErrorCode {
+            $($name,)*
+        }
+
+        impl ErrorCode {
+            pub const ALL: &'static [ErrorCode] = &[$(ErrorCode::$name,)*];
+
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(ErrorCode::$name => $code,)*
+                }
+            }
+
+            pub fn status(&self) -> StatusCode {
+                match self {
+                    $(ErrorCode::$name => StatusCode::$status,)*
+                }
+            }
+
+            /// 默认语言的错误信息
+            pub fn msg(&self) -> String {
+                translate(Locale::default(), self.as_str(), &[])
+            }
+        }
+    };
+}
+
+error_codes! {
+    //通用
+    BadRequest => ("BAD_REQUEST", BAD_REQUEST),
+    ValidationFailed => ("VALIDATION_FAILED", BAD_REQUEST),
+    Unauthorized => ("UNAUTHORIZED", UNAUTHORIZED),
+    TokenInvalid => ("TOKEN_INVALID", UNAUTHORIZED),
+    SessionOffline => ("SESSION_OFFLINE", UNAUTHORIZED),
+    PermissionDenied => ("PERMISSION_DENIED", FORBIDDEN),
+    DataInUse => ("DATA_IN_USE", CONFLICT),
+    InternalError => ("INTERNAL_ERROR", INTERNAL_SERVER_ERROR),
+    DatabaseError => ("DATABASE_ERROR", INTERNAL_SERVER_ERROR),
+    ConfigError => ("CONFIG_ERROR", INTERNAL_SERVER_ERROR),
+    //登录认证
+    BadCredentials => ("BAD_CREDENTIALS", UNAUTHORIZED),
+    AccountLocked => ("ACCOUNT_LOCKED", FORBIDDEN),
+    CaptchaRequired => ("CAPTCHA_REQUIRED", BAD_REQUEST),
+    CaptchaInvalid => ("CAPTCHA_INVALID", BAD_REQUEST),
+    AuthFailed => ("AUTH_FAILED", UNAUTHORIZED),
+    SsoDisabled => ("SSO_DISABLED", BAD_REQUEST),
+    UserDisabled => ("USER_DISABLED", FORBIDDEN),
+    UserNoPermission => ("USER_NO_PERMISSION", FORBIDDEN),
+    PasswordIncorrect => ("PASSWORD_INCORRECT", BAD_REQUEST),
+    PasswordPolicyViolation => ("PASSWORD_POLICY_VIOLATION", BAD_REQUEST),
+    MfaCodeInvalid => ("MFA_CODE_INVALID", BAD_REQUEST),
+    MfaNotBound => ("MFA_NOT_BOUND", BAD_REQUEST),
+    MfaNotEnabled => ("MFA_NOT_ENABLED", BAD_REQUEST),
+    MfaAlreadyEnabled => ("MFA_ALREADY_ENABLED", CONFLICT),
+    MfaRequired => ("MFA_REQUIRED", FORBIDDEN),
+    ImpersonateDenied => ("IMPERSONATE_DENIED", FORBIDDEN),
+    SuperAdminProtected => ("SUPER_ADMIN_PROTECTED", FORBIDDEN),
+    ApiTokenLimitExceeded => ("API_TOKEN_LIMIT_EXCEEDED", CONFLICT),
+    //数据不存在
+    UserNotFound => ("USER_NOT_FOUND", NOT_FOUND),
+    RoleNotFound => ("ROLE_NOT_FOUND", NOT_FOUND),
+    DeptNotFound => ("DEPT_NOT_FOUND", NOT_FOUND),
+    MenuNotFound => ("MENU_NOT_FOUND", NOT_FOUND),
+    PostNotFound => ("POST_NOT_FOUND", NOT_FOUND),
+    DictTypeNotFound => ("DICT_TYPE_NOT_FOUND", NOT_FOUND),
+    DictDataNotFound => ("DICT_DATA_NOT_FOUND", NOT_FOUND),
+    NoticeNotFound => ("NOTICE_NOT_FOUND", NOT_FOUND),
+    LoginLogNotFound => ("LOGIN_LOG_NOT_FOUND", NOT_FOUND),
+    OperateLogNotFound => ("OPERATE_LOG_NOT_FOUND", NOT_FOUND),
+    SessionNotFound => ("SESSION_NOT_FOUND", NOT_FOUND),
+    //数据重复
+    DuplicateUserName => ("DUPLICATE_USER_NAME", CONFLICT),
+    DuplicateMobile => ("DUPLICATE_MOBILE", CONFLICT),
+    DuplicateEmail => ("DUPLICATE_EMAIL", CONFLICT),
+    DuplicateRoleName => ("DUPLICATE_ROLE_NAME", CONFLICT),
+    DuplicateRoleKey => ("DUPLICATE_ROLE_KEY", CONFLICT),
+    DuplicateMenuName => ("DUPLICATE_MENU_NAME", CONFLICT),
+    DuplicateMenuUrl => ("DUPLICATE_MENU_URL", CONFLICT),
+    DuplicateDeptName => ("DUPLICATE_DEPT_NAME", CONFLICT),
+    DuplicatePostName => ("DUPLICATE_POST_NAME", CONFLICT),
+    DuplicatePostCode => ("DUPLICATE_POST_CODE", CONFLICT),
+    DuplicateDictType => ("DUPLICATE_DICT_TYPE", CONFLICT),
+    DuplicateDictLabel => ("DUPLICATE_DICT_LABEL", CONFLICT),
+    DuplicateDictValue => ("DUPLICATE_DICT_VALUE", CONFLICT),
+    DuplicateNoticeTitle => ("DUPLICATE_NOTICE_TITLE", CONFLICT),
+    DeptDisabled => ("DEPT_DISABLED", CONFLICT),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::error_code::ErrorCode;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_error_codes() {
+        let mut codes = HashSet::new();
+        for code in ErrorCode::ALL {
+            assert!(codes.insert(code.as_str()), "duplicate error code {}", code.as_str());
+            assert!(code.as_str().chars().all(|c| c.is_ascii_uppercase() || c == '_'));
+            assert!(code.status().is_client_error() || code.status().is_server_error());
+            assert_ne!(code.msg(), code.as_str());
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::LazyLock;
+
+/*
+ *语言,新增语言时在locales目录下添加对应的文件并在这里注册
+ *author：刘飞华
+ *date：2026/10/19 16:10:42
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Locale {
+    #[default]
+    ZhCn, //简体中文(默认)
+    EnUs, //英文
+}
+
+impl Locale {
+    pub const ALL: &'static [Locale] = &[Locale::ZhCn, Locale::EnUs];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::ZhCn => "zh-CN",
+            Locale::EnUs => "en-US",
+        }
+    }
+
+    /// 解析语言标签(不区分大小写,支持 zh, zh-CN, zh_CN, en, en-US 等)
+    pub fn parse(tag: &str) -> Option<Locale> {
+        let tag = tag.trim().replace('_', "-").to_lowercase();
+        match tag.split('-').next() {
+            Some("zh") => Some(Locale::ZhCn),
+            Some("en") => Some(Locale::EnUs),
+            _ => None,
+        }
+    }
+
+    /// 根据请求头Accept-Language协商语言(按q值从高到低选择第一个支持的语言),没有支持的语言时使用默认语言
+    pub fn negotiate(accept_language: &str) -> Locale {
+        let mut tags = accept_language
+            .split(',')
+            .filter_map(|item| {
+                let mut parts = item.split(';');
+                let tag = parts.next()?.trim();
+                let q = parts.find_map(|x| x.trim().strip_prefix("q=")).and_then(|x| x.parse::<f32>().ok()).unwrap_or(1.0);
+                Some((tag, q))
+            })
+            .filter(|(_, q)| *q > 0.0)
+            .collect::<Vec<(&str, f32)>>();
+        tags.sort_by(|a, b| b.1.total_cmp(&a.1));
+        tags.iter().find_map(|(tag, _)| Locale::parse(tag)).unwrap_or_default()
+    }
+
+    fn bundle(&self) -> &'static str {
+        match self {
+            Locale::ZhCn => include_str!("../../locales/zh-CN.json"),
+            Locale::EnUs => include_str!("../../locales/en-US.json"),
+        }
+    }
+}
+
+//语言包: 语言 -> (消息key -> 消息)
+static BUNDLES: LazyLock<HashMap<Locale, HashMap<String, String>>> = LazyLock::new(|| {
+    Locale::ALL
+        .iter()
+        .map(|locale| {
+            let messages = serde_json::from_str(locale.bundle()).unwrap_or_else(|e| panic!("invalid locale bundle {}: {}", locale.as_str(), e));
+            (*locale, messages)
+        })
+        .collect()
+});
+
+tokio::task_local! {
+    static CURRENT_LOCALE: Locale;
+}
+
+/// 当前请求的语言(不在请求中调用时,如命令行,返回默认语言)
+pub fn current_locale() -> Locale {
+    CURRENT_LOCALE.try_with(|x| *x).unwrap_or_default()
+}
+
+/// 在指定的语言下执行
+pub async fn with_locale<F: Future>(locale: Locale, f: F) -> F::Output {
+    CURRENT_LOCALE.scope(locale, f).await
+}
+
+/// 按当前请求的语言翻译消息
+pub fn t(key: &str) -> String {
+    translate(current_locale(), key, &[])
+}
+
+/// 按当前请求的语言翻译带参数的消息,消息中的{0},{1}依次替换为参数
+pub fn t_args(key: &str, args: &[String]) -> String {
+    translate(current_locale(), key, args)
+}
+
+/// 翻译消息,语言包中没有该消息时使用默认语言,都没有时返回key;缺少的参数连同前面的空格一起去掉
+pub fn translate(locale: Locale, key: &str, args: &[String]) -> String {
+    let template = [locale, Locale::default()].iter().find_map(|x| BUNDLES.get(x).and_then(|bundle| bundle.get(key)));
+    let mut msg = match template {
+        Some(template) => template.clone(),
+        None => return key.to_string(),
+    };
+    let mut index = 0;
+    while msg.contains(&format!("{{{}}}", index)) {
+        let placeholder = format!("{{{}}}", index);
+        msg = match args.get(index) {
+            Some(arg) => msg.replace(&placeholder, arg),
+            None => msg.replace(&format!(" {}", placeholder), "").replace(&placeholder, ""),
+        };
+        index += 1;
+    }
+    msg
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::error_code::ErrorCode;
+    use crate::common::i18n::{translate, Locale, BUNDLES};
+
+    #[test]
+    fn test_bundles() {
+        let default = &BUNDLES[&Locale::default()];
+        for locale in Locale::ALL {
+            let bundle = &BUNDLES[locale];
+            assert_eq!(bundle.len(), default.len(), "{} has different keys", locale.as_str());
+            for code in ErrorCode::ALL {
+                assert!(bundle.contains_key(code.as_str()), "{} missing {}", locale.as_str(), code.as_str());
+            }
+        }
+    }
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(Locale::negotiate(""), Locale::ZhCn);
+        assert_eq!(Locale::negotiate("en-US,en;q=0.9"), Locale::EnUs);
+        assert_eq!(Locale::negotiate("fr-FR,en;q=0.8,zh-CN;q=0.9"), Locale::ZhCn);
+        assert_eq!(Locale::negotiate("fr-FR, en-GB;q=0.5"), Locale::EnUs);
+        assert_eq!(Locale::negotiate("en;q=0,zh_TW"), Locale::ZhCn);
+        assert_eq!(Locale::parse("EN_us"), Some(Locale::EnUs));
+        assert_eq!(Locale::parse("ja"), None);
+    }
+
+    #[test]
+    fn test_translate() {
+        let args = vec!["18613030111".to_string()];
+        assert_eq!(translate(Locale::ZhCn, "DUPLICATE_MOBILE", &args), "手机号码18613030111已存在");
+        assert_eq!(translate(Locale::ZhCn, "DUPLICATE_MOBILE", &[]), "手机号码已存在");
+        assert_eq!(translate(Locale::EnUs, "DUPLICATE_MOBILE", &args), "Mobile number 18613030111 already exists");
+        assert_eq!(translate(Locale::EnUs, "DUPLICATE_MOBILE", &[]), "Mobile number already exists");
+        assert_eq!(translate(Locale::EnUs, "OK", &[]), "Success");
+        assert_eq!(translate(Locale::EnUs, "not.exists", &[]), "not.exists");
+    }
+}
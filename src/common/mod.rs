@@ -1,2 +1,5 @@
 pub mod error;
+pub mod error_code;
+pub mod i18n;
 pub mod result;
+pub mod validate;
@@ -1,4 +1,5 @@
 use crate::common::error::AppResult;
+use crate::common::i18n::t;
 use actix_web::web::Json;
 use rbatis::rbdc::DateTime;
 use serde::Serialize;
@@ -22,11 +23,7 @@ pub struct ResponsePage<T> {
 }
 
 pub fn ok_result() -> AppResult<Json<BaseResponse<String>>> {
-    Ok(Json(BaseResponse {
-        msg: "操作成功".to_string(),
-        code: 0,
-        data: None,
-    }))
+    Ok(Json(BaseResponse { msg: t("OK"), code: 0, data: None }))
 }
 
 pub fn ok_result_msg(msg: &str) -> AppResult<Json<BaseResponse<String>>> {
@@ -47,7 +44,7 @@ pub fn err_result_msg(msg: &str) -> AppResult<Json<BaseResponse<String>>> {
 
 pub fn ok_result_page<T>(data: T, total: u64) -> AppResult<Json<ResponsePage<T>>> {
     Ok(Json(ResponsePage {
-        msg: "操作成功".to_string(),
+        msg: t("OK"),
         code: 0,
         success: true,
         data: Some(data),
@@ -56,7 +53,7 @@ pub fn ok_result_page<T>(data: T, total: u64) -> AppResult<Json<ResponsePage<T>>
 }
 pub fn ok_result_data<T>(data: T) -> AppResult<Json<BaseResponse<T>>> {
     Ok(Json(BaseResponse {
-        msg: "操作成功".to_string(),
+        msg: t("OK"),
         code: 0,
         data: Some(data),
     }))
@@ -0,0 +1,278 @@
+use crate::common::error::AppError;
+use crate::common::i18n::{t, t_args, Locale};
+use actix_web::dev::Payload;
+use actix_web::error::JsonPayloadError;
+use actix_web::web::JsonBody;
+use actix_web::{FromRequest, HttpRequest};
+use futures_util::future::LocalBoxFuture;
+use regex::Regex;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::LazyLock;
+use validator::{Validate, ValidateEmail, ValidationError, ValidationErrors, ValidationErrorsKind};
+
+//请求体的最大长度
+pub const JSON_LIMIT: usize = 2 * 1024 * 1024;
+
+//批量操作一次最多的记录数
+pub const MAX_IDS: usize = 1000;
+
+static MOBILE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^1[3-9]\d{9}$").unwrap());
+
+/*
+ *校验不通过的字段
+ *author：刘飞华
+ *date：2026/10/19 11:20:05
+ */
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldError {
+    pub field: String, //字段名(和请求参数的json字段名一致)
+    pub msg: String,   //错误信息
+}
+
+/*
+ *解析json请求参数并按VO上的#[validate]规则校验,校验不通过时返回所有不通过的字段
+ *用法和web::Json一样: item: ValidatedJson<UserReq>, item.0 或者 item.字段
+ *author：刘飞华
+ *date：2026/10/19 11:20:05
+ */
+#[derive(Debug)]
+pub struct ValidatedJson<T>(pub T);
+
+impl<T> ValidatedJson<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for ValidatedJson<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: DeserializeOwned + Validate + 'static> FromRequest for ValidatedJson<T> {
+    type Error = AppError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let body = JsonBody::<T>::new(req, payload, None, true).limit(JSON_LIMIT);
+        Box::pin(async move {
+            let value = body.await.map_err(payload_error)?;
+            value.validate()?;
+            Ok(ValidatedJson(value))
+        })
+    }
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        AppError::ValidationError(field_errors(&errors))
+    }
+}
+
+/// 把校验结果转换成字段错误列表(按字段名排序,字段名转换成json中的驼峰格式)
+pub fn field_errors(errors: &ValidationErrors) -> Vec<FieldError> {
+    let mut list = Vec::new();
+    for (field, kind) in errors.errors() {
+        if let ValidationErrorsKind::Field(items) = kind {
+            for err in items {
+                list.push(FieldError {
+                    field: camel_case(field),
+                    msg: message(err),
+                });
+            }
+        }
+    }
+    list.sort_by(|a, b| a.field.cmp(&b.field));
+    list
+}
+
+// json解析失败: 缺少字段时返回该字段,其他错误(类型不对,格式不对)返回解析的错误信息
+fn payload_error(err: JsonPayloadError) -> AppError {
+    let error = match &err {
+        JsonPayloadError::Deserialize(e) => match e.to_string().split('`').nth(1) {
+            Some(field) if e.to_string().starts_with("missing field") => FieldError {
+                field: field.to_string(),
+                msg: t("validation.required"),
+            },
+            _ => FieldError {
+                field: "".to_string(),
+                msg: t_args("validation.format", &[e.to_string()]),
+            },
+        },
+        JsonPayloadError::ContentType => FieldError {
+            field: "".to_string(),
+            msg: t("validation.content_type"),
+        },
+        e => FieldError {
+            field: "".to_string(),
+            msg: t_args("validation.payload", &[e.to_string()]),
+        },
+    };
+    AppError::ValidationError(vec![error])
+}
+
+// 规则上没有指定message时按规则和当前请求的语言生成错误信息(语言包中的key为validation.规则)
+fn message(err: &ValidationError) -> String {
+    if let Some(msg) = &err.message {
+        return msg.to_string();
+    }
+    let param = |key: &str| err.params.get(key).map(|x| x.to_string());
+    let (key, args) = match (err.code.as_ref(), param("min"), param("max")) {
+        ("length", Some(min), Some(max)) => ("length_between", vec![min, max]),
+        ("length", Some(min), None) => ("length_min", vec![min]),
+        ("length", None, Some(max)) => ("length_max", vec![max]),
+        ("range", Some(min), Some(max)) => ("range_between", vec![min, max]),
+        ("range", Some(min), None) => ("range_min", vec![min]),
+        ("range", None, Some(max)) => ("range_max", vec![max]),
+        (code, _, max) => (code, max.into_iter().collect()),
+    };
+    let key = format!("validation.{}", key);
+    match t_args(&key, &args) {
+        msg if msg == key => t_args("validation.invalid", &[err.code.to_string()]),
+        msg => msg,
+    }
+}
+
+fn camel_case(field: &str) -> String {
+    let mut result = String::new();
+    let mut upper = false;
+    for c in field.chars() {
+        match c {
+            '_' => upper = true,
+            c if upper => {
+                result.extend(c.to_uppercase());
+                upper = false;
+            }
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// 批量操作的id: 至少一条,最多MAX_IDS条,并且都是有效的id
+pub fn validate_ids(ids: &[i64]) -> Result<(), ValidationError> {
+    if ids.is_empty() {
+        return Err(ValidationError::new("ids_empty"));
+    }
+    if ids.len() > MAX_IDS {
+        let mut err = ValidationError::new("ids_too_many");
+        err.add_param("max".into(), &MAX_IDS);
+        return Err(err);
+    }
+    if ids.iter().any(|x| *x <= 0) {
+        return Err(ValidationError::new("ids_invalid"));
+    }
+    Ok(())
+}
+
+/// 手机号码: 可以为空(单点登录自动创建的用户没有手机号码),不为空时必须是11位的手机号码
+pub fn validate_mobile(mobile: &str) -> Result<(), ValidationError> {
+    match mobile.is_empty() || MOBILE_REGEX.is_match(mobile) {
+        true => Ok(()),
+        false => Err(ValidationError::new("mobile")),
+    }
+}
+
+/// 邮箱: 可以为空,不为空时必须是有效的邮箱
+pub fn validate_email(email: &str) -> Result<(), ValidationError> {
+    match email.is_empty() || email.validate_email() {
+        true => Ok(()),
+        false => Err(ValidationError::new("email")),
+    }
+}
+
+/// 语言: 可以为空(为空时根据请求头Accept-Language选择语言),不为空时必须是支持的语言
+pub fn validate_locale(locale: &str) -> Result<(), ValidationError> {
+    match locale.is_empty() || Locale::parse(locale).is_some() {
+        true => Ok(()),
+        false => Err(ValidationError::new("locale")),
+    }
+}
+
+/// 多语言标签: 可以为空,不为空时必须是 语言 -> 标签 的json对象,如: {"en-US":"Male"}
+pub fn validate_label_i18n(labels: &str) -> Result<(), ValidationError> {
+    if labels.is_empty() {
+        return Ok(());
+    }
+    match serde_json::from_str::<HashMap<String, String>>(labels) {
+        Ok(map) if map.keys().all(|x| Locale::parse(x).is_some()) => Ok(()),
+        _ => Err(ValidationError::new("label_i18n")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::validate::{camel_case, field_errors, validate_email, validate_ids, validate_label_i18n, validate_locale, validate_mobile, FieldError};
+    use validator::Validate;
+
+    #[derive(Validate)]
+    struct TestReq {
+        #[validate(length(min = 1, max = 5))]
+        user_name: String,
+        #[validate(range(min = 1, max = 100))]
+        page_size: u64,
+        #[validate(custom(function = "validate_ids"))]
+        ids: Vec<i64>,
+    }
+
+    #[test]
+    fn test_field_errors() {
+        let req = TestReq {
+            user_name: "".to_string(),
+            page_size: 0,
+            ids: vec![],
+        };
+        let errors = field_errors(&req.validate().unwrap_err());
+        assert_eq!(
+            errors,
+            vec![
+                FieldError {
+                    field: "ids".to_string(),
+                    msg: "至少选择一条记录".to_string()
+                },
+                FieldError {
+                    field: "pageSize".to_string(),
+                    msg: "必须在1到100之间".to_string()
+                },
+                FieldError {
+                    field: "userName".to_string(),
+                    msg: "长度必须在1到5之间".to_string()
+                },
+            ]
+        );
+        assert!(TestReq {
+            user_name: "admin".to_string(),
+            page_size: 10,
+            ids: vec![1]
+        }
+        .validate()
+        .is_ok());
+    }
+
+    #[test]
+    fn test_validators() {
+        assert_eq!(camel_case("dept_id"), "deptId");
+        assert_eq!(camel_case("ids"), "ids");
+        assert!(validate_ids(&[1, 2]).is_ok());
+        assert!(validate_ids(&[1, 0]).is_err());
+        assert!(validate_ids(&vec![1; 1001]).is_err());
+        assert!(validate_mobile("").is_ok());
+        assert!(validate_mobile("18613030111").is_ok());
+        assert!(validate_mobile("1861303011").is_err());
+        assert!(validate_mobile("28613030111").is_err());
+        assert!(validate_email("").is_ok());
+        assert!(validate_email("admin@example.com").is_ok());
+        assert!(validate_email("admin").is_err());
+        assert!(validate_locale("en-US").is_ok());
+        assert!(validate_locale("ja-JP").is_err());
+        assert!(validate_label_i18n(r#"{"en-US":"Male"}"#).is_ok());
+        assert!(validate_label_i18n(r#"{"xx":"Male"}"#).is_err());
+        assert!(validate_label_i18n("Male").is_err());
+    }
+}
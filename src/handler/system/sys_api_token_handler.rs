@@ -0,0 +1,175 @@
+use crate::common::error::{AppError, AppResult};
+use crate::common::error_code::ErrorCode;
+use crate::common::result::{ok_result, ok_result_data};
+use crate::common::validate::ValidatedJson;
+use crate::handler::system::sys_user_handler::query_user_permissions;
+use crate::model::system::sys_api_token_model::{revoke_api_tokens, update_last_used, ApiToken};
+use crate::model::system::sys_user_model::User;
+use crate::utils::api_token_util::{generate_api_token, hash_api_token, parse_api_token};
+use crate::utils::db::last_insert_id;
+use crate::utils::jwt_util::{JwtToken, API_TOKEN};
+use crate::utils::password_util::constant_time_eq;
+use crate::vo::system::sys_api_token_vo::*;
+use crate::AppState;
+use actix_web::{post, web, HttpMessage, HttpRequest, Responder};
+use rbatis::rbdc::DateTime;
+use rbs::value;
+use std::time::Duration;
+
+/*
+ *创建API令牌(令牌的权限只能是当前用户权限的子集)
+ *author：刘飞华
+ *date：2026/10/18 22:48:15
+ */
+#[post("/system/apiToken/addApiToken")]
+pub async fn add_api_token(http_req: HttpRequest, item: ValidatedJson<AddApiTokenReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("add api_token params: {:?}", &item);
+    let rb = &data.batis;
+    let config = &data.api_token_config;
+    let user_id = current_user_id(&http_req)?;
+
+    let name = item.name.trim();
+    if name.is_empty() {
+        return Err(AppError::business(ErrorCode::BadRequest, "令牌名称不能为空"));
+    }
+    if item.permissions.is_empty() {
+        return Err(AppError::business(ErrorCode::BadRequest, "请选择令牌的权限"));
+    }
+    let user_permissions = query_user_permissions(&data, user_id).await?;
+    if item.permissions.iter().any(|x| x.contains(',') || !user_permissions.contains(x)) {
+        return Err(AppError::business(ErrorCode::PermissionDenied, "令牌的权限只能从自己拥有的权限中选择"));
+    }
+
+    let expire_days = match item.expire_days.unwrap_or_default() {
+        0 => config.max_expire_days,
+        x if config.max_expire_days > 0 && x > config.max_expire_days => return Err(AppError::business(ErrorCode::BadRequest, "令牌有效期超过允许的最长有效期")),
+        x => x,
+    };
+
+    let now = DateTime::now();
+    let active = ApiToken::select_by_user_id(rb, user_id).await?.iter().filter(|x| is_active(x, &now)).count();
+    if active as u64 >= config.max_tokens {
+        return Err(AppError::from(ErrorCode::ApiTokenLimitExceeded));
+    }
+
+    let (token, token_prefix, token_hash) = generate_api_token();
+    let expire_time = match expire_days {
+        0 => None,
+        days => Some(now.clone().add(Duration::from_secs(days * 86400))),
+    };
+    let api_token = ApiToken {
+        id: None,                                //主键
+        user_id,                                 //所属用户ID
+        name: name.to_string(),                  //令牌名称
+        token_prefix: token_prefix.clone(),      //令牌标识
+        token_hash,                              //令牌哈希
+        permissions: item.permissions.join(","), //令牌的权限
+        status: 1,                               //状态(0:已吊销,1:正常)
+        expire_time: expire_time.clone(),        //过期时间
+        last_used_time: None,                    //最后使用时间
+        last_used_ip: "".to_string(),            //最后使用IP
+        create_time: None,                       //创建时间
+        update_time: None,                       //修改时间
+    };
+    let conn = rb.acquire().await?;
+    let result = ApiToken::insert(&conn, &api_token).await?;
+    let id = last_insert_id(&conn, &result).await?;
+
+    ok_result_data(AddApiTokenResp { id, token, token_prefix, expire_time })
+}
+
+/*
+ *查询当前用户的API令牌列表
+ *author：刘飞华
+ *date：2026/10/18 22:48:15
+ */
+#[post("/system/apiToken/queryApiTokenList")]
+pub async fn query_api_token_list(http_req: HttpRequest, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    let user_id = current_user_id(&http_req)?;
+    let list = ApiToken::select_by_user_id(&data.batis, user_id).await?;
+    ok_result_data(list.into_iter().map(|x| x.into()).collect::<Vec<ApiTokenResp>>())
+}
+
+/*
+ *吊销当前用户的API令牌
+ *author：刘飞华
+ *date：2026/10/18 22:48:15
+ */
+#[post("/system/apiToken/revokeApiToken")]
+pub async fn revoke_api_token(http_req: HttpRequest, item: ValidatedJson<RevokeApiTokenReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("revoke api_token params: {:?}", &item);
+    let user_id = current_user_id(&http_req)?;
+    if item.ids.is_empty() {
+        return ok_result();
+    }
+
+    revoke_api_tokens(&data.batis, user_id, &item.ids).await.map(|_| ok_result())?
+}
+
+/*
+ *吊销用户的全部API令牌(管理员操作)
+ *author：刘飞华
+ *date：2026/10/18 22:48:15
+ */
+#[post("/system/user/revokeUserApiToken")]
+pub async fn revoke_user_api_token(item: ValidatedJson<RevokeUserApiTokenReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("revoke user api_token params: {:?}", &item);
+    data.batis
+        .exec("update sys_api_token set status = 0 where user_id = ?", vec![value!(item.user_id)])
+        .await
+        .map(|_| ok_result())?
+}
+
+/*
+ *校验API令牌,返回令牌所属用户的身份和令牌的权限
+ *author：刘飞华
+ *date：2026/10/18 22:48:15
+ */
+pub async fn verify_api_token(data: &AppState, token: &str, ipaddr: &str) -> AppResult<(JwtToken, Vec<String>)> {
+    let rb = &data.batis;
+    if !data.api_token_config.enabled {
+        return Err(AppError::JwtTokenError("没有启用API令牌".to_string()));
+    }
+
+    let invalid = || AppError::JwtTokenError("API令牌无效".to_string());
+    let token_prefix = parse_api_token(token).ok_or_else(invalid)?;
+    let api_token = ApiToken::select_by_token_prefix(rb, token_prefix).await?.ok_or_else(invalid)?;
+    if !constant_time_eq(hash_api_token(token).as_bytes(), api_token.token_hash.as_bytes()) {
+        return Err(invalid());
+    }
+
+    let now = DateTime::now();
+    if !is_active(&api_token, &now) {
+        return Err(AppError::JwtTokenError("API令牌已吊销或者已过期".to_string()));
+    }
+    let user = match User::select_by_id(rb, api_token.user_id).await? {
+        Some(user) if user.status == 1 => user,
+        _ => return Err(AppError::JwtTokenError("令牌所属用户不存在或者已被禁用".to_string())),
+    };
+
+    let interval = data.api_token_config.last_used_interval as i64;
+    if api_token.last_used_time.as_ref().is_none_or(|x| x.unix_timestamp() + interval <= now.unix_timestamp()) {
+        if let Err(err) = update_last_used(rb, &now, ipaddr, api_token.id.unwrap_or_default()).await {
+            log::error!("update api_token last_used_time error: {:?}", err);
+        }
+    }
+
+    let permissions = api_token.permissions.split(',').filter(|x| !x.is_empty()).map(|x| x.to_string()).collect();
+    Ok((
+        JwtToken::new_api(user.id.unwrap_or_default(), &user.user_name, &api_token.token_prefix, &data.jwt_keys).with_lang(&user.locale),
+        permissions,
+    ))
+}
+
+// 令牌没有被吊销并且没有过期
+fn is_active(api_token: &ApiToken, now: &DateTime) -> bool {
+    api_token.status == 1 && api_token.expire_time.as_ref().is_none_or(|x| x.unix_timestamp() > now.unix_timestamp())
+}
+
+// 只允许使用登录token管理API令牌,API令牌不能再创建或者吊销令牌
+fn current_user_id(http_req: &HttpRequest) -> AppResult<i64> {
+    match http_req.extensions().get::<JwtToken>() {
+        Some(token) if token.token_type != API_TOKEN => Ok(token.id),
+        _ => Err(AppError::JwtTokenError("请先登录".to_string())),
+    }
+}
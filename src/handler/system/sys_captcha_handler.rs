@@ -0,0 +1,16 @@
+use crate::common::error::AppResult;
+use crate::common::result::ok_result_data;
+use crate::vo::system::sys_captcha_vo::CaptchaResp;
+use crate::AppState;
+use actix_web::{get, web, Responder};
+
+/*
+ *获取登录图片验证码
+ *author：刘飞华
+ *date：2026/10/18 19:26:08
+ */
+#[get("/system/captcha")]
+pub async fn query_captcha(data: web::Data<AppState>) -> AppResult<impl Responder> {
+    let (captcha_id, image) = data.captcha_store.generate()?;
+    ok_result_data(CaptchaResp { captcha_id, image })
+}
@@ -1,9 +1,19 @@
 use crate::common::error::{AppError, AppResult};
-use crate::common::result::{ok_result, ok_result_data};
-use crate::model::system::sys_dept_model::{check_dept_exist_user, select_children_dept_by_id, select_dept_count, select_normal_children_dept_by_id, Dept};
+use crate::common::error_code::ErrorCode;
+use crate::common::result::{ok_result, ok_result_data, ok_result_page};
+use crate::common::validate::ValidatedJson;
+use crate::model::system::sys_dept_model::{
+    check_dept_exist_user, select_all_children_dept_by_id, select_dept_count, select_normal_children_dept_by_id, update_dept_del_flag, update_dept_status, Dept,
+};
+use crate::model::system::sys_role_dept_model::RoleDept;
+use crate::model::system::sys_user_model::User;
+use crate::utils::data_scope_util::query_data_scope;
+use crate::utils::db::{last_insert_id, transaction};
+use crate::utils::jwt_util::JwtToken;
 use crate::vo::system::sys_dept_vo::*;
 use crate::AppState;
-use actix_web::{post, web, Responder};
+use actix_web::{post, web, HttpMessage, HttpRequest, Responder};
+use rbatis::plugin::page::PageRequest;
 use rbatis::rbatis_codegen::ops::AsProxy;
 use rbatis::rbdc::DateTime;
 use rbs::value;
@@ -14,27 +24,29 @@ use rbs::value;
  *date：2025/01/08 17:16:44
  */
 #[post("/system/dept/addDept")]
-pub async fn add_sys_dept(item: web::Json<DeptReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn add_sys_dept(item: ValidatedJson<DeptReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("add sys_dept params: {:?}", &item);
     let rb = &data.batis;
 
     let req = item.0;
 
     if Dept::select_by_dept_name(rb, &req.dept_name, req.parent_id).await?.is_some() {
-        return Err(AppError::BusinessError("部门名称已存在"));
+        return Err(AppError::from(ErrorCode::DuplicateDeptName));
     }
 
     match Dept::select_by_id(rb, &req.parent_id).await? {
-        None => Err(AppError::BusinessError("添加失败,上级部门不存在")),
+        None => Err(AppError::business(ErrorCode::DeptNotFound, "添加失败,上级部门不存在")),
         Some(dept) => {
             if dept.status == 0 {
-                return Err(AppError::BusinessError("部门停用，不允许添加"));
+                return Err(AppError::from(ErrorCode::DeptDisabled));
             }
             let ancestors = format!("{},{}", dept.ancestors.unwrap_or_default(), &req.parent_id);
             let mut sys_dept = Dept::from(req);
             sys_dept.id = None;
             sys_dept.ancestors = Some(ancestors);
-            Dept::insert(rb, &sys_dept).await.map(|x| ok_result_data(x.last_insert_id))?
+            let conn = rb.acquire().await?;
+            let result = Dept::insert(&conn, &sys_dept).await?;
+            ok_result_data(last_insert_id(&conn, &result).await?)
         }
     }
 }
@@ -45,19 +57,99 @@ pub async fn add_sys_dept(item: web::Json<DeptReq>, data: web::Data<AppState>) -
  *date：2025/01/08 17:16:44
  */
 #[post("/system/dept/deleteDept")]
-pub async fn delete_sys_dept(item: web::Json<DeleteDeptReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn delete_sys_dept(item: ValidatedJson<DeleteDeptReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("delete sys_dept params: {:?}", &item);
     let rb = &data.batis;
 
     if select_dept_count(rb, &item.id).await? > 0 {
-        return Err(AppError::BusinessError("存在下级部门,不允许删除"));
+        return Err(AppError::business(ErrorCode::DataInUse, "存在下级部门,不允许删除"));
     }
 
     if check_dept_exist_user(rb, &item.id).await? > 0 {
-        return Err(AppError::BusinessError("部门存在用户,不允许删除"));
+        return Err(AppError::business(ErrorCode::DataInUse, "部门存在用户,不允许删除"));
     }
 
-    Dept::delete_by_map(rb, value! {"id": &item.id}).await.map(|_| ok_result())?
+    //逻辑删除,可以从回收站恢复
+    update_dept_del_flag(rb, &[item.id], 0, &DateTime::now()).await.map(|_| ok_result())?
+}
+
+/*
+ *查询已删除的部门列表(回收站)
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+#[post("/system/dept/queryDeletedDeptList")]
+pub async fn query_deleted_dept_list(item: ValidatedJson<QueryDeletedDeptListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("query deleted sys_dept_list params: {:?}", &item);
+    let rb = &data.batis;
+
+    let dept_name = item.dept_name.as_deref().unwrap_or_default();
+    let page = &PageRequest::new(item.page_no, item.page_size);
+    Dept::select_deleted_dept_list(rb, page, dept_name)
+        .await
+        .map(|x| ok_result_page(x.records.into_iter().map(|x| x.into()).collect::<Vec<DeptResp>>(), x.total))?
+}
+
+/*
+ *从回收站恢复部门(上级部门未删除或者一起恢复,同一上级部门下的名称不能重复)
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+#[post("/system/dept/restoreDept")]
+pub async fn restore_sys_dept(item: ValidatedJson<RestoreDeptReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("restore sys_dept params: {:?}", &item);
+    let rb = &data.batis;
+
+    let depts = Dept::select_deleted_by_ids(rb, &item.ids).await?;
+    if depts.len() != item.ids.len() {
+        return Err(AppError::business(ErrorCode::DeptNotFound, "回收站中不存在该部门"));
+    }
+
+    let mut restored: Vec<&Dept> = Vec::new();
+    for dept in &depts {
+        if !item.ids.contains(&dept.parent_id) && dept.parent_id != 0 && Dept::select_by_id(rb, &dept.parent_id).await?.is_none() {
+            return Err(AppError::business(ErrorCode::DeptNotFound, "上级部门已删除,请先恢复上级部门"));
+        }
+        let duplicate = restored.iter().any(|x| x.dept_name == dept.dept_name && x.parent_id == dept.parent_id);
+        if duplicate || Dept::select_by_dept_name(rb, &dept.dept_name, dept.parent_id).await?.is_some() {
+            return Err(AppError::from(ErrorCode::DuplicateDeptName));
+        }
+        restored.push(dept);
+    }
+
+    update_dept_del_flag(rb, &item.ids, 1, &DateTime::now()).await.map(|_| ok_result())?
+}
+
+/*
+ *彻底删除回收站中的部门,同时删除角色的该部门数据权限
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+#[post("/system/dept/purgeDept")]
+pub async fn purge_sys_dept(item: ValidatedJson<PurgeDeptReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("purge sys_dept params: {:?}", &item);
+    let rb = &data.batis;
+
+    let ids = &item.ids;
+    if Dept::select_deleted_by_ids(rb, ids).await?.len() != ids.len() {
+        return Err(AppError::business(ErrorCode::DeptNotFound, "回收站中不存在该部门"));
+    }
+
+    //回收站中的下级部门和用户恢复时需要该部门,需要先彻底删除
+    if Dept::select_by_map(rb, value! {"parent_id": ids}).await?.iter().any(|x| !ids.contains(&x.id.unwrap_or_default())) {
+        return Err(AppError::business(ErrorCode::DataInUse, "回收站中存在下级部门,请先彻底删除下级部门"));
+    }
+    if !User::select_by_map(rb, value! {"dept_id": ids}).await?.is_empty() {
+        return Err(AppError::business(ErrorCode::DataInUse, "回收站中存在该部门的用户,请先彻底删除用户"));
+    }
+
+    transaction(rb, async |tx| {
+        RoleDept::delete_by_map(tx, value! {"dept_id": ids}).await?;
+        Dept::delete_by_map(tx, value! {"id": ids}).await?;
+        Ok(())
+    })
+    .await?;
+    ok_result()
 }
 
 /*
@@ -66,7 +158,7 @@ pub async fn delete_sys_dept(item: web::Json<DeleteDeptReq>, data: web::Data<App
  *date：2025/01/08 17:16:44
  */
 #[post("/system/dept/updateDept")]
-pub async fn update_sys_dept(item: web::Json<DeptReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn update_sys_dept(item: ValidatedJson<DeptReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update sys_dept params: {:?}", &item);
     let rb = &data.batis;
     let req = item.0;
@@ -74,16 +166,16 @@ pub async fn update_sys_dept(item: web::Json<DeptReq>, data: web::Data<AppState>
     let id = req.id;
 
     if Some(req.parent_id) == id {
-        return Err(AppError::BusinessError("上级部门不能是自己"));
+        return Err(AppError::business(ErrorCode::BadRequest, "上级部门不能是自己"));
     }
 
     let old_ancestors = match Dept::select_by_id(rb, &id.unwrap_or_default()).await? {
-        None => return Err(AppError::BusinessError("更新失败,部门不存在")),
+        None => return Err(AppError::business(ErrorCode::DeptNotFound, "更新失败,部门不存在")),
         Some(dept) => dept.ancestors.unwrap_or_default(),
     };
 
     let ancestors = match Dept::select_by_id(rb, &req.parent_id).await? {
-        None => return Err(AppError::BusinessError("更新失败,上级部门不存在")),
+        None => return Err(AppError::business(ErrorCode::DeptNotFound, "更新失败,上级部门不存在")),
         Some(dept) => {
             format!("{},{}", dept.ancestors.unwrap_or_default(), &req.parent_id)
         }
@@ -91,37 +183,40 @@ pub async fn update_sys_dept(item: web::Json<DeptReq>, data: web::Data<AppState>
 
     if let Some(dept) = Dept::select_by_dept_name(rb, &req.dept_name, req.parent_id).await? {
         if dept.id != id {
-            return Err(AppError::BusinessError("部门名称已存在"));
+            return Err(AppError::from(ErrorCode::DuplicateDeptName));
         }
     }
 
     if select_normal_children_dept_by_id(rb, &id.unwrap_or_default()).await? > 0 && req.status == 0 {
-        return Err(AppError::BusinessError("该部门包含未停用的子部门"));
-    }
-
-    for mut x in select_children_dept_by_id(rb, &id.unwrap_or_default()).await? {
-        x.ancestors = Some(x.ancestors.unwrap_or_default().replace(old_ancestors.as_str(), ancestors.as_str()));
-        Dept::update_by_map(rb, &x, value! {"id": &x.id}).await?;
+        return Err(AppError::business(ErrorCode::DataInUse, "该部门包含未停用的子部门"));
     }
 
-    if req.status == 1 && ancestors != "0" {
-        let ids = ancestors.split(",").map(|s| s.i64()).collect::<Vec<i64>>();
+    let children = select_all_children_dept_by_id(rb, &id.unwrap_or_default()).await?;
+    let parent_ids = match req.status == 1 && ancestors != "0" {
+        true => ancestors.split(",").map(|s| s.i64()).collect::<Vec<i64>>(),
+        false => Vec::new(),
+    };
 
-        let update_sql = format!(
-            "update sys_dept set status = ? ,update_time = ? where id in ({})",
-            ids.iter().map(|_| "?").collect::<Vec<&str>>().join(", ")
-        );
+    let mut data = Dept::from(req);
+    data.ancestors = Some(ancestors.clone());
+    data.update_time = Some(DateTime::now());
 
-        let mut param = vec![value!(req.status), value!(DateTime::now())];
-        param.extend(ids.iter().map(|&id| value!(id)));
+    //子部门的祖级列表,上级部门的状态和当前部门在同一个事务中更新
+    transaction(rb, async |tx| {
+        for mut x in children {
+            x.ancestors = Some(x.ancestors.unwrap_or_default().replace(old_ancestors.as_str(), ancestors.as_str()));
+            Dept::update_by_map(tx, &x, value! {"id": &x.id}).await?;
+        }
 
-        rb.exec(&update_sql, param).await?;
-    }
+        if !parent_ids.is_empty() {
+            update_dept_status(tx, &parent_ids, 1, &DateTime::now()).await?;
+        }
 
-    let mut data = Dept::from(req);
-    data.ancestors = Some(ancestors);
-    data.update_time = Some(DateTime::now());
-    Dept::update_by_map(rb, &data, value! {"id":  &id}).await.map(|_| ok_result())?
+        Dept::update_by_map(tx, &data, value! {"id":  &id}).await?;
+        Ok(())
+    })
+    .await?;
+    ok_result()
 }
 
 /*
@@ -130,32 +225,28 @@ pub async fn update_sys_dept(item: web::Json<DeptReq>, data: web::Data<AppState>
  *date：2025/01/08 17:16:44
  */
 #[post("/system/dept/updateDeptStatus")]
-pub async fn update_sys_dept_status(item: web::Json<UpdateDeptStatusReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn update_sys_dept_status(item: ValidatedJson<UpdateDeptStatusReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update sys_dept_status params: {:?}", &item);
     let rb = &data.batis;
     let req = item.0;
 
-    if req.status == 1 {
-        for id in req.ids.clone() {
-            if let Some(x) = Dept::select_by_id(rb, &id).await? {
-                let ancestors = x.ancestors.unwrap_or_default();
-                let ids = ancestors.split(",").map(|s| s.i64()).collect::<Vec<i64>>();
-
-                let update_sql = format!("update sys_dept set status = ? where id in ({})", ids.iter().map(|_| "?").collect::<Vec<&str>>().join(", "));
+    transaction(rb, async |tx| {
+        if req.status == 1 {
+            for id in req.ids.clone() {
+                if let Some(x) = Dept::select_by_id(tx, &id).await? {
+                    let ancestors = x.ancestors.unwrap_or_default();
+                    let ids = ancestors.split(",").map(|s| s.i64()).collect::<Vec<i64>>();
 
-                let mut param = vec![value!(req.status)];
-                param.extend(ids.iter().map(|&id| value!(id)));
-                rb.exec(&update_sql, param).await?;
+                    update_dept_status(tx, &ids, req.status, &DateTime::now()).await?;
+                }
             }
         }
-    }
-
-    let ids = req.ids.iter().map(|_| "?").collect::<Vec<&str>>().join(", ");
-    let update_sql = format!("update sys_dept set status = ? where id in ({})", ids);
 
-    let mut param = vec![value!(req.status)];
-    param.extend(req.ids.iter().map(|&id| value!(id)));
-    rb.exec(&update_sql, param).await.map(|_| ok_result())?
+        update_dept_status(tx, &req.ids, req.status, &DateTime::now()).await?;
+        Ok(())
+    })
+    .await?;
+    ok_result()
 }
 
 /*
@@ -164,12 +255,12 @@ pub async fn update_sys_dept_status(item: web::Json<UpdateDeptStatusReq>, data:
  *date：2025/01/08 17:16:44
  */
 #[post("/system/dept/queryDeptDetail")]
-pub async fn query_sys_dept_detail(item: web::Json<QueryDeptDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_dept_detail(item: ValidatedJson<QueryDeptDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_dept_detail params: {:?}", &item);
     let rb = &data.batis;
 
     Dept::select_by_id(rb, &item.id).await?.map_or_else(
-        || Err(AppError::BusinessError("部门不存在")),
+        || Err(AppError::from(ErrorCode::DeptNotFound)),
         |x| {
             let data: DeptResp = x.into();
             ok_result_data(data)
@@ -183,14 +274,16 @@ pub async fn query_sys_dept_detail(item: web::Json<QueryDeptDetailReq>, data: we
  *date：2025/01/08 17:16:44
  */
 #[post("/system/dept/queryDeptList")]
-pub async fn query_sys_dept_list(item: web::Json<QueryDeptListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_dept_list(http_req: HttpRequest, item: ValidatedJson<QueryDeptListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_dept_list params: {:?}", &item);
     let rb = &data.batis;
 
     let dept_name = item.dept_name.as_deref().unwrap_or_default(); //部门名称
     let status = item.status.unwrap_or_default(); //部状态（0：停用，1:正常）
+    let user_id = http_req.extensions().get::<JwtToken>().map(|x| x.id).unwrap_or_default();
+    let scope = query_data_scope(rb, user_id).await?;
 
-    Dept::select_page_dept_list(rb, dept_name, status)
+    Dept::select_page_dept_list(rb, dept_name, status, &scope)
         .await
         .map(|x| ok_result_data(x.into_iter().map(|x| x.into()).collect::<Vec<DeptResp>>()))?
 }
@@ -1,6 +1,9 @@
 use crate::common::error::{AppError, AppResult};
+use crate::common::error_code::ErrorCode;
 use crate::common::result::{ok_result, ok_result_data, ok_result_page};
-use crate::model::system::sys_dict_data_model::DictData;
+use crate::common::validate::ValidatedJson;
+use crate::model::system::sys_dict_data_model::{update_dict_data_status, DictData};
+use crate::utils::db::last_insert_id;
 use crate::vo::system::sys_dict_data_vo::*;
 use crate::AppState;
 use actix_web::{post, web, Responder};
@@ -14,21 +17,23 @@ use rbs::value;
  *date：2025/01/08 17:16:44
  */
 #[post("/system/dictData/addDictData")]
-pub async fn add_sys_dict_data(item: web::Json<DictDataReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn add_sys_dict_data(item: ValidatedJson<DictDataReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("add sys_dict_data params: {:?}", &item);
     let rb = &data.batis;
 
     let req = item.0;
 
     if DictData::select_by_dict_label(rb, &req.dict_type, &req.dict_label).await?.is_some() {
-        return Err(AppError::BusinessError("字典标签已存在"));
+        return Err(AppError::from(ErrorCode::DuplicateDictLabel));
     }
 
     if DictData::select_by_dict_value(rb, &req.dict_type, &req.dict_value).await?.is_some() {
-        return Err(AppError::BusinessError("字典键值已存在"));
+        return Err(AppError::from(ErrorCode::DuplicateDictValue));
     }
 
-    DictData::insert(rb, &DictData::from(req)).await.map(|x| ok_result_data(x.last_insert_id))?
+    let conn = rb.acquire().await?;
+    let result = DictData::insert(&conn, &DictData::from(req)).await?;
+    ok_result_data(last_insert_id(&conn, &result).await?)
 }
 
 /*
@@ -37,7 +42,7 @@ pub async fn add_sys_dict_data(item: web::Json<DictDataReq>, data: web::Data<App
  *date：2025/01/08 17:16:44
  */
 #[post("/system/dictData/deleteDictData")]
-pub async fn delete_sys_dict_data(item: web::Json<DeleteDictDataReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn delete_sys_dict_data(item: ValidatedJson<DeleteDictDataReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("delete sys_dict_data params: {:?}", &item);
     let rb = &data.batis;
 
@@ -50,7 +55,7 @@ pub async fn delete_sys_dict_data(item: web::Json<DeleteDictDataReq>, data: web:
  *date：2025/01/08 17:16:44
  */
 #[post("/system/dictData/updateDictData")]
-pub async fn update_sys_dict_data(item: web::Json<DictDataReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn update_sys_dict_data(item: ValidatedJson<DictDataReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update sys_dict_data params: {:?}", &item);
     let rb = &data.batis;
     let req = item.0;
@@ -58,18 +63,18 @@ pub async fn update_sys_dict_data(item: web::Json<DictDataReq>, data: web::Data<
     let id = req.id;
 
     if DictData::select_by_id(rb, &id.unwrap_or_default()).await?.is_none() {
-        return Err(AppError::BusinessError("字典数据不存在"));
+        return Err(AppError::from(ErrorCode::DictDataNotFound));
     }
 
     if let Some(x) = DictData::select_by_dict_label(rb, &req.dict_type, &req.dict_label).await? {
         if x.id != id {
-            return Err(AppError::BusinessError("字典标签已存在"));
+            return Err(AppError::from(ErrorCode::DuplicateDictLabel));
         }
     }
 
     if let Some(x) = DictData::select_by_dict_value(rb, &req.dict_type, &req.dict_value).await? {
         if x.id != id {
-            return Err(AppError::BusinessError("字典键值已存在"));
+            return Err(AppError::from(ErrorCode::DuplicateDictValue));
         }
     }
 
@@ -84,16 +89,12 @@ pub async fn update_sys_dict_data(item: web::Json<DictDataReq>, data: web::Data<
  *date：2025/01/08 17:16:44
  */
 #[post("/system/dictData/updateDictDataStatus")]
-pub async fn update_sys_dict_data_status(item: web::Json<UpdateDictDataStatusReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn update_sys_dict_data_status(item: ValidatedJson<UpdateDictDataStatusReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update sys_dict_data_status params: {:?}", &item);
     let rb = &data.batis;
     let req = item.0;
 
-    let update_sql = format!("update sys_dict_data set status = ? where id in ({})", req.ids.iter().map(|_| "?").collect::<Vec<&str>>().join(", "));
-
-    let mut param = vec![value!(req.status)];
-    param.extend(req.ids.iter().map(|&id| value!(id)));
-    rb.exec(&update_sql, param).await.map(|_| ok_result())?
+    update_dict_data_status(rb, &req.ids, req.status).await.map(|_| ok_result())?
 }
 
 /*
@@ -102,12 +103,12 @@ pub async fn update_sys_dict_data_status(item: web::Json<UpdateDictDataStatusReq
  *date：2025/01/08 17:16:44
  */
 #[post("/system/dictData/queryDictDataDetail")]
-pub async fn query_sys_dict_data_detail(item: web::Json<QueryDictDataDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_dict_data_detail(item: ValidatedJson<QueryDictDataDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_dict_data_detail params: {:?}", &item);
     let rb = &data.batis;
 
     DictData::select_by_id(rb, &item.id).await?.map_or_else(
-        || Err(AppError::BusinessError("字典数据不存在")),
+        || Err(AppError::from(ErrorCode::DictDataNotFound)),
         |x| {
             let data: DictDataResp = x.into();
             ok_result_data(data)
@@ -121,7 +122,7 @@ pub async fn query_sys_dict_data_detail(item: web::Json<QueryDictDataDetailReq>,
  *date：2025/01/08 17:16:44
  */
 #[post("/system/dictData/queryDictDataList")]
-pub async fn query_sys_dict_data_list(item: web::Json<QueryDictDataListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_dict_data_list(item: ValidatedJson<QueryDictDataListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_dict_data_list params: {:?}", &item);
     let rb = &data.batis;
 
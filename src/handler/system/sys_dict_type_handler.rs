@@ -1,7 +1,10 @@
 use crate::common::error::{AppError, AppResult};
+use crate::common::error_code::ErrorCode;
 use crate::common::result::{ok_result, ok_result_data, ok_result_page};
+use crate::common::validate::ValidatedJson;
 use crate::model::system::sys_dict_data_model::{count_dict_data_by_type, update_dict_data_type};
-use crate::model::system::sys_dict_type_model::DictType;
+use crate::model::system::sys_dict_type_model::{update_dict_type_status, DictType};
+use crate::utils::db::last_insert_id;
 use crate::vo::system::sys_dict_type_vo::*;
 use crate::AppState;
 use actix_web::{post, web, Responder};
@@ -15,17 +18,19 @@ use rbs::value;
  *date：2025/01/08 17:16:44
  */
 #[post("/system/dictType/addDictType")]
-pub async fn add_sys_dict_type(item: web::Json<DictTypeReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn add_sys_dict_type(item: ValidatedJson<DictTypeReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("add sys_dict_type params: {:?}", &item);
     let rb = &data.batis;
 
     let req = item.0;
 
     if DictType::select_by_dict_type(rb, &req.dict_type).await?.is_some() {
-        return Err(AppError::BusinessError("字典类型已存在"));
+        return Err(AppError::from(ErrorCode::DuplicateDictType));
     }
 
-    DictType::insert(rb, &DictType::from(req)).await.map(|x| ok_result_data(x.last_insert_id))?
+    let conn = rb.acquire().await?;
+    let result = DictType::insert(&conn, &DictType::from(req)).await?;
+    ok_result_data(last_insert_id(&conn, &result).await?)
 }
 
 /*
@@ -34,7 +39,7 @@ pub async fn add_sys_dict_type(item: web::Json<DictTypeReq>, data: web::Data<App
  *date：2025/01/08 17:16:44
  */
 #[post("/system/dictType/deleteDictType")]
-pub async fn delete_sys_dict_type(item: web::Json<DeleteDictTypeReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn delete_sys_dict_type(item: ValidatedJson<DeleteDictTypeReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("delete sys_dict_type params: {:?}", &item);
     let rb = &data.batis;
 
@@ -42,10 +47,10 @@ pub async fn delete_sys_dict_type(item: web::Json<DeleteDictTypeReq>, data: web:
 
     for id in ids {
         match DictType::select_by_id(rb, &id).await? {
-            None => return Err(AppError::BusinessError("字典类型不存在,不能删除")),
+            None => return Err(AppError::business(ErrorCode::DictTypeNotFound, "字典类型不存在,不能删除")),
             Some(p) => {
                 if count_dict_data_by_type(rb, &p.dict_type).await? > 0 {
-                    return Err(AppError::BusinessError("已分配,不能删除"));
+                    return Err(AppError::business(ErrorCode::DataInUse, "已分配,不能删除"));
                 }
             }
         };
@@ -60,7 +65,7 @@ pub async fn delete_sys_dict_type(item: web::Json<DeleteDictTypeReq>, data: web:
  *date：2025/01/08 17:16:44
  */
 #[post("/system/dictType/updateDictType")]
-pub async fn update_sys_dict_type(item: web::Json<DictTypeReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn update_sys_dict_type(item: ValidatedJson<DictTypeReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update sys_dict_type params: {:?}", &item);
     let rb = &data.batis;
     let req = item.0;
@@ -68,12 +73,12 @@ pub async fn update_sys_dict_type(item: web::Json<DictTypeReq>, data: web::Data<
     let id = req.id;
 
     if DictType::select_by_id(rb, &id.unwrap_or_default()).await?.is_none() {
-        return Err(AppError::BusinessError("字典类型不存在"));
+        return Err(AppError::from(ErrorCode::DictTypeNotFound));
     }
 
     if let Some(x) = DictType::select_by_dict_type(rb, &req.dict_type).await? {
         if x.id != id {
-            return Err(AppError::BusinessError("字典类型已存在"));
+            return Err(AppError::from(ErrorCode::DuplicateDictType));
         }
 
         let dict_type = x.dict_type;
@@ -91,16 +96,12 @@ pub async fn update_sys_dict_type(item: web::Json<DictTypeReq>, data: web::Data<
  *date：2025/01/08 17:16:44
  */
 #[post("/system/dictType/updateDictTypeStatus")]
-pub async fn update_sys_dict_type_status(item: web::Json<UpdateDictTypeStatusReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn update_sys_dict_type_status(item: ValidatedJson<UpdateDictTypeStatusReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update sys_dict_type_status params: {:?}", &item);
     let rb = &data.batis;
     let req = item.0;
 
-    let update_sql = format!("update sys_dict_type set status = ? where id in ({})", req.ids.iter().map(|_| "?").collect::<Vec<&str>>().join(", "));
-
-    let mut param = vec![value!(req.status)];
-    param.extend(req.ids.iter().map(|&id| value!(id)));
-    rb.exec(&update_sql, param).await.map(|_| ok_result())?
+    update_dict_type_status(rb, &req.ids, req.status).await.map(|_| ok_result())?
 }
 
 /*
@@ -109,12 +110,12 @@ pub async fn update_sys_dict_type_status(item: web::Json<UpdateDictTypeStatusReq
  *date：2025/01/08 17:16:44
  */
 #[post("/system/dictType/queryDictTypeDetail")]
-pub async fn query_sys_dict_type_detail(item: web::Json<QueryDictTypeDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_dict_type_detail(item: ValidatedJson<QueryDictTypeDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_dict_type_detail params: {:?}", &item);
     let rb = &data.batis;
 
     DictType::select_by_id(rb, &item.id).await?.map_or_else(
-        || Err(AppError::BusinessError("字典类型不存在")),
+        || Err(AppError::from(ErrorCode::DictTypeNotFound)),
         |x| {
             let data: DictTypeResp = x.into();
             ok_result_data(data)
@@ -128,7 +129,7 @@ pub async fn query_sys_dict_type_detail(item: web::Json<QueryDictTypeDetailReq>,
  *date：2025/01/08 17:16:44
  */
 #[post("/system/dictType/queryDictTypeList")]
-pub async fn query_sys_dict_type_list(item: web::Json<QueryDictTypeListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_dict_type_list(item: ValidatedJson<QueryDictTypeListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_dict_type_list params: {:?}", &item);
     let rb = &data.batis;
 
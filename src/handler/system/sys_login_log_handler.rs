@@ -1,9 +1,13 @@
 use crate::common::error::{AppError, AppResult};
+use crate::common::error_code::ErrorCode;
 use crate::common::result::{ok_result, ok_result_data, ok_result_page};
+use crate::common::validate::ValidatedJson;
 use crate::model::system::sys_login_log_model::{clean_login_log, LoginLog};
+use crate::utils::data_scope_util::query_data_scope;
+use crate::utils::jwt_util::JwtToken;
 use crate::vo::system::sys_login_log_vo::*;
 use crate::AppState;
-use actix_web::{post, web, Responder};
+use actix_web::{post, web, HttpMessage, HttpRequest, Responder};
 use rbatis::plugin::page::PageRequest;
 use rbs::value;
 
@@ -13,7 +17,7 @@ use rbs::value;
  *date：2025/01/08 17:16:44
  */
 #[post("/system/loginLog/deleteLoginLog")]
-pub async fn delete_sys_login_log(item: web::Json<DeleteLoginLogReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn delete_sys_login_log(item: ValidatedJson<DeleteLoginLogReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("delete sys_login_log params: {:?}", &item);
     let rb = &data.batis;
 
@@ -39,12 +43,12 @@ pub async fn clean_sys_login_log(data: web::Data<AppState>) -> AppResult<impl Re
  *date：2025/01/08 17:16:44
  */
 #[post("/system/loginLog/queryLoginLogDetail")]
-pub async fn query_sys_login_log_detail(item: web::Json<QueryLoginLogDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_login_log_detail(item: ValidatedJson<QueryLoginLogDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_login_log_detail params: {:?}", &item);
     let rb = &data.batis;
 
     LoginLog::select_by_id(rb, &item.id).await?.map_or_else(
-        || Err(AppError::BusinessError("系统访问记录不存在")),
+        || Err(AppError::from(ErrorCode::LoginLogNotFound)),
         |x| {
             let data: LoginLogResp = x.into();
             ok_result_data(data)
@@ -58,7 +62,7 @@ pub async fn query_sys_login_log_detail(item: web::Json<QueryLoginLogDetailReq>,
  *date：2025/01/08 17:16:44
  */
 #[post("/system/loginLog/queryLoginLogList")]
-pub async fn query_sys_login_log_list(item: web::Json<QueryLoginLogListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_login_log_list(http_req: HttpRequest, item: ValidatedJson<QueryLoginLogListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_login_log_list params: {:?}", &item);
     let rb = &data.batis;
 
@@ -67,10 +71,12 @@ pub async fn query_sys_login_log_list(item: web::Json<QueryLoginLogListReq>, dat
     let browser = item.browser.as_deref().unwrap_or_default(); //浏览器类型
     let os = item.os.as_deref().unwrap_or_default(); //操作系统
     let status = item.status.unwrap_or(2); //登录状态(0:失败,1:成功)
+    let user_id = http_req.extensions().get::<JwtToken>().map(|x| x.id).unwrap_or_default();
+    let scope = query_data_scope(rb, user_id).await?;
 
     let page = &PageRequest::new(item.page_no, item.page_size);
 
-    LoginLog::select_login_log_list(rb, page, name, ipaddr, browser, os, &status)
+    LoginLog::select_login_log_list(rb, page, name, ipaddr, browser, os, &status, &scope)
         .await
         .map(|x| ok_result_page(x.records.into_iter().map(|x| x.into()).collect::<Vec<LoginLogResp>>(), x.total))?
 }
@@ -1,7 +1,10 @@
 use crate::common::error::{AppError, AppResult};
+use crate::common::error_code::ErrorCode;
 use crate::common::result::{ok_result, ok_result_data};
-use crate::model::system::sys_menu_model::{select_count_menu_by_parent_id, Menu};
+use crate::common::validate::ValidatedJson;
+use crate::model::system::sys_menu_model::{select_count_menu_by_parent_id, update_menu_status, Menu};
 use crate::model::system::sys_role_menu_model::select_count_menu_by_menu_id;
+use crate::utils::db::last_insert_id;
 use crate::vo::system::sys_menu_vo::*;
 use crate::AppState;
 use actix_web::{post, web, Responder};
@@ -14,23 +17,28 @@ use rbs::value;
  *date：2025/01/08 17:16:44
  */
 #[post("/system/menu/addMenu")]
-pub async fn add_sys_menu(item: web::Json<MenuReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn add_sys_menu(item: ValidatedJson<MenuReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("add sys_menu params: {:?}", &item);
     let rb = &data.batis;
     let req = item.0;
 
     if Menu::select_by_menu_name(rb, &req.menu_name).await?.is_some() {
-        return Err(AppError::BusinessError("菜单名称已存在"));
+        return Err(AppError::from(ErrorCode::DuplicateMenuName));
     }
 
     let menu_url = req.menu_url.clone();
     if menu_url.is_some() {
         if Menu::select_by_menu_url(rb, &menu_url.unwrap()).await?.is_some() {
-            return Err(AppError::BusinessError("路由路径已存在"));
+            return Err(AppError::from(ErrorCode::DuplicateMenuUrl));
         }
     }
 
-    Menu::insert(rb, &Menu::from(req)).await.map(|x| ok_result_data(x.last_insert_id))?
+    let conn = rb.acquire().await?;
+    let result = Menu::insert(&conn, &Menu::from(req)).await?;
+    let id = last_insert_id(&conn, &result).await?;
+
+    data.permission_cache.invalidate_all();
+    ok_result_data(id)
 }
 
 /*
@@ -39,19 +47,22 @@ pub async fn add_sys_menu(item: web::Json<MenuReq>, data: web::Data<AppState>) -
  *date：2025/01/08 17:16:44
  */
 #[post("/system/menu/deleteMenu")]
-pub async fn delete_sys_menu(item: web::Json<DeleteMenuReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn delete_sys_menu(item: ValidatedJson<DeleteMenuReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("delete sys_menu params: {:?}", &item);
     let rb = &data.batis;
 
     if select_count_menu_by_parent_id(rb, &item.id).await? > 0 {
-        return Err(AppError::BusinessError("存在子菜单,不允许删除"));
+        return Err(AppError::business(ErrorCode::DataInUse, "存在子菜单,不允许删除"));
     }
 
     if select_count_menu_by_menu_id(rb, &item.id).await? > 0 {
-        return Err(AppError::BusinessError("菜单已分配,不允许删除"));
+        return Err(AppError::business(ErrorCode::DataInUse, "菜单已分配,不允许删除"));
     }
 
-    Menu::delete_by_map(rb, value! {"id": &item.id}).await.map(|_| ok_result())?
+    Menu::delete_by_map(rb, value! {"id": &item.id}).await?;
+
+    data.permission_cache.invalidate_all();
+    ok_result()
 }
 
 /*
@@ -60,7 +71,7 @@ pub async fn delete_sys_menu(item: web::Json<DeleteMenuReq>, data: web::Data<App
  *date：2025/01/08 17:16:44
  */
 #[post("/system/menu/updateMenu")]
-pub async fn update_sys_menu(item: web::Json<MenuReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn update_sys_menu(item: ValidatedJson<MenuReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update sys_menu params: {:?}", &item);
     let rb = &data.batis;
     let req = item.0;
@@ -68,12 +79,12 @@ pub async fn update_sys_menu(item: web::Json<MenuReq>, data: web::Data<AppState>
     let id = req.id;
 
     if Menu::select_by_id(rb, &id.unwrap_or_default()).await?.is_none() {
-        return Err(AppError::BusinessError("菜单信息不存在"));
+        return Err(AppError::from(ErrorCode::MenuNotFound));
     }
 
     if let Some(x) = Menu::select_by_menu_name(rb, &req.menu_name).await? {
         if x.id != id {
-            return Err(AppError::BusinessError("菜单名称已存在"));
+            return Err(AppError::from(ErrorCode::DuplicateMenuName));
         }
     }
 
@@ -81,14 +92,17 @@ pub async fn update_sys_menu(item: web::Json<MenuReq>, data: web::Data<AppState>
     if menu_url.is_some() {
         if let Some(x) = Menu::select_by_menu_url(rb, &menu_url.unwrap()).await? {
             if x.id != id {
-                return Err(AppError::BusinessError("路由路径已存在"));
+                return Err(AppError::from(ErrorCode::DuplicateMenuUrl));
             }
         }
     }
 
-    let mut data = Menu::from(req);
-    data.update_time = Some(DateTime::now());
-    Menu::update_by_map(rb, &data, value! {"id": &id}).await.map(|_| ok_result())?
+    let mut menu = Menu::from(req);
+    menu.update_time = Some(DateTime::now());
+    Menu::update_by_map(rb, &menu, value! {"id": &id}).await?;
+
+    data.permission_cache.invalidate_all();
+    ok_result()
 }
 
 /*
@@ -97,16 +111,15 @@ pub async fn update_sys_menu(item: web::Json<MenuReq>, data: web::Data<AppState>
  *date：2025/01/08 17:16:44
  */
 #[post("/system/menu/updateMenuStatus")]
-pub async fn update_sys_menu_status(item: web::Json<UpdateMenuStatusReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn update_sys_menu_status(item: ValidatedJson<UpdateMenuStatusReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update sys_menu_status params: {:?}", &item);
     let rb = &data.batis;
     let req = item.0;
 
-    let update_sql = format!("update sys_menu set status = ? where id in ({})", req.ids.iter().map(|_| "?").collect::<Vec<&str>>().join(", "));
+    update_menu_status(rb, &req.ids, req.status).await?;
 
-    let mut param = vec![value!(req.status)];
-    param.extend(req.ids.iter().map(|&id| value!(id)));
-    rb.exec(&update_sql, param).await.map(|_| ok_result())?
+    data.permission_cache.invalidate_all();
+    ok_result()
 }
 /*
  *查询菜单信息详情
@@ -114,12 +127,12 @@ pub async fn update_sys_menu_status(item: web::Json<UpdateMenuStatusReq>, data:
  *date：2025/01/08 17:16:44
  */
 #[post("/system/menu/queryMenuDetail")]
-pub async fn query_sys_menu_detail(item: web::Json<QueryMenuDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_menu_detail(item: ValidatedJson<QueryMenuDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_menu_detail params: {:?}", &item);
     let rb = &data.batis;
 
     Menu::select_by_id(rb, &item.id).await?.map_or_else(
-        || Err(AppError::BusinessError("菜单信息不存在")),
+        || Err(AppError::from(ErrorCode::MenuNotFound)),
         |x| {
             let data: MenuResp = x.into();
             ok_result_data(data)
@@ -133,7 +146,7 @@ pub async fn query_sys_menu_detail(item: web::Json<QueryMenuDetailReq>, data: we
  *date：2025/01/08 17:16:44
  */
 #[post("/system/menu/queryMenuList")]
-pub async fn query_sys_menu_list(item: web::Json<QueryMenuListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_menu_list(item: ValidatedJson<QueryMenuListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_menu_list params: {:?}", &item);
     let rb = &data.batis;
 
@@ -0,0 +1,238 @@
+use crate::common::error::{AppError, AppResult};
+use crate::common::error_code::ErrorCode;
+use crate::common::result::{ok_result, ok_result_data};
+use crate::common::validate::ValidatedJson;
+use crate::model::system::sys_role_model::select_role_by_user_id;
+use crate::model::system::sys_user_mfa_model::{update_last_used_step, update_mfa_status, update_recovery_codes, UserMfa};
+use crate::model::system::sys_user_model::User;
+use crate::utils::jwt_util::JwtToken;
+use crate::utils::password_util::PasswordVerify;
+use crate::utils::token_util::now_secs;
+use crate::utils::totp_util::{generate_recovery_codes, generate_secret, is_recovery_code, otpauth_uri, qr_code, verify_code};
+use crate::vo::system::sys_mfa_vo::*;
+use crate::AppState;
+use actix_web::{post, web, HttpMessage, HttpRequest, Responder};
+use rbatis::rbdc::DateTime;
+use rbs::value;
+
+/*
+ *查询当前用户的两步验证状态
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+#[post("/system/mfa/queryMfaStatus")]
+pub async fn query_mfa_status(http_req: HttpRequest, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    let user_id = current_user_id(&http_req)?;
+    let mfa = UserMfa::select_by_user_id(&data.batis, user_id).await?.filter(|x| x.status == 1);
+
+    ok_result_data(MfaStatusResp {
+        enabled: mfa.is_some(),
+        required: is_mfa_required(&data, user_id).await?,
+        recovery_codes_left: mfa.map(|x| split_codes(&x.recovery_codes).len()).unwrap_or_default(),
+    })
+}
+
+/*
+ *绑定身份验证器(生成密钥和恢复码,提交动态码确认后才会启用)
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+#[post("/system/mfa/setupMfa")]
+pub async fn setup_mfa(http_req: HttpRequest, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    let user_id = current_user_id(&http_req)?;
+    let user = match User::select_by_id(&data.batis, user_id).await? {
+        None => return Err(AppError::from(ErrorCode::UserNotFound)),
+        Some(user) => user,
+    };
+    ok_result_data(create_mfa_secret(&data, &user).await?)
+}
+
+/*
+ *确认绑定身份验证器,启用两步验证
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+#[post("/system/mfa/confirmMfa")]
+pub async fn confirm_mfa(http_req: HttpRequest, item: ValidatedJson<MfaCodeReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    let user_id = current_user_id(&http_req)?;
+    match UserMfa::select_by_user_id(&data.batis, user_id).await? {
+        Some(mfa) if mfa.status == 0 => {
+            if !verify_mfa_code(&data, &mfa, &item.code).await? {
+                return Err(AppError::from(ErrorCode::MfaCodeInvalid));
+            }
+            enable_mfa(&data, &mfa).await?;
+            ok_result()
+        }
+        Some(_) => Err(AppError::from(ErrorCode::MfaAlreadyEnabled)),
+        None => Err(AppError::from(ErrorCode::MfaNotBound)),
+    }
+}
+
+/*
+ *关闭两步验证(需要提交动态码或者恢复码)
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+#[post("/system/mfa/disableMfa")]
+pub async fn disable_mfa(http_req: HttpRequest, item: ValidatedJson<MfaCodeReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    let user_id = current_user_id(&http_req)?;
+    if is_mfa_required(&data, user_id).await? {
+        return Err(AppError::from(ErrorCode::MfaRequired));
+    }
+
+    let mfa = match UserMfa::select_by_user_id(&data.batis, user_id).await? {
+        Some(mfa) if mfa.status == 1 => mfa,
+        _ => return Err(AppError::from(ErrorCode::MfaNotEnabled)),
+    };
+    if !verify_mfa_code(&data, &mfa, &item.code).await? {
+        return Err(AppError::from(ErrorCode::MfaCodeInvalid));
+    }
+
+    UserMfa::delete_by_map(&data.batis, value! {"user_id": user_id}).await.map(|_| ok_result())?
+}
+
+/*
+ *重新生成恢复码(之前的恢复码全部失效)
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+#[post("/system/mfa/regenerateRecoveryCodes")]
+pub async fn regenerate_recovery_codes(http_req: HttpRequest, item: ValidatedJson<MfaCodeReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    let user_id = current_user_id(&http_req)?;
+    let mfa = match UserMfa::select_by_user_id(&data.batis, user_id).await? {
+        Some(mfa) if mfa.status == 1 => mfa,
+        _ => return Err(AppError::from(ErrorCode::MfaNotEnabled)),
+    };
+    if is_recovery_code(&item.code) || !verify_mfa_code(&data, &mfa, &item.code).await? {
+        return Err(AppError::from(ErrorCode::MfaCodeInvalid));
+    }
+
+    let (recovery_codes, hashes) = new_recovery_codes(&data)?;
+    update_recovery_codes(&data.batis, &hashes, &DateTime::now(), user_id, &mfa.recovery_codes).await?;
+    ok_result_data(RecoveryCodesResp { recovery_codes })
+}
+
+/*
+ *重置用户的两步验证(用户丢失身份验证器和恢复码时由管理员操作)
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+#[post("/system/user/resetUserMfa")]
+pub async fn reset_user_mfa(item: ValidatedJson<ResetUserMfaReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("reset user mfa params: {:?}", &item);
+    UserMfa::delete_by_map(&data.batis, value! {"user_id": item.user_id}).await.map(|_| ok_result())?
+}
+
+/*
+ *用户的角色是否要求必须启用两步验证
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+pub async fn is_mfa_required(data: &AppState, user_id: i64) -> AppResult<bool> {
+    let required_role_keys = &data.mfa_config.required_role_keys;
+    if required_role_keys.is_empty() {
+        return Ok(false);
+    }
+    let roles = select_role_by_user_id(&data.batis, &user_id).await?;
+    Ok(roles.iter().any(|x| required_role_keys.contains(&x.role_key)))
+}
+
+/*
+ *生成待确认的密钥和恢复码(已启用时需要先关闭才能重新绑定)
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+pub async fn create_mfa_secret(data: &AppState, user: &User) -> AppResult<MfaSetupResp> {
+    let rb = &data.batis;
+    let user_id = user.id.unwrap_or_default();
+    if let Some(mfa) = UserMfa::select_by_user_id(rb, user_id).await? {
+        if mfa.status == 1 {
+            return Err(AppError::from(ErrorCode::MfaAlreadyEnabled));
+        }
+        UserMfa::delete_by_map(rb, value! {"user_id": user_id}).await?;
+    }
+
+    let secret = generate_secret();
+    let (recovery_codes, hashes) = new_recovery_codes(data)?;
+    let mfa = UserMfa {
+        id: None,               //主键
+        user_id,                //用户ID
+        secret: secret.clone(), //TOTP密钥(base32)
+        status: 0,              //状态(0:待确认,1:已启用)
+        recovery_codes: hashes, //恢复码哈希(多个用逗号分隔,使用后删除)
+        last_used_step: 0,      //最后使用的动态码时间步(防止动态码重放)
+        create_time: None,      //创建时间
+        update_time: None,      //修改时间
+    };
+    UserMfa::insert(rb, &mfa).await?;
+
+    let otpauth_uri = otpauth_uri(&data.mfa_config.issuer, &user.user_name, &secret);
+    Ok(MfaSetupResp {
+        qr_code: qr_code(&otpauth_uri)?,
+        secret,
+        otpauth_uri,
+        recovery_codes,
+    })
+}
+
+/*
+ *启用两步验证
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+pub async fn enable_mfa(data: &AppState, mfa: &UserMfa) -> AppResult<()> {
+    update_mfa_status(&data.batis, 1, &DateTime::now(), mfa.user_id).await?;
+    Ok(())
+}
+
+/*
+ *校验动态码或者恢复码(恢复码只能在启用后使用,使用后立即失效)
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+pub async fn verify_mfa_code(data: &AppState, mfa: &UserMfa, code: &str) -> AppResult<bool> {
+    let rb = &data.batis;
+    let now = DateTime::now();
+
+    if is_recovery_code(code) {
+        if mfa.status != 1 {
+            return Ok(false);
+        }
+        let hashes = split_codes(&mfa.recovery_codes);
+        let code = code.trim().to_lowercase();
+        return match hashes.iter().position(|x| data.password_util.verify(&code, x) != PasswordVerify::Invalid) {
+            None => Ok(false),
+            Some(index) => {
+                let left = hashes.iter().enumerate().filter(|(i, _)| *i != index).map(|(_, x)| *x).collect::<Vec<&str>>().join(",");
+                let result = update_recovery_codes(rb, &left, &now, mfa.user_id, &mfa.recovery_codes).await?;
+                Ok(result.rows_affected == 1)
+            }
+        };
+    }
+
+    match verify_code(&mfa.secret, code, now_secs() as u64, data.mfa_config.skew) {
+        Some(step) if step as i64 > mfa.last_used_step => {
+            let result = update_last_used_step(rb, step as i64, &now, mfa.user_id, step as i64).await?;
+            Ok(result.rows_affected == 1)
+        }
+        _ => Ok(false),
+    }
+}
+
+// 生成恢复码,返回明文和逗号分隔的哈希
+fn new_recovery_codes(data: &AppState) -> AppResult<(Vec<String>, String)> {
+    let codes = generate_recovery_codes(data.mfa_config.recovery_codes);
+    let hashes = codes.iter().map(|x| data.password_util.hash(x)).collect::<AppResult<Vec<String>>>()?;
+    Ok((codes, hashes.join(",")))
+}
+
+fn split_codes(codes: &str) -> Vec<&str> {
+    codes.split(',').filter(|x| !x.is_empty()).collect()
+}
+
+fn current_user_id(http_req: &HttpRequest) -> AppResult<i64> {
+    match http_req.extensions().get::<JwtToken>() {
+        Some(token) => Ok(token.id),
+        None => Err(AppError::JwtTokenError("请先登录".to_string())),
+    }
+}
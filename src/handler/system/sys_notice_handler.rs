@@ -1,6 +1,9 @@
 use crate::common::error::{AppError, AppResult};
+use crate::common::error_code::ErrorCode;
 use crate::common::result::{ok_result, ok_result_data, ok_result_page};
-use crate::model::system::sys_notice_model::Notice;
+use crate::common::validate::ValidatedJson;
+use crate::model::system::sys_notice_model::{update_notice_status, Notice};
+use crate::utils::db::last_insert_id;
 use crate::vo::system::sys_notice_vo::*;
 use crate::AppState;
 use actix_web::{post, web, Responder};
@@ -14,18 +17,20 @@ use rbs::value;
  *date：2025/01/08 17:16:44
  */
 #[post("/system/notice/addNotice")]
-pub async fn add_sys_notice(item: web::Json<NoticeReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn add_sys_notice(item: ValidatedJson<NoticeReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("add sys_notice params: {:?}", &item);
     let rb = &data.batis;
 
     let mut req = item.0;
 
     if Notice::check_title_unique(rb, None, &req.notice_title).await?.is_some() {
-        return Err(AppError::BusinessError("公告标题已存在"));
+        return Err(AppError::from(ErrorCode::DuplicateNoticeTitle));
     };
 
     req.id = None;
-    Notice::insert(rb, &Notice::from(req)).await.map(|x| ok_result_data(x.last_insert_id))?
+    let conn = rb.acquire().await?;
+    let result = Notice::insert(&conn, &Notice::from(req)).await?;
+    ok_result_data(last_insert_id(&conn, &result).await?)
 }
 
 /*
@@ -34,7 +39,7 @@ pub async fn add_sys_notice(item: web::Json<NoticeReq>, data: web::Data<AppState
  *date：2025/01/08 17:16:44
  */
 #[post("/system/notice/deleteNotice")]
-pub async fn delete_sys_notice(item: web::Json<DeleteNoticeReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn delete_sys_notice(item: ValidatedJson<DeleteNoticeReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("delete sys_notice params: {:?}", &item);
     let rb = &data.batis;
 
@@ -47,7 +52,7 @@ pub async fn delete_sys_notice(item: web::Json<DeleteNoticeReq>, data: web::Data
  *date：2025/01/08 17:16:44
  */
 #[post("/system/notice/updateNotice")]
-pub async fn update_sys_notice(item: web::Json<NoticeReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn update_sys_notice(item: ValidatedJson<NoticeReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update sys_notice params: {:?}", &item);
     let rb = &data.batis;
     let req = item.0;
@@ -55,11 +60,11 @@ pub async fn update_sys_notice(item: web::Json<NoticeReq>, data: web::Data<AppSt
     let id = req.id;
 
     if Notice::select_by_id(rb, &id.unwrap_or_default()).await?.is_none() {
-        return Err(AppError::BusinessError("通知公告表不存在"));
+        return Err(AppError::from(ErrorCode::NoticeNotFound));
     }
 
     if Notice::check_title_unique(rb, id, &req.notice_title).await?.is_some() {
-        return Err(AppError::BusinessError("公告标题已存在"));
+        return Err(AppError::from(ErrorCode::DuplicateNoticeTitle));
     }
 
     let mut data = Notice::from(req);
@@ -73,17 +78,12 @@ pub async fn update_sys_notice(item: web::Json<NoticeReq>, data: web::Data<AppSt
  *date：2025/01/08 17:16:44
  */
 #[post("/system/notice/updateNoticeStatus")]
-pub async fn update_sys_notice_status(item: web::Json<UpdateNoticeStatusReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn update_sys_notice_status(item: ValidatedJson<UpdateNoticeStatusReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update sys_notice_status params: {:?}", &item);
     let rb = &data.batis;
     let req = item.0;
 
-    let update_sql = format!("update sys_notice set status = ? where id in ({})", req.ids.iter().map(|_| "?").collect::<Vec<&str>>().join(", "));
-
-    let mut param = vec![value!(req.status)];
-    param.extend(req.ids.iter().map(|&id| value!(id)));
-
-    rb.exec(&update_sql, param).await.map(|_| ok_result())?
+    update_notice_status(rb, &req.ids, req.status).await.map(|_| ok_result())?
 }
 
 /*
@@ -92,12 +92,12 @@ pub async fn update_sys_notice_status(item: web::Json<UpdateNoticeStatusReq>, da
  *date：2025/01/08 17:16:44
  */
 #[post("/system/notice/queryNoticeDetail")]
-pub async fn query_sys_notice_detail(item: web::Json<QueryNoticeDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_notice_detail(item: ValidatedJson<QueryNoticeDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_notice_detail params: {:?}", &item);
     let rb = &data.batis;
 
     Notice::select_by_id(rb, &item.id).await?.map_or_else(
-        || Err(AppError::BusinessError("通知公告表不存在")),
+        || Err(AppError::from(ErrorCode::NoticeNotFound)),
         |x| {
             let notice: NoticeResp = x.into();
             ok_result_data(notice)
@@ -111,7 +111,7 @@ pub async fn query_sys_notice_detail(item: web::Json<QueryNoticeDetailReq>, data
  *date：2025/01/08 17:16:44
  */
 #[post("/system/notice/queryNoticeList")]
-pub async fn query_sys_notice_list(item: web::Json<QueryNoticeListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_notice_list(item: ValidatedJson<QueryNoticeListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_notice_list params: {:?}", &item);
     let rb = &data.batis;
 
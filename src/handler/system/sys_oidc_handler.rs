@@ -0,0 +1,236 @@
+use crate::common::error::{AppError, AppResult};
+use crate::common::error_code::ErrorCode;
+use crate::common::result::ok_result_data;
+use crate::handler::system::sys_user_handler::{add_login_log, complete_login};
+use crate::model::system::sys_user_identity_model::UserIdentity;
+use crate::model::system::sys_user_model::User;
+use crate::model::system::sys_user_role_model::UserRole;
+use crate::utils::db::{last_insert_id, transaction};
+use crate::utils::oidc_util::{OidcClaims, OIDC_AUTH};
+use crate::utils::user_agent_util::UserAgentUtil;
+use crate::vo::system::sys_oidc_vo::OidcCallbackReq;
+use crate::vo::system::sys_user_vo::UserLoginResp;
+use crate::AppState;
+use actix_web::http::header;
+use actix_web::{get, web, Either, HttpRequest, HttpResponse, Responder};
+use rbatis::executor::Executor;
+use rbatis::rbdc::datetime::DateTime;
+use rbs::value;
+use uuid::Uuid;
+
+/*
+ *单点登录: 跳转到身份提供方登录
+ *author：刘飞华
+ *date：2026/10/18 21:25:36
+ */
+#[get("/system/oidc/authorize")]
+pub async fn oidc_authorize(data: web::Data<AppState>) -> AppResult<impl Responder> {
+    if !data.oidc_client.config().enabled {
+        return Err(AppError::from(ErrorCode::SsoDisabled));
+    }
+    let url = data.oidc_client.authorize_url().await?;
+    Ok(HttpResponse::Found().insert_header((header::LOCATION, url)).finish())
+}
+
+/*
+ *单点登录回调: 使用授权码换取用户信息,匹配系统用户后签发token
+ *配置了前端地址时跳转到前端(token或者错误信息放在地址的#后面),否则直接返回token
+ *author：刘飞华
+ *date：2026/10/18 21:25:36
+ */
+#[get("/system/oidc/callback")]
+pub async fn oidc_callback(http_request: HttpRequest, item: web::Query<OidcCallbackReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("oidc callback params: state {:?}, error {:?}", &item.state, &item.error);
+    let result = oidc_login(&http_request, &item, &data).await;
+
+    let frontend_url = &data.oidc_client.config().frontend_url;
+    if frontend_url.is_empty() {
+        return Ok(Either::Left(ok_result_data(result?)?));
+    }
+
+    let mut fragment = url::form_urlencoded::Serializer::new(String::new());
+    match result {
+        Ok(token) => fragment
+            .append_pair("token", &token.token)
+            .append_pair("refreshToken", &token.refresh_token)
+            .append_pair("expiresIn", &token.expires_in.to_string()),
+        Err(err) => fragment.append_pair("error", &err.to_string()),
+    };
+    let location = format!("{}#{}", frontend_url.split('#').next().unwrap_or_default(), fragment.finish());
+    Ok(Either::Right(HttpResponse::Found().insert_header((header::LOCATION, location)).finish()))
+}
+
+/*
+ *单点登录: 校验回调,匹配或者创建用户,完成登录
+ *author：刘飞华
+ *date：2026/10/18 21:25:36
+ */
+async fn oidc_login(http_request: &HttpRequest, item: &OidcCallbackReq, data: &AppState) -> AppResult<UserLoginResp> {
+    if !data.oidc_client.config().enabled {
+        return Err(AppError::from(ErrorCode::SsoDisabled));
+    }
+
+    let user_agent = http_request.headers().get("User-Agent").and_then(|x| x.to_str().ok()).unwrap_or_default();
+    let agent = UserAgentUtil::new(user_agent);
+    let ipaddr = data.ip_util.client_ip(http_request.peer_addr(), http_request.headers());
+    let location = data.ip_util.location(&ipaddr);
+
+    if let Some(error) = item.error.as_deref() {
+        let msg = format!("单点登录失败: {} {}", error, item.error_description.as_deref().unwrap_or_default());
+        add_login_log(&data.batis, "".to_string(), 0, &msg, agent, &ipaddr, &location).await;
+        return Err(AppError::AuthError(msg));
+    }
+
+    let (code, state) = match (item.code.as_deref(), item.state.as_deref()) {
+        (Some(code), Some(state)) if !code.is_empty() && !state.is_empty() => (code, state),
+        _ => return Err(AppError::business(ErrorCode::BadRequest, "缺少授权码")),
+    };
+
+    let claims = match data.oidc_client.exchange(code, state).await {
+        Ok(claims) => claims,
+        Err(err) => {
+            log::error!("oidc exchange error: {}", err);
+            add_login_log(&data.batis, "".to_string(), 0, "单点登录失败: 身份校验不通过", agent, &ipaddr, &location).await;
+            return Err(err);
+        }
+    };
+
+    let user = match find_or_create_user(data, &claims).await {
+        Ok(user) => user,
+        Err(err) => {
+            add_login_log(&data.batis, claims.sub.clone(), 0, &format!("单点登录失败: {}", err), agent, &ipaddr, &location).await;
+            return Err(err);
+        }
+    };
+    if user.status != 1 {
+        add_login_log(&data.batis, user.user_name, 0, "单点登录失败: 用户已被禁用", agent, &ipaddr, &location).await;
+        return Err(AppError::from(ErrorCode::UserDisabled));
+    }
+
+    let login_name = user.user_name.clone();
+    complete_login(data, user, login_name, agent, &ipaddr, &location).await
+}
+
+/*
+ *根据身份提供方的用户信息查询系统用户: 已绑定的用户 -> 邮箱相同的用户(需要开启按邮箱匹配) -> 自动创建用户(需要开启自动创建)
+ *author：刘飞华
+ *date：2026/10/18 21:25:36
+ */
+async fn find_or_create_user(data: &AppState, claims: &OidcClaims) -> AppResult<User> {
+    let rb = &data.batis;
+    let config = data.oidc_client.config();
+
+    if let Some(identity) = UserIdentity::select_by_subject(rb, &claims.iss, &claims.sub).await? {
+        if let Some(user) = User::select_by_id(rb, identity.user_id).await? {
+            return Ok(user);
+        }
+        //绑定的用户已被删除,重新匹配
+        UserIdentity::delete_by_map(rb, value! {"id": identity.id}).await?;
+    }
+
+    if config.match_email && claims.email_verified && !claims.email.is_empty() {
+        if let Some(user) = User::select_by_email(rb, &claims.email).await? {
+            add_identity(rb, user.id.unwrap_or_default(), claims).await?;
+            return Ok(user);
+        }
+    }
+
+    if !config.auto_provision {
+        return Err(AppError::business(ErrorCode::UserNotFound, "用户不存在,请联系管理员开通账号"));
+    }
+
+    let user_name = new_user_name(data, claims).await?;
+    let email = match claims.email.as_str() {
+        "" => "".to_string(),
+        email if User::select_by_email(rb, email).await?.is_some() => "".to_string(),
+        email => email.to_string(),
+    };
+    let mobile = match claims.phone_number.trim_start_matches("+86") {
+        x if x.len() == 11 && x.bytes().all(|c| c.is_ascii_digit()) && User::select_by_mobile(rb, x).await?.is_none() => x.to_string(),
+        _ => "".to_string(),
+    };
+    let nick_name = match claims.name.as_str() {
+        "" => user_name.clone(),
+        name => name.to_string(),
+    };
+
+    let mut user = User {
+        id: None,                                                        //主键
+        mobile,                                                          //手机
+        user_name,                                                       //用户账号
+        nick_name: nick_name.chars().take(30).collect(),                 //用户昵称
+        user_type: Some("00".to_string()),                               //用户类型（00系统用户）
+        email,                                                           //用户邮箱
+        avatar: None,                                                    //头像路径
+        password: data.password_util.hash(&Uuid::new_v4().to_string())?, //随机密码,只能通过单点登录
+        auth_source: OIDC_AUTH.to_string(),                              //认证方式
+        status: 1,                                                       //状态(1:正常，0:禁用)
+        dept_id: config.default_dept_id,                                 //部门ID
+        login_ip: "".to_string(),                                        //最后登录IP
+        login_date: None,                                                //最后登录时间
+        login_browser: "".to_string(),                                   //浏览器类型
+        login_os: "".to_string(),                                        //操作系统
+        pwd_update_date: None,                                           //密码最后更新时间
+        remark: Some("单点登录自动创建".to_string()),                    //备注
+        locale: "".to_string(),                                          //语言偏好,为空时根据请求头Accept-Language选择语言
+        del_flag: 1,                                                     //删除标志（0代表删除 1代表存在）
+        create_time: None,                                               //创建时间
+        update_time: None,                                               //修改时间
+    };
+    //用户,角色和身份绑定在同一个事务中写入,避免中途失败留下没有角色和身份绑定的用户
+    let id = transaction(rb, async |tx| {
+        let result = User::insert(tx, &user).await?;
+        let id = last_insert_id(tx, &result).await?;
+
+        let roles = config
+            .default_role_ids
+            .iter()
+            .map(|role_id| UserRole {
+                id: None,                           //主键
+                user_id: id,                        //用户ID
+                role_id: *role_id,                  //角色ID
+                create_time: Some(DateTime::now()), //创建时间
+            })
+            .collect::<Vec<UserRole>>();
+        if !roles.is_empty() {
+            UserRole::insert_batch(tx, &roles, roles.len() as u64).await?;
+        }
+
+        add_identity(tx, id, claims).await?;
+        Ok(id)
+    })
+    .await?;
+    user.id = Some(id);
+    log::info!("oidc auto provision user: {}, subject: {}", user.user_name, claims.sub);
+    Ok(user)
+}
+
+/*
+ *绑定用户和身份提供方的用户
+ *author：刘飞华
+ *date：2026/10/18 21:25:36
+ */
+async fn add_identity(rb: &dyn Executor, user_id: i64, claims: &OidcClaims) -> AppResult<()> {
+    let identity = UserIdentity {
+        id: None,                    //主键
+        user_id,                     //用户ID
+        issuer: claims.iss.clone(),  //身份提供方
+        subject: claims.sub.clone(), //用户在身份提供方的唯一标识
+        email: claims.email.clone(), //绑定时的邮箱
+        create_time: None,           //创建时间
+        update_time: None,           //修改时间
+    };
+    UserIdentity::insert(rb, &identity).await?;
+    Ok(())
+}
+
+// 自动创建用户的账号: 依次使用身份提供方的用户名,邮箱,唯一标识中第一个没有被占用的
+async fn new_user_name(data: &AppState, claims: &OidcClaims) -> AppResult<String> {
+    let candidates = [claims.preferred_username.clone(), claims.email.clone(), format!("{}_{}", OIDC_AUTH, claims.sub)];
+    for user_name in candidates.iter().filter(|x| !x.is_empty() && x.chars().count() <= 50) {
+        if User::select_by_user_name(&data.batis, user_name).await?.is_none() {
+            return Ok(user_name.clone());
+        }
+    }
+    Ok(format!("{}_{}", OIDC_AUTH, Uuid::new_v4().simple()))
+}
@@ -0,0 +1,96 @@
+use crate::common::error::{AppError, AppResult};
+use crate::common::error_code::ErrorCode;
+use crate::common::result::{ok_result, ok_result_data, ok_result_page};
+use crate::common::validate::ValidatedJson;
+use crate::model::system::sys_user_session_model::{update_sessions_offline, update_user_session_offline, UserSession};
+use crate::utils::token_util::now_secs;
+use crate::vo::system::sys_online_vo::*;
+use crate::AppState;
+use actix_web::{post, web, Responder};
+use rbatis::plugin::page::PageRequest;
+use rbatis::rbdc::DateTime;
+use rbs::value;
+
+/*
+ *查询在线用户列表
+ *author：刘飞华
+ *date：2026/10/18 15:31:20
+ */
+#[post("/system/online/queryOnlineList")]
+pub async fn query_online_list(item: ValidatedJson<QueryOnlineListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("query online_list params: {:?}", &item);
+    let rb = &data.batis;
+
+    let user_name = item.user_name.as_deref().unwrap_or_default(); //用户账号
+    let ipaddr = item.ipaddr.as_deref().unwrap_or_default(); //登录IP地址
+
+    let page = &PageRequest::new(item.page_no, item.page_size);
+    UserSession::select_online_list(rb, page, user_name, ipaddr, &DateTime::now())
+        .await
+        .map(|x| ok_result_page(x.records.into_iter().map(|x| x.into()).collect::<Vec<OnlineResp>>(), x.total))?
+}
+
+/*
+ *查询在线用户会话详情
+ *author：刘飞华
+ *date：2026/10/18 15:31:20
+ */
+#[post("/system/online/queryOnlineDetail")]
+pub async fn query_online_detail(item: ValidatedJson<QueryOnlineDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("query online_detail params: {:?}", &item);
+    let rb = &data.batis;
+
+    UserSession::select_by_session_id(rb, &item.session_id).await?.map_or_else(
+        || Err(AppError::from(ErrorCode::SessionNotFound)),
+        |x| {
+            let data: OnlineResp = x.into();
+            ok_result_data(data)
+        },
+    )
+}
+
+/*
+ *强制下线会话
+ *author：刘飞华
+ *date：2026/10/18 15:31:20
+ */
+#[post("/system/online/forceLogout")]
+pub async fn force_logout(item: ValidatedJson<ForceLogoutReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("force logout params: {:?}", &item);
+    let rb = &data.batis;
+
+    let exp = now_secs() + data.jwt_keys.refresh_ttl as usize;
+    for session_id in &item.session_ids {
+        data.token_store.revoke_session(session_id, exp);
+    }
+
+    update_sessions_offline(rb, &item.session_ids).await.map(|_| ok_result())?
+}
+
+/*
+ *强制下线用户的全部会话
+ *author：刘飞华
+ *date：2026/10/18 15:31:20
+ */
+#[post("/system/online/forceLogoutUser")]
+pub async fn force_logout_user(item: ValidatedJson<ForceLogoutUserReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("force logout user params: {:?}", &item);
+
+    logout_user_sessions(&data, item.user_id).await.map(|_| ok_result())?
+}
+
+/*
+ *下线用户的全部会话(禁用用户,重置密码,强制下线等)
+ *author：刘飞华
+ *date：2026/10/18 15:31:20
+ */
+pub async fn logout_user_sessions(data: &AppState, user_id: i64) -> AppResult<()> {
+    //按会话吊销,和吊销时间在同一秒内签发的旧token也能立即失效
+    let exp = now_secs() + data.jwt_keys.refresh_ttl as usize;
+    for session in UserSession::select_by_map(&data.batis, value! {"user_id": user_id, "status": 1}).await? {
+        data.token_store.revoke_session(&session.session_id, exp);
+    }
+    data.token_store.revoke_user(user_id);
+    update_user_session_offline(&data.batis, user_id).await?;
+    Ok(())
+}
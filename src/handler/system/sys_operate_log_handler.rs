@@ -1,9 +1,13 @@
 use crate::common::error::{AppError, AppResult};
+use crate::common::error_code::ErrorCode;
 use crate::common::result::{ok_result, ok_result_data, ok_result_page};
+use crate::common::validate::ValidatedJson;
 use crate::model::system::sys_operate_log_model::{clean_operate_log, OperateLog};
+use crate::utils::data_scope_util::query_data_scope;
+use crate::utils::jwt_util::JwtToken;
 use crate::vo::system::sys_operate_log_vo::*;
 use crate::AppState;
-use actix_web::{post, web, Responder};
+use actix_web::{post, web, HttpMessage, HttpRequest, Responder};
 use rbatis::plugin::page::PageRequest;
 use rbs::value;
 
@@ -13,7 +17,7 @@ use rbs::value;
  *date：2025/01/08 17:16:44
  */
 #[post("/system/operateLog/deleteOperateLog")]
-pub async fn delete_sys_operate_log(item: web::Json<DeleteOperateLogReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn delete_sys_operate_log(item: ValidatedJson<DeleteOperateLogReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("delete sys_operate_log params: {:?}", &item);
     let rb = &data.batis;
 
@@ -39,12 +43,12 @@ pub async fn clean_sys_operate_log(data: web::Data<AppState>) -> AppResult<impl
  *date：2025/01/08 17:16:44
  */
 #[post("/system/operateLog/queryOperateLogDetail")]
-pub async fn query_sys_operate_log_detail(item: web::Json<QueryOperateLogDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_operate_log_detail(item: ValidatedJson<QueryOperateLogDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_operate_log_detail params: {:?}", &item);
     let rb = &data.batis;
 
     OperateLog::select_by_id(rb, &item.id).await?.map_or_else(
-        || Err(AppError::BusinessError("操作日志不存在")),
+        || Err(AppError::from(ErrorCode::OperateLogNotFound)),
         |x| {
             let data: OperateLogResp = x.into();
             ok_result_data(data)
@@ -58,7 +62,7 @@ pub async fn query_sys_operate_log_detail(item: web::Json<QueryOperateLogDetailR
  *date：2025/01/08 17:16:44
  */
 #[post("/system/operateLog/queryOperateLogList")]
-pub async fn query_sys_operate_log_list(item: web::Json<QueryOperateLogListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_operate_log_list(http_req: HttpRequest, item: ValidatedJson<QueryOperateLogListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_operate_log_list params: {:?}", &item);
     let rb = &data.batis;
 
@@ -72,6 +76,8 @@ pub async fn query_sys_operate_log_list(item: web::Json<QueryOperateLogListReq>,
     let operate_url = item.operate_url.as_deref().unwrap_or_default(); //请求URL
     let operate_ip = item.operate_ip.as_deref().unwrap_or_default(); //主机地址
     let status = item.status.unwrap_or(2); //操作状态(0:异常,正常)
+    let user_id = http_req.extensions().get::<JwtToken>().map(|x| x.id).unwrap_or_default();
+    let scope = query_data_scope(rb, user_id).await?;
 
     let page = &PageRequest::new(item.page_no, item.page_size);
     OperateLog::select_page_by_name(
@@ -87,6 +93,7 @@ pub async fn query_sys_operate_log_list(item: web::Json<QueryOperateLogListReq>,
         operate_url,
         operate_ip,
         &status,
+        &scope,
     )
     .await
     .map(|x| ok_result_page(x.records.into_iter().map(|x| x.into()).collect::<Vec<OperateLogResp>>(), x.total))?
@@ -1,7 +1,10 @@
 use crate::common::error::{AppError, AppResult};
+use crate::common::error_code::ErrorCode;
 use crate::common::result::{ok_result, ok_result_data, ok_result_page};
-use crate::model::system::sys_post_model::Post;
+use crate::common::validate::ValidatedJson;
+use crate::model::system::sys_post_model::{update_post_status, Post};
 use crate::model::system::sys_user_post_model::count_user_post_by_id;
+use crate::utils::db::last_insert_id;
 use crate::vo::system::sys_post_vo::*;
 use crate::AppState;
 use actix_web::{post, web, Responder};
@@ -15,21 +18,23 @@ use rbs::value;
  *date：2025/01/08 17:16:44
  */
 #[post("/system/post/addPost")]
-pub async fn add_sys_post(item: web::Json<PostReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn add_sys_post(item: ValidatedJson<PostReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("add sys_post params: {:?}", &item);
     let rb = &data.batis;
 
     let req = item.0;
 
     if Post::select_by_name(rb, &req.post_name).await?.is_some() {
-        return Err(AppError::BusinessError("岗位名称已存在"));
+        return Err(AppError::from(ErrorCode::DuplicatePostName));
     }
 
     if Post::select_by_code(rb, &req.post_code).await?.is_some() {
-        return Err(AppError::BusinessError("岗位编码已存在"));
+        return Err(AppError::from(ErrorCode::DuplicatePostCode));
     }
 
-    Post::insert(rb, &Post::from(req)).await.map(|x| ok_result_data(x.last_insert_id))?
+    let conn = rb.acquire().await?;
+    let result = Post::insert(&conn, &Post::from(req)).await?;
+    ok_result_data(last_insert_id(&conn, &result).await?)
 }
 
 /*
@@ -38,14 +43,14 @@ pub async fn add_sys_post(item: web::Json<PostReq>, data: web::Data<AppState>) -
  *date：2025/01/08 17:16:44
  */
 #[post("/system/post/deletePost")]
-pub async fn delete_sys_post(item: web::Json<DeletePostReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn delete_sys_post(item: ValidatedJson<DeletePostReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("delete sys_post params: {:?}", &item);
     let rb = &data.batis;
 
     let ids = item.ids.clone();
     for id in ids {
         if count_user_post_by_id(rb, id).await? > 0 {
-            return Err(AppError::BusinessError("已分配,不能删除"));
+            return Err(AppError::business(ErrorCode::DataInUse, "已分配,不能删除"));
         }
     }
 
@@ -58,7 +63,7 @@ pub async fn delete_sys_post(item: web::Json<DeletePostReq>, data: web::Data<App
  *date：2025/01/08 17:16:44
  */
 #[post("/system/post/updatePost")]
-pub async fn update_sys_post(item: web::Json<PostReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn update_sys_post(item: ValidatedJson<PostReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update sys_post params: {:?}", &item);
     let rb = &data.batis;
     let req = item.0;
@@ -66,18 +71,18 @@ pub async fn update_sys_post(item: web::Json<PostReq>, data: web::Data<AppState>
     let id = req.id;
 
     if Post::select_by_id(rb, &id.unwrap_or_default()).await?.is_none() {
-        return Err(AppError::BusinessError("岗位不存在"));
+        return Err(AppError::from(ErrorCode::PostNotFound));
     }
 
     if let Some(x) = Post::select_by_name(rb, &req.post_name).await? {
         if x.id != id {
-            return Err(AppError::BusinessError("岗位名称已存在"));
+            return Err(AppError::from(ErrorCode::DuplicatePostName));
         }
     }
 
     if let Some(x) = Post::select_by_code(rb, &req.post_code).await? {
         if x.id != id {
-            return Err(AppError::BusinessError("岗位编码已存在"));
+            return Err(AppError::from(ErrorCode::DuplicatePostCode));
         }
     }
 
@@ -92,16 +97,12 @@ pub async fn update_sys_post(item: web::Json<PostReq>, data: web::Data<AppState>
  *date：2025/01/08 17:16:44
  */
 #[post("/system/post/updatePostStatus")]
-pub async fn update_sys_post_status(item: web::Json<UpdatePostStatusReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn update_sys_post_status(item: ValidatedJson<UpdatePostStatusReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update sys_post_status params: {:?}", &item);
     let rb = &data.batis;
     let req = item.0;
 
-    let update_sql = format!("update sys_post set status = ? where id in ({})", req.ids.iter().map(|_| "?").collect::<Vec<&str>>().join(", "));
-
-    let mut param = vec![value!(req.status)];
-    param.extend(req.ids.iter().map(|&id| value!(id)));
-    rb.exec(&update_sql, param).await.map(|_| ok_result())?
+    update_post_status(rb, &req.ids, req.status).await.map(|_| ok_result())?
 }
 
 /*
@@ -110,12 +111,12 @@ pub async fn update_sys_post_status(item: web::Json<UpdatePostStatusReq>, data:
  *date：2025/01/08 17:16:44
  */
 #[post("/system/post/queryPostDetail")]
-pub async fn query_sys_post_detail(item: web::Json<QueryPostDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_post_detail(item: ValidatedJson<QueryPostDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_post_detail params: {:?}", &item);
     let rb = &data.batis;
 
     Post::select_by_id(rb, &item.id).await?.map_or_else(
-        || Err(AppError::BusinessError("岗位不存在")),
+        || Err(AppError::from(ErrorCode::PostNotFound)),
         |x| {
             let data: PostResp = x.into();
             ok_result_data(data)
@@ -129,7 +130,7 @@ pub async fn query_sys_post_detail(item: web::Json<QueryPostDetailReq>, data: we
  *date：2025/01/08 17:16:44
  */
 #[post("/system/post/queryPostList")]
-pub async fn query_sys_post_list(item: web::Json<QueryPostListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_post_list(item: ValidatedJson<QueryPostListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_post_list params: {:?}", &item);
     let rb = &data.batis;
 
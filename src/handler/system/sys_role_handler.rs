@@ -1,11 +1,17 @@
 use crate::common::error::{AppError, AppResult};
+use crate::common::error_code::ErrorCode;
 use crate::common::result::{ok_result, ok_result_data, ok_result_page};
+use crate::common::validate::ValidatedJson;
+use crate::model::system::sys_dept_model::Dept;
 use crate::model::system::sys_menu_model::Menu;
 use crate::model::system::sys_role_dept_model::RoleDept;
 use crate::model::system::sys_role_menu_model::{query_menu_by_role, RoleMenu};
-use crate::model::system::sys_role_model::Role;
+use crate::model::system::sys_role_model::{update_data_scope, update_role_del_flag, update_role_status, Role};
 use crate::model::system::sys_user_model::{count_allocated_list, count_unallocated_list, select_allocated_list, select_unallocated_list};
-use crate::model::system::sys_user_role_model::{count_user_role_by_role_id, delete_user_role_by_role_id_user_id, UserRole};
+use crate::model::system::sys_user_role_model::{count_user_role_by_role_id, delete_user_role_by_role_id_user_id, delete_user_roles, UserRole};
+use crate::utils::data_scope_util::{DATA_SCOPE_ALL, DATA_SCOPE_CUSTOM, DATA_SCOPE_SELF};
+use crate::utils::db::{last_insert_id, transaction};
+use crate::vo::system::sys_dept_vo::DeptResp;
 use crate::vo::system::sys_role_vo::*;
 use crate::vo::system::sys_user_vo::UserResp;
 use crate::AppState;
@@ -19,21 +25,23 @@ use rbs::value;
  *date：2025/01/08 17:16:44
  */
 #[post("/system/role/addRole")]
-pub async fn add_sys_role(item: web::Json<RoleReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn add_sys_role(item: ValidatedJson<RoleReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("add sys_role params: {:?}", &item);
     let rb = &data.batis;
 
     let req = item.0;
 
     if Role::select_by_role_name(rb, &req.role_name).await?.is_some() {
-        return Err(AppError::BusinessError("角色名称已存在"));
+        return Err(AppError::from(ErrorCode::DuplicateRoleName));
     }
 
     if Role::select_by_role_key(rb, &req.role_key).await?.is_some() {
-        return Err(AppError::BusinessError("角色权限已存在"));
+        return Err(AppError::from(ErrorCode::DuplicateRoleKey));
     }
 
-    Role::insert(rb, &Role::from(req)).await.map(|x| ok_result_data(x.last_insert_id))?
+    let conn = rb.acquire().await?;
+    let result = Role::insert(&conn, &Role::from(req)).await?;
+    ok_result_data(last_insert_id(&conn, &result).await?)
 }
 
 /*
@@ -42,25 +50,102 @@ pub async fn add_sys_role(item: web::Json<RoleReq>, data: web::Data<AppState>) -
  *date：2025/01/08 17:16:44
  */
 #[post("/system/role/deleteRole")]
-pub async fn delete_sys_role(item: web::Json<DeleteRoleReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn delete_sys_role(item: ValidatedJson<DeleteRoleReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("delete sys_role params: {:?}", &item);
     let rb = &data.batis;
 
     let ids = item.ids.clone();
 
     if ids.contains(&1) {
-        return Err(AppError::BusinessError("不允许操作超级管理员角色"));
+        return Err(AppError::business(ErrorCode::SuperAdminProtected, "不允许操作超级管理员角色"));
     }
 
     for id in ids {
         if count_user_role_by_role_id(rb, id).await? > 0 {
-            return Err(AppError::BusinessError("已分配,不能删除"));
+            return Err(AppError::business(ErrorCode::DataInUse, "已分配,不能删除"));
         }
     }
 
-    RoleMenu::delete_by_map(rb, value! {"role_id": &item.ids}).await?;
-    RoleDept::delete_by_map(rb, value! {"role_id": &item.ids}).await?;
-    Role::delete_by_map(rb, value! {"id": &item.ids}).await.map(|_| ok_result())?
+    //逻辑删除,保留角色的菜单和数据权限,从回收站恢复后可以继续使用;已删除用户的角色直接删除
+    transaction(rb, async |tx| {
+        UserRole::delete_by_map(tx, value! {"role_id": &item.ids}).await?;
+        update_role_del_flag(tx, &item.ids, 0, &DateTime::now()).await?;
+        Ok(())
+    })
+    .await?;
+    ok_result()
+}
+
+/*
+ *查询已删除的角色列表(回收站)
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+#[post("/system/role/queryDeletedRoleList")]
+pub async fn query_deleted_role_list(item: ValidatedJson<QueryDeletedRoleListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("query deleted sys_role_list params: {:?}", &item);
+    let rb = &data.batis;
+
+    let role_name = item.role_name.as_deref().unwrap_or_default();
+    let page = &PageRequest::new(item.page_no, item.page_size);
+    Role::select_deleted_role_list(rb, page, role_name)
+        .await
+        .map(|x| ok_result_page(x.records.into_iter().map(|x| x.into()).collect::<Vec<RoleResp>>(), x.total))?
+}
+
+/*
+ *从回收站恢复角色(名称和权限字符不能和未删除的角色重复)
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+#[post("/system/role/restoreRole")]
+pub async fn restore_sys_role(item: ValidatedJson<RestoreRoleReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("restore sys_role params: {:?}", &item);
+    let rb = &data.batis;
+
+    let roles = Role::select_deleted_by_ids(rb, &item.ids).await?;
+    if roles.len() != item.ids.len() {
+        return Err(AppError::business(ErrorCode::RoleNotFound, "回收站中不存在该角色"));
+    }
+
+    let mut restored: Vec<&Role> = Vec::new();
+    for role in &roles {
+        if restored.iter().any(|x| x.role_name == role.role_name) || Role::select_by_role_name(rb, &role.role_name).await?.is_some() {
+            return Err(AppError::from(ErrorCode::DuplicateRoleName));
+        }
+        if restored.iter().any(|x| x.role_key == role.role_key) || Role::select_by_role_key(rb, &role.role_key).await?.is_some() {
+            return Err(AppError::from(ErrorCode::DuplicateRoleKey));
+        }
+        restored.push(role);
+    }
+
+    update_role_del_flag(rb, &item.ids, 1, &DateTime::now()).await.map(|_| ok_result())?
+}
+
+/*
+ *彻底删除回收站中的角色,同时删除角色的菜单和数据权限
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+#[post("/system/role/purgeRole")]
+pub async fn purge_sys_role(item: ValidatedJson<PurgeRoleReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("purge sys_role params: {:?}", &item);
+    let rb = &data.batis;
+
+    let ids = &item.ids;
+    if Role::select_deleted_by_ids(rb, ids).await?.len() != ids.len() {
+        return Err(AppError::business(ErrorCode::RoleNotFound, "回收站中不存在该角色"));
+    }
+
+    transaction(rb, async |tx| {
+        UserRole::delete_by_map(tx, value! {"role_id": ids}).await?;
+        RoleMenu::delete_by_map(tx, value! {"role_id": ids}).await?;
+        RoleDept::delete_by_map(tx, value! {"role_id": ids}).await?;
+        Role::delete_by_map(tx, value! {"id": ids}).await?;
+        Ok(())
+    })
+    .await?;
+    ok_result()
 }
 
 /*
@@ -69,7 +154,7 @@ pub async fn delete_sys_role(item: web::Json<DeleteRoleReq>, data: web::Data<App
  *date：2025/01/08 17:16:44
  */
 #[post("/system/role/updateRole")]
-pub async fn update_sys_role(item: web::Json<RoleReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn update_sys_role(item: ValidatedJson<RoleReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update sys_role params: {:?}", &item);
     let rb = &data.batis;
     let req = item.0;
@@ -77,22 +162,22 @@ pub async fn update_sys_role(item: web::Json<RoleReq>, data: web::Data<AppState>
     let id = req.id;
 
     if id == Some(1) {
-        return Err(AppError::BusinessError("不允许操作超级管理员角色"));
+        return Err(AppError::business(ErrorCode::SuperAdminProtected, "不允许操作超级管理员角色"));
     }
 
     if Role::select_by_id(rb, &id.unwrap_or_default()).await?.is_none() {
-        return Err(AppError::BusinessError("角色不存在"));
+        return Err(AppError::from(ErrorCode::RoleNotFound));
     }
 
     if let Some(x) = Role::select_by_role_name(rb, &req.role_name).await? {
         if x.id != id {
-            return Err(AppError::BusinessError("角色名称已存在"));
+            return Err(AppError::from(ErrorCode::DuplicateRoleName));
         }
     }
 
     if let Some(x) = Role::select_by_role_key(rb, &req.role_key).await? {
         if x.id != id {
-            return Err(AppError::BusinessError("角色权限已存在"));
+            return Err(AppError::from(ErrorCode::DuplicateRoleKey));
         }
     }
 
@@ -107,20 +192,19 @@ pub async fn update_sys_role(item: web::Json<RoleReq>, data: web::Data<AppState>
  *date：2025/01/08 17:16:44
  */
 #[post("/system/role/updateRoleStatus")]
-pub async fn update_sys_role_status(item: web::Json<UpdateRoleStatusReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn update_sys_role_status(item: ValidatedJson<UpdateRoleStatusReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update sys_role_status params: {:?}", &item);
     let rb = &data.batis;
     let req = item.0;
 
     if req.ids.contains(&1) {
-        return Err(AppError::BusinessError("不允许操作超级管理员角色"));
+        return Err(AppError::business(ErrorCode::SuperAdminProtected, "不允许操作超级管理员角色"));
     }
 
-    let update_sql = format!("update sys_role set status = ? where id in ({})", req.ids.iter().map(|_| "?").collect::<Vec<&str>>().join(", "));
+    update_role_status(rb, &req.ids, req.status).await?;
 
-    let mut param = vec![value!(req.status)];
-    param.extend(req.ids.iter().map(|&id| value!(id)));
-    rb.exec(&update_sql, param).await.map(|_| ok_result())?
+    data.permission_cache.invalidate_all();
+    ok_result()
 }
 
 /*
@@ -129,12 +213,12 @@ pub async fn update_sys_role_status(item: web::Json<UpdateRoleStatusReq>, data:
  *date：2025/01/08 17:16:44
  */
 #[post("/system/role/queryRoleDetail")]
-pub async fn query_sys_role_detail(item: web::Json<QueryRoleDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_role_detail(item: ValidatedJson<QueryRoleDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_role_detail params: {:?}", &item);
     let rb = &data.batis;
 
     Role::select_by_id(rb, &item.id).await?.map_or_else(
-        || Err(AppError::BusinessError("角色不存在")),
+        || Err(AppError::from(ErrorCode::RoleNotFound)),
         |x| {
             let data: RoleResp = x.into();
             ok_result_data(data)
@@ -148,7 +232,7 @@ pub async fn query_sys_role_detail(item: web::Json<QueryRoleDetailReq>, data: we
  *date：2025/01/08 17:16:44
  */
 #[post("/system/role/queryRoleList")]
-pub async fn query_sys_role_list(item: web::Json<QueryRoleListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_role_list(item: ValidatedJson<QueryRoleListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_role_list params: {:?}", &item);
     let rb = &data.batis;
 
@@ -168,7 +252,7 @@ pub async fn query_sys_role_list(item: web::Json<QueryRoleListReq>, data: web::D
  *date：2025/01/08 17:16:44
  */
 #[post("/system/role/queryRoleMenu")]
-pub async fn query_role_menu(item: web::Json<QueryRoleMenuReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_role_menu(item: ValidatedJson<QueryRoleMenuReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query role_menu params: {:?}", &item);
     let rb = &data.batis;
 
@@ -211,18 +295,16 @@ pub async fn query_role_menu(item: web::Json<QueryRoleMenuReq>, data: web::Data<
  *date：2025/01/08 17:16:44
  */
 #[post("/system/role/updateRoleMenu")]
-pub async fn update_role_menu(item: web::Json<UpdateRoleMenuReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn update_role_menu(item: ValidatedJson<UpdateRoleMenuReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update role_menu params: {:?}", &item);
     let role_id = item.role_id;
 
     if role_id == 1 {
-        return Err(AppError::BusinessError("不允许操作超级管理员角色"));
+        return Err(AppError::business(ErrorCode::SuperAdminProtected, "不允许操作超级管理员角色"));
     }
 
     let rb = &data.batis;
 
-    RoleMenu::delete_by_map(rb, value! {"role_id": &role_id}).await?;
-
     let mut role_menu: Vec<RoleMenu> = Vec::new();
 
     for id in &item.menu_ids {
@@ -235,7 +317,84 @@ pub async fn update_role_menu(item: web::Json<UpdateRoleMenuReq>, data: web::Dat
         })
     }
 
-    RoleMenu::insert_batch(rb, &role_menu, item.menu_ids.len() as u64).await.map(|_| ok_result())?
+    transaction(rb, async |tx| {
+        RoleMenu::delete_by_map(tx, value! {"role_id": &role_id}).await?;
+        if !role_menu.is_empty() {
+            RoleMenu::insert_batch(tx, &role_menu, role_menu.len() as u64).await?;
+        }
+        Ok(())
+    })
+    .await?;
+
+    data.permission_cache.invalidate_all();
+    ok_result()
+}
+
+/*
+ *查询角色的数据权限
+ *author：刘飞华
+ *date：2026/10/18 17:42:10
+ */
+#[post("/system/role/queryRoleDept")]
+pub async fn query_role_dept(item: ValidatedJson<QueryRoleDeptReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("query role_dept params: {:?}", &item);
+    let rb = &data.batis;
+
+    let role = match Role::select_by_id(rb, &item.role_id).await? {
+        None => return Err(AppError::from(ErrorCode::RoleNotFound)),
+        Some(role) => role,
+    };
+
+    let dept_list = Dept::select_all_not_deleted(rb).await?.into_iter().map(|x| x.into()).collect::<Vec<DeptResp>>();
+    let dept_ids = RoleDept::select_by_map(rb, value! {"role_id": item.role_id})
+        .await?
+        .into_iter()
+        .map(|x| x.dept_id)
+        .collect::<Vec<i64>>();
+
+    ok_result_data(QueryRoleDeptData {
+        data_scope: role.data_scope,
+        dept_ids,
+        dept_list,
+    })
+}
+
+/*
+ *更新角色的数据权限(自定数据权限时保存角色关联的部门)
+ *author：刘飞华
+ *date：2026/10/18 17:42:10
+ */
+#[post("/system/role/updateRoleDept")]
+pub async fn update_role_dept(item: ValidatedJson<UpdateRoleDeptReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("update role_dept params: {:?}", &item);
+    let rb = &data.batis;
+    let role_id = item.role_id;
+
+    if role_id == 1 {
+        return Err(AppError::business(ErrorCode::SuperAdminProtected, "不允许操作超级管理员角色"));
+    }
+
+    if item.data_scope < DATA_SCOPE_ALL || item.data_scope > DATA_SCOPE_SELF {
+        return Err(AppError::business(ErrorCode::BadRequest, "数据范围不正确"));
+    }
+
+    if Role::select_by_id(rb, &role_id).await?.is_none() {
+        return Err(AppError::from(ErrorCode::RoleNotFound));
+    }
+
+    transaction(rb, async |tx| {
+        update_data_scope(tx, &item.data_scope, &DateTime::now(), &role_id).await?;
+        RoleDept::delete_by_map(tx, value! {"role_id": &role_id}).await?;
+
+        if item.data_scope == DATA_SCOPE_CUSTOM && !item.dept_ids.is_empty() {
+            let role_dept = item.dept_ids.iter().map(|&dept_id| RoleDept { role_id, dept_id }).collect::<Vec<RoleDept>>();
+            RoleDept::insert_batch(tx, &role_dept, item.dept_ids.len() as u64).await?;
+        }
+        Ok(())
+    })
+    .await?;
+
+    ok_result()
 }
 
 /*
@@ -244,7 +403,7 @@ pub async fn update_role_menu(item: web::Json<UpdateRoleMenuReq>, data: web::Dat
  *date：2025/01/08 17:16:44
  */
 #[post("/system/role/queryAllocatedList")]
-pub async fn query_allocated_list(item: web::Json<AllocatedListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_allocated_list(item: ValidatedJson<AllocatedListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update role_menu params: {:?}", &item);
 
     let rb = &data.batis;
@@ -273,7 +432,7 @@ pub async fn query_allocated_list(item: web::Json<AllocatedListReq>, data: web::
  *date：2025/01/08 17:16:44
  */
 #[post("/system/role/queryUnallocatedList")]
-pub async fn query_unallocated_list(item: web::Json<UnallocatedListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_unallocated_list(item: ValidatedJson<UnallocatedListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update role_menu params: {:?}", &item);
 
     let rb = &data.batis;
@@ -302,12 +461,15 @@ pub async fn query_unallocated_list(item: web::Json<UnallocatedListReq>, data: w
  *date：2025/01/08 17:16:44
  */
 #[post("/system/role/cancelAuthUser")]
-pub async fn cancel_auth_user(item: web::Json<CancelAuthUserReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn cancel_auth_user(item: ValidatedJson<CancelAuthUserReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update role_menu params: {:?}", &item);
 
     let rb = &data.batis;
 
-    delete_user_role_by_role_id_user_id(rb, item.role_id, item.user_id).await.map(|_| ok_result())?
+    delete_user_role_by_role_id_user_id(rb, item.role_id, item.user_id).await?;
+
+    data.permission_cache.invalidate_user(item.user_id);
+    ok_result()
 }
 
 /*
@@ -316,19 +478,17 @@ pub async fn cancel_auth_user(item: web::Json<CancelAuthUserReq>, data: web::Dat
  *date：2025/01/08 17:16:44
  */
 #[post("/system/role/batchCancelAuthUser")]
-pub async fn batch_cancel_auth_user(item: web::Json<CancelAuthUserAllReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn batch_cancel_auth_user(item: ValidatedJson<CancelAuthUserAllReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("cancel auth_user_all params: {:?}", &item);
 
     let rb = &data.batis;
 
-    let update_sql = format!(
-        "delete from sys_user_role where role_id = ? and user_id in ({})",
-        item.user_ids.iter().map(|_| "?").collect::<Vec<&str>>().join(", ")
-    );
+    delete_user_roles(rb, item.role_id, &item.user_ids).await?;
 
-    let mut param = vec![value!(item.role_id)];
-    param.extend(item.user_ids.iter().map(|&id| value!(id)));
-    rb.exec(&update_sql, param).await.map(|_| ok_result())?
+    for user_id in &item.user_ids {
+        data.permission_cache.invalidate_user(*user_id);
+    }
+    ok_result()
 }
 
 /*
@@ -337,7 +497,7 @@ pub async fn batch_cancel_auth_user(item: web::Json<CancelAuthUserAllReq>, data:
  *date：2025/01/08 17:16:44
  */
 #[post("/system/role/batchAuthUser")]
-pub async fn batch_auth_user(item: web::Json<SelectAuthUserAllReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn batch_auth_user(item: ValidatedJson<SelectAuthUserAllReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("select all_auth_user params: {:?}", &item);
     let role_id = item.role_id;
 
@@ -355,5 +515,10 @@ pub async fn batch_auth_user(item: web::Json<SelectAuthUserAllReq>, data: web::D
         })
     }
 
-    UserRole::insert_batch(rb, &user_role, item.user_ids.len() as u64).await.map(|_| ok_result())?
+    UserRole::insert_batch(rb, &user_role, item.user_ids.len() as u64).await?;
+
+    for user_id in &item.user_ids {
+        data.permission_cache.invalidate_user(*user_id);
+    }
+    ok_result()
 }
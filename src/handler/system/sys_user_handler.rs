@@ -1,57 +1,101 @@
 use crate::common::error::{AppError, AppResult};
+use crate::common::error_code::ErrorCode;
+use crate::common::i18n::{t_args, Locale};
 use crate::common::result::{ok_result, ok_result_data, ok_result_page};
-use crate::model::system::sys_dept_model::Dept;
+use crate::common::validate::ValidatedJson;
+use crate::handler::system::sys_mfa_handler::{create_mfa_secret, enable_mfa, is_mfa_required, verify_mfa_code};
+use crate::handler::system::sys_online_handler::logout_user_sessions;
+use crate::model::system::sys_api_token_model::ApiToken;
+use crate::model::system::sys_dept_model::{select_children_dept_by_id, Dept};
 use crate::model::system::sys_login_log_model::LoginLog;
 use crate::model::system::sys_menu_model::Menu;
 use crate::model::system::sys_role_model::Role;
-use crate::model::system::sys_user_model::User;
+use crate::model::system::sys_user_identity_model::UserIdentity;
+use crate::model::system::sys_user_mfa_model::UserMfa;
+use crate::model::system::sys_user_model::{update_user_del_flag, update_user_locale, update_user_status, User};
+use crate::model::system::sys_user_password_history_model::{delete_before, UserPasswordHistory};
 use crate::model::system::sys_user_post_model::UserPost;
 use crate::model::system::sys_user_role_model::{is_admin, UserRole};
-use crate::utils::jwt_util::JwtToken;
+use crate::model::system::sys_user_session_model::{rotate_refresh_jti, update_session_offline, UserSession};
+use crate::utils::authenticator::LDAP_AUTH;
+use crate::utils::data_scope_util::query_data_scope;
+use crate::utils::db::{last_insert_id, transaction};
+use crate::utils::jwt_util::{JwtToken, ACCESS_TOKEN, MFA_TOKEN, PASSWORD_TOKEN, REFRESH_TOKEN};
+use crate::utils::login_limit_util::LoginCheck;
+use crate::utils::oidc_util::OIDC_AUTH;
+use crate::utils::password_util::PasswordVerify;
+use crate::utils::token_util::now_secs;
 use crate::utils::user_agent_util::UserAgentUtil;
 use crate::vo::system::sys_dept_vo::DeptResp;
+use crate::vo::system::sys_mfa_vo::{LoginMfaReq, LoginMfaSetupReq, MfaChallengeResp};
 use crate::vo::system::sys_role_vo::RoleResp;
 use crate::vo::system::sys_user_vo::*;
 use crate::AppState;
-use actix_web::{get, post, web, HttpRequest, Responder};
+use actix_web::{get, post, web, Either, HttpMessage, HttpRequest, Responder};
+use rbatis::executor::Executor;
 use rbatis::plugin::page::PageRequest;
-use rbatis::rbatis_codegen::ops::AsProxy;
 use rbatis::rbdc::datetime::DateTime;
 use rbatis::RBatis;
 use rbs::value;
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+//账号或者IP被锁定时记录的登录日志
+const LOCKED_MSG: &str = "登录失败次数过多,账号或者IP已被锁定";
 /*
  *添加用户信息
  *author：刘飞华
  *date：2025/01/08 17:16:44
  */
 #[post("/system/user/addUser")]
-pub async fn add_sys_user(item: web::Json<UserReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn add_sys_user(item: ValidatedJson<UserReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("add sys_user params: {:?}", &item);
     let rb = &data.batis;
 
     let req = item.0;
 
     if User::select_by_user_name(rb, &req.user_name).await?.is_some() {
-        return Err(AppError::BusinessError("登录账号已存在"));
+        return Err(AppError::with_args(ErrorCode::DuplicateUserName, &[&req.user_name]));
     }
 
-    if User::select_by_mobile(rb, &req.mobile).await?.is_some() {
-        return Err(AppError::BusinessError("手机号码已存在"));
+    if !req.mobile.is_empty() && User::select_by_mobile(rb, &req.mobile).await?.is_some() {
+        return Err(AppError::with_args(ErrorCode::DuplicateMobile, &[&req.mobile]));
     }
 
-    if User::select_by_email(rb, &req.email).await?.is_some() {
-        return Err(AppError::BusinessError("邮箱账号已存在"));
+    if !req.email.is_empty() && User::select_by_email(rb, &req.email).await?.is_some() {
+        return Err(AppError::with_args(ErrorCode::DuplicateEmail, &[&req.email]));
     }
 
-    let post_ids = req.post_ids.clone();
-    let id = User::insert(rb, &User::from(req)).await?.last_insert_id;
+    let password = match req.password.as_deref() {
+        None | Some("") => return Err(AppError::business(ErrorCode::BadRequest, "密码不能为空")),
+        Some(x) => {
+            data.password_policy.validate(x, &req.user_name)?;
+            data.password_util.hash(x)?
+        }
+    };
 
-    let mut list: Vec<UserPost> = Vec::new();
-    for post_id in post_ids {
-        list.push(UserPost { user_id: id.i64(), post_id })
-    }
-    UserPost::insert_batch(rb, &list, list.len() as u64).await.map(|_| ok_result())?
+    let post_ids = req.post_ids.clone();
+    let mut user = User::from(req);
+    user.password = password;
+    user.pwd_update_date = Some(DateTime::now());
+    transaction(rb, async |tx| {
+        let result = User::insert(tx, &user).await?;
+        let id = last_insert_id(tx, &result).await?;
+        add_password_history(tx, data.password_policy.history_count, id, &user.password).await?;
+
+        let mut list: Vec<UserPost> = Vec::new();
+        for post_id in post_ids {
+            list.push(UserPost { user_id: id, post_id })
+        }
+        if !list.is_empty() {
+            UserPost::insert_batch(tx, &list, list.len() as u64).await?;
+        }
+        Ok(())
+    })
+    .await?;
+    ok_result()
 }
 
 /*
@@ -60,25 +104,120 @@ pub async fn add_sys_user(item: web::Json<UserReq>, data: web::Data<AppState>) -
  *date：2025/01/08 17:16:44
  */
 #[post("/system/user/deleteUser")]
-pub async fn delete_sys_user(http_req: HttpRequest, item: web::Json<DeleteUserReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn delete_sys_user(http_req: HttpRequest, item: ValidatedJson<DeleteUserReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("delete sys_user params: {:?}", &item);
     let rb = &data.batis;
 
-    let user_id = http_req.headers().get("userId").unwrap().to_str().unwrap_or_default().parse::<i64>().unwrap_or_default();
+    let user_id = http_req.headers().get("userId").and_then(|x| x.to_str().ok()).and_then(|x| x.parse::<i64>().ok()).unwrap_or_default();
 
     log::info!("delete sys_user params user_id {:?}", user_id);
 
     let ids = item.ids.clone();
     if ids.contains(&user_id) {
-        return Err(AppError::BusinessError("当前用户不能删除"));
+        return Err(AppError::business(ErrorCode::BadRequest, "当前用户不能删除"));
     }
     if ids.contains(&1) {
-        return Err(AppError::BusinessError("不允许操作超级管理员用户"));
+        return Err(AppError::business(ErrorCode::SuperAdminProtected, "不允许操作超级管理员用户"));
+    }
+
+    //逻辑删除,保留用户的角色和岗位,从回收站恢复后可以继续使用;API令牌直接删除
+    transaction(rb, async |tx| {
+        ApiToken::delete_by_map(tx, value! {"user_id": &ids}).await?;
+        update_user_del_flag(tx, &ids, 0, &DateTime::now()).await?;
+        Ok(())
+    })
+    .await?;
+
+    for id in ids {
+        data.permission_cache.invalidate_user(id);
+        logout_user_sessions(&data, id).await?;
     }
+    ok_result()
+}
+
+/*
+ *查询已删除的用户列表(回收站)
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+#[post("/system/user/queryDeletedUserList")]
+pub async fn query_deleted_user_list(item: ValidatedJson<QueryDeletedUserListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("query deleted sys_user_list params: {:?}", &item);
+    let rb = &data.batis;
 
-    UserRole::delete_by_map(rb, value! {"user_id": &ids}).await?;
-    UserPost::delete_by_map(rb, value! {"user_id": &ids}).await?;
-    User::delete_by_map(rb, value! {"id": &item.ids}).await.map(|_| ok_result())?
+    let user_name = item.user_name.as_deref().unwrap_or_default();
+    let page = &PageRequest::new(item.page_no, item.page_size);
+    User::select_deleted_user_list(rb, page, user_name)
+        .await
+        .map(|x| ok_result_page(x.records.into_iter().map(|x| x.into()).collect::<Vec<UserResp>>(), x.total))?
+}
+
+/*
+ *从回收站恢复用户(账号,手机号码和邮箱不能和未删除的用户重复,用户所在的部门不能是已删除的部门)
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+#[post("/system/user/restoreUser")]
+pub async fn restore_sys_user(item: ValidatedJson<RestoreUserReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("restore sys_user params: {:?}", &item);
+    let rb = &data.batis;
+
+    let users = User::select_deleted_by_ids(rb, &item.ids).await?;
+    if users.len() != item.ids.len() {
+        return Err(AppError::business(ErrorCode::UserNotFound, "回收站中不存在该用户"));
+    }
+
+    let mut restored: Vec<&User> = Vec::new();
+    for user in &users {
+        let duplicate = |f: fn(&User) -> &str| restored.iter().any(|x| !f(user).is_empty() && f(x) == f(user));
+        if duplicate(|x| &x.user_name) || User::select_by_user_name(rb, &user.user_name).await?.is_some() {
+            return Err(AppError::with_args(ErrorCode::DuplicateUserName, &[&user.user_name]));
+        }
+        if !user.mobile.is_empty() && (duplicate(|x| &x.mobile) || User::select_by_mobile(rb, &user.mobile).await?.is_some()) {
+            return Err(AppError::with_args(ErrorCode::DuplicateMobile, &[&user.mobile]));
+        }
+        if !user.email.is_empty() && (duplicate(|x| &x.email) || User::select_by_email(rb, &user.email).await?.is_some()) {
+            return Err(AppError::with_args(ErrorCode::DuplicateEmail, &[&user.email]));
+        }
+        if Dept::select_by_id(rb, &user.dept_id).await?.is_none() {
+            return Err(AppError::business(ErrorCode::DeptNotFound, "用户所在的部门已删除,请先恢复部门"));
+        }
+        restored.push(user);
+    }
+
+    update_user_del_flag(rb, &item.ids, 1, &DateTime::now()).await?;
+    for id in &item.ids {
+        data.permission_cache.invalidate_user(*id);
+    }
+    ok_result()
+}
+
+/*
+ *彻底删除回收站中的用户,同时删除用户的角色,岗位,两步验证,历史密码和第三方身份
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+#[post("/system/user/purgeUser")]
+pub async fn purge_sys_user(item: ValidatedJson<PurgeUserReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("purge sys_user params: {:?}", &item);
+    let rb = &data.batis;
+
+    let ids = &item.ids;
+    if User::select_deleted_by_ids(rb, ids).await?.len() != ids.len() {
+        return Err(AppError::business(ErrorCode::UserNotFound, "回收站中不存在该用户"));
+    }
+
+    transaction(rb, async |tx| {
+        UserRole::delete_by_map(tx, value! {"user_id": ids}).await?;
+        UserPost::delete_by_map(tx, value! {"user_id": ids}).await?;
+        UserMfa::delete_by_map(tx, value! {"user_id": ids}).await?;
+        UserPasswordHistory::delete_by_map(tx, value! {"user_id": ids}).await?;
+        UserIdentity::delete_by_map(tx, value! {"user_id": ids}).await?;
+        User::delete_by_map(tx, value! {"id": ids}).await?;
+        Ok(())
+    })
+    .await?;
+    ok_result()
 }
 
 /*
@@ -87,36 +226,36 @@ pub async fn delete_sys_user(http_req: HttpRequest, item: web::Json<DeleteUserRe
  *date：2025/01/08 17:16:44
  */
 #[post("/system/user/updateUser")]
-pub async fn update_sys_user(item: web::Json<UserReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn update_sys_user(item: ValidatedJson<UserReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update sys_user params: {:?}", &item);
     let rb = &data.batis;
     let req = item.0;
 
     let id = req.id;
     if id == Some(1) {
-        return Err(AppError::BusinessError("不允许操作超级管理员用户"));
+        return Err(AppError::business(ErrorCode::SuperAdminProtected, "不允许操作超级管理员用户"));
     }
 
     let user = match User::select_by_id(rb, id.unwrap_or_default()).await? {
-        None => return Err(AppError::BusinessError("用户不存在")),
+        None => return Err(AppError::from(ErrorCode::UserNotFound)),
         Some(x) => x,
     };
 
     if let Some(x) = User::select_by_user_name(rb, &req.user_name).await? {
         if x.id != id {
-            return Err(AppError::BusinessError("登录账号已存在"));
+            return Err(AppError::with_args(ErrorCode::DuplicateUserName, &[&req.user_name]));
         }
     }
 
-    if let Some(x) = User::select_by_mobile(rb, &req.mobile).await? {
+    if let Some(x) = User::select_by_mobile(rb, &req.mobile).await?.filter(|_| !req.mobile.is_empty()) {
         if x.id != id {
-            return Err(AppError::BusinessError("手机号码已存在"));
+            return Err(AppError::with_args(ErrorCode::DuplicateMobile, &[&req.mobile]));
         }
     }
 
-    if let Some(x) = User::select_by_email(rb, &req.email).await? {
+    if let Some(x) = User::select_by_email(rb, &req.email).await?.filter(|_| !req.email.is_empty()) {
         if x.id != id {
-            return Err(AppError::BusinessError("邮箱账号已存在"));
+            return Err(AppError::with_args(ErrorCode::DuplicateEmail, &[&req.email]));
         }
     }
 
@@ -129,12 +268,23 @@ pub async fn update_sys_user(item: web::Json<UserReq>, data: web::Data<AppState>
         })
     }
 
-    UserPost::delete_by_map(rb, value! {"user_id": &id}).await?;
-    UserPost::insert_batch(rb, &user_post_list, user_post_list.len() as u64).await?;
-
+    let locale = req.locale.clone();
     let mut data = User::from(req);
+    data.password = user.password;
+    data.pwd_update_date = user.pwd_update_date;
+    data.locale = locale.unwrap_or(user.locale);
     data.update_time = Some(DateTime::now());
-    User::update_by_map(rb, &data, value! {"id": &id}).await.map(|_| ok_result())?
+
+    transaction(rb, async |tx| {
+        UserPost::delete_by_map(tx, value! {"user_id": &id}).await?;
+        if !user_post_list.is_empty() {
+            UserPost::insert_batch(tx, &user_post_list, user_post_list.len() as u64).await?;
+        }
+        User::update_by_map(tx, &data, value! {"id": &id}).await?;
+        Ok(())
+    })
+    .await?;
+    ok_result()
 }
 
 /*
@@ -143,21 +293,25 @@ pub async fn update_sys_user(item: web::Json<UserReq>, data: web::Data<AppState>
  *date：2025/01/08 17:16:44
  */
 #[post("/system/user/updateUserStatus")]
-pub async fn update_sys_user_status(item: web::Json<UpdateUserStatusReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn update_sys_user_status(item: ValidatedJson<UpdateUserStatusReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update sys_user_status params: {:?}", &item);
     let rb = &data.batis;
     let req = item.0;
 
     let ids = req.ids.clone();
     if ids.contains(&1) {
-        return Err(AppError::BusinessError("不允许操作超级管理员用户"));
+        return Err(AppError::business(ErrorCode::SuperAdminProtected, "不允许操作超级管理员用户"));
     }
 
-    let update_sql = format!("update sys_user set status = ? where id in ({})", req.ids.iter().map(|_| "?").collect::<Vec<&str>>().join(", "));
+    update_user_status(rb, &req.ids, req.status).await?;
 
-    let mut param = vec![value!(req.status)];
-    param.extend(req.ids.iter().map(|&id| value!(id)));
-    rb.exec(&update_sql, param).await.map(|_| ok_result())?
+    //禁用用户时,让用户已登录的会话立即失效
+    if req.status == 0 {
+        for id in ids {
+            logout_user_sessions(&data, id).await?;
+        }
+    }
+    ok_result()
 }
 
 /*
@@ -166,7 +320,7 @@ pub async fn update_sys_user_status(item: web::Json<UpdateUserStatusReq>, data:
  *date：2025/01/08 17:16:44
  */
 #[post("/system/user/resetUserPassword")]
-pub async fn reset_sys_user_password(item: web::Json<ResetUserPwdReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn reset_sys_user_password(item: ValidatedJson<ResetUserPwdReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("reset sys_user_password params: {:?}", &item);
 
     let rb = &data.batis;
@@ -174,17 +328,19 @@ pub async fn reset_sys_user_password(item: web::Json<ResetUserPwdReq>, data: web
     let req = item.0;
     let id = req.id.clone();
     if id == 1 {
-        return Err(AppError::BusinessError("不允许操作超级管理员用户"));
+        return Err(AppError::business(ErrorCode::SuperAdminProtected, "不允许操作超级管理员用户"));
     }
 
     let sys_user_result = User::select_by_id(rb, req.id).await?;
 
     match sys_user_result {
-        None => Err(AppError::BusinessError("用户不存在")),
+        None => Err(AppError::from(ErrorCode::UserNotFound)),
         Some(x) => {
             let mut user = x;
-            user.password = req.password;
-            User::update_by_map(rb, &user, value! {"id": &user.id}).await.map(|_| ok_result())?
+            let password = check_new_password(&data, &user, &req.password).await?;
+            save_password(&data, &mut user, password).await?;
+            logout_user_sessions(&data, id).await?;
+            ok_result()
         }
     }
 }
@@ -195,40 +351,65 @@ pub async fn reset_sys_user_password(item: web::Json<ResetUserPwdReq>, data: web
  *date：2025/01/08 17:16:44
  */
 #[post("/system/user/updateUserPassword")]
-pub async fn update_sys_user_password(http_req: HttpRequest, item: web::Json<UpdateUserPwdReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn update_sys_user_password(http_req: HttpRequest, item: ValidatedJson<UpdateUserPwdReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update sys_user_password params: {:?}", &item);
     let rb = &data.batis;
     let req = item.0;
 
-    let user_id = http_req.headers().get("userId").unwrap().to_str().unwrap_or_default().parse::<i64>().unwrap_or_default();
+    let user_id = http_req.headers().get("userId").and_then(|x| x.to_str().ok()).and_then(|x| x.parse::<i64>().ok()).unwrap_or_default();
 
     log::info!("query user menu params user_id {:?}", user_id);
 
     match User::select_by_id(rb, user_id).await? {
-        None => Err(AppError::BusinessError("用户不存在")),
+        None => Err(AppError::from(ErrorCode::UserNotFound)),
         Some(x) => {
             let mut user = x;
-            if user.password != req.pwd {
-                return Err(AppError::BusinessError("旧密码不正确"));
+            if data.password_util.verify(&req.pwd, &user.password) == PasswordVerify::Invalid {
+                return Err(AppError::from(ErrorCode::PasswordIncorrect));
             }
-            user.password = req.re_pwd;
-            User::update_by_map(rb, &user, value! {"id": &user.id}).await.map(|_| ok_result())?
+            let password = check_new_password(&data, &user, &req.re_pwd).await?;
+            save_password(&data, &mut user, password).await?;
+            logout_user_sessions(&data, user_id).await?;
+            ok_result()
         }
     }
 }
 
+/*
+ *用户修改自己的语言偏好(重新登录或者刷新token后生效)
+ *author：刘飞华
+ *date：2026/10/19 16:10:42
+ */
+#[post("/system/user/updateUserLocale")]
+pub async fn update_sys_user_locale(http_req: HttpRequest, item: ValidatedJson<UpdateUserLocaleReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("update sys_user_locale params: {:?}", &item);
+    let rb = &data.batis;
+    let req = item.0;
+
+    let user_id = http_req
+        .headers()
+        .get("userId")
+        .and_then(|header| header.to_str().ok())
+        .and_then(|s| s.parse::<i64>().ok())
+        .unwrap_or_default();
+
+    //保存为规范的语言标签,如: en_us -> en-US
+    let locale = Locale::parse(&req.locale).map(|x| x.as_str()).unwrap_or_default();
+    update_user_locale(rb, user_id, locale).await.map(|_| ok_result())?
+}
+
 /*
  *查询用户信息详情
  *author：刘飞华
  *date：2025/01/08 17:16:44
  */
 #[post("/system/user/queryUserDetail")]
-pub async fn query_sys_user_detail(item: web::Json<QueryUserDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_user_detail(item: ValidatedJson<QueryUserDetailReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_user_detail params: {:?}", &item);
     let rb = &data.batis;
 
     let mut user = match User::select_by_id(rb, item.id).await? {
-        None => return Err(AppError::BusinessError("用户不存在")),
+        None => return Err(AppError::from(ErrorCode::UserNotFound)),
         Some(user) => {
             let res: UserResp = user.into();
             res
@@ -236,7 +417,7 @@ pub async fn query_sys_user_detail(item: web::Json<QueryUserDetailReq>, data: we
     };
 
     let dept = match Dept::select_by_id(rb, &user.dept_id).await? {
-        None => return Err(AppError::BusinessError("部门不存在")),
+        None => return Err(AppError::from(ErrorCode::DeptNotFound)),
 
         Some(y) => {
             let a: DeptResp = y.into();
@@ -258,7 +439,7 @@ pub async fn query_sys_user_detail(item: web::Json<QueryUserDetailReq>, data: we
  *date：2025/01/08 17:16:44
  */
 #[post("/system/user/queryUserList")]
-pub async fn query_sys_user_list(item: web::Json<QueryUserListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_sys_user_list(http_req: HttpRequest, item: ValidatedJson<QueryUserListReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query sys_user_list params: {:?}", &item);
     let rb = &data.batis;
 
@@ -266,9 +447,18 @@ pub async fn query_sys_user_list(item: web::Json<QueryUserListReq>, data: web::D
     let user_name = item.user_name.as_deref().unwrap_or_default();
     let status = item.status.unwrap_or(2);
     let dept_id = item.dept_id.unwrap_or_default();
+    let user_id = http_req.extensions().get::<JwtToken>().map(|x| x.id).unwrap_or_default();
+    let scope = query_data_scope(rb, user_id).await?;
+
+    //选择的部门及其下级部门
+    let mut dept_ids = Vec::new();
+    if dept_id != 0 {
+        dept_ids.push(dept_id);
+        dept_ids.extend(select_children_dept_by_id(rb, &dept_id).await?.into_iter().filter_map(|x| x.id));
+    }
 
     let page = &PageRequest::new(item.page_no, item.page_size);
-    User::select_sys_user_list(rb, page, mobile, user_name, status, dept_id)
+    User::select_sys_user_list(rb, page, mobile, user_name, status, &dept_ids, &scope)
         .await
         .map(|x| ok_result_page(x.records.into_iter().map(|x| x.into()).collect::<Vec<UserResp>>(), x.total))?
 }
@@ -278,66 +468,600 @@ pub async fn query_sys_user_list(item: web::Json<QueryUserListReq>, data: web::D
  *author：刘飞华
  */
 #[post("/system/user/login")]
-pub async fn login(http_request: HttpRequest, item: web::Json<UserLoginReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn login(http_request: HttpRequest, item: ValidatedJson<UserLoginReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("user login params: {:?}, {:?}", &item, data.batis);
     let rb = &data.batis;
 
     let req = item.0;
 
-    let user_agent = http_request.headers().get("User-Agent").unwrap().to_str().unwrap();
+    let user_agent = http_request.headers().get("User-Agent").and_then(|x| x.to_str().ok()).unwrap_or_default();
     log::info!("user login params user_agent {:?}", user_agent);
 
     let agent = UserAgentUtil::new(user_agent);
+    let ipaddr = data.ip_util.client_ip(http_request.peer_addr(), http_request.headers());
+    let location = data.ip_util.location(&ipaddr);
 
-    let user_result = User::select_by_mobile(rb, &req.mobile).await?;
-    log::info!("query user by mobile: {:?}", user_result);
+    if req.account.trim().is_empty() {
+        return Err(AppError::business(ErrorCode::BadRequest, "请输入账号"));
+    }
+
+    let user_result = select_by_account(rb, &req.account).await?;
+    log::info!("query user by account: {:?}", user_result);
+
+    //用户名,邮箱和手机号码登录同一个用户时使用同一个失败计数,用户不存在时按输入的账号计数
+    let limit_key = user_result.as_ref().map(|x| x.user_name.clone()).unwrap_or_else(|| req.account.clone());
+    let limiter = &data.login_limiter;
+    match limiter.check(&limit_key, &ipaddr) {
+        LoginCheck::Allowed => {}
+        LoginCheck::Locked => {
+            add_login_log(rb, req.account, 0, LOCKED_MSG, agent, &ipaddr, &location).await;
+            return Err(AppError::from(ErrorCode::AccountLocked));
+        }
+        LoginCheck::CaptchaRequired => {
+            let (captcha_id, captcha_code) = match (req.captcha_id.as_deref(), req.captcha_code.as_deref()) {
+                (Some(id), Some(code)) if !id.is_empty() && !code.is_empty() => (id, code),
+                _ => return Err(AppError::from(ErrorCode::CaptchaRequired)),
+            };
+            if !data.captcha_store.verify(captcha_id, captcha_code) {
+                add_login_log(rb, req.account, 0, "验证码错误", agent, &ipaddr, &location).await;
+                return Err(AppError::from(ErrorCode::CaptchaInvalid));
+            }
+        }
+    }
 
     match user_result {
         None => {
-            add_login_log(rb, req.mobile, 0, "用户不存在", agent).await;
-            Err(AppError::BusinessError("用户不存在"))
+            add_login_fail(&data, req.account, &limit_key, "用户不存在", agent, &ipaddr, &location).await;
+            Err(AppError::business(ErrorCode::BadCredentials, "用户不存在"))
         }
-        Some(user) => {
-            let mut s_user = user.clone();
+        Some(mut user) => {
             let id = user.id.unwrap();
-            let username = user.user_name;
-            let password = user.password;
 
-            if password.ne(&req.password) {
-                add_login_log(rb, req.mobile, 0, "密码不正确", agent).await;
-                return Err(AppError::BusinessError("密码不正确"));
+            let verify = data.authenticators.authenticate(&user, &req.password).await;
+            if verify == PasswordVerify::Invalid {
+                add_login_fail(&data, req.account, &limit_key, "密码不正确", agent, &ipaddr, &location).await;
+                return Err(AppError::business(ErrorCode::BadCredentials, "密码不正确"));
             }
 
-            let btn_menu = query_btn_menu(&id, rb.clone()).await;
+            //禁用的用户不能登录(修改状态时已经下线了该用户的会话)
+            if user.status != 1 {
+                add_login_log(rb, req.account, 0, "用户已被禁用", agent, &ipaddr, &location).await;
+                return Err(AppError::from(ErrorCode::UserDisabled));
+            }
+
+            if verify == PasswordVerify::NeedsRehash {
+                //明文或者较弱的哈希,登录成功后按当前参数重新加密
+                log::info!("rehash password for user: {:?}", id);
+                user.password = data.password_util.hash(&req.password)?;
+            }
 
-            if btn_menu.len() == 0 {
-                add_login_log(rb, req.mobile, 0, "用户没有分配角色或者菜单,不能登录", agent).await;
-                return Err(AppError::BusinessError("用户没有分配角色或者菜单,不能登录"));
+            //启用了两步验证或者角色要求必须启用两步验证时,先返回挑战token,提交动态码后才能完成登录
+            let mfa_enabled = UserMfa::select_by_user_id(rb, id).await?.is_some_and(|x| x.status == 1);
+            if mfa_enabled || is_mfa_required(&data, id).await? {
+                if verify == PasswordVerify::NeedsRehash {
+                    User::update_by_map(rb, &user, value! {"id": &user.id}).await?;
+                }
+                let keys = &data.jwt_keys;
+                let ttl = data.mfa_config.challenge_ttl;
+                let challenge_token = JwtToken::new_mfa(id, &user.user_name, &req.account, ttl, keys).create_token(keys)?;
+                return Ok(Either::Right(Either::Left(ok_result_data(MfaChallengeResp {
+                    mfa_required: true,
+                    setup_required: !mfa_enabled,
+                    challenge_token,
+                    expires_in: ttl,
+                })?)));
             }
 
-            let token = JwtToken::new(id, &username, btn_menu).create_token("123")?;
+            //需要两步验证时在第二步成功后才清除失败次数,否则交替调用两个接口可以无限次尝试动态码
+            limiter.success(&limit_key);
 
-            add_login_log(rb, req.mobile, 1, "登录成功", agent.clone()).await;
-            s_user.login_os = agent.os;
-            s_user.login_browser = agent.browser;
-            s_user.login_date = Some(DateTime::now());
-            User::update_by_map(rb, &s_user, value! {"id": &s_user.id}).await?;
-            ok_result_data(token)
+            if let Some(expired) = check_password_expired(&data, &user, &req.account, agent.clone(), &ipaddr, &location).await? {
+                if verify == PasswordVerify::NeedsRehash {
+                    User::update_by_map(rb, &user, value! {"id": &user.id}).await?;
+                }
+                return Ok(Either::Right(Either::Right(ok_result_data(expired)?)));
+            }
+
+            let token = complete_login(&data, user, req.account, agent, &ipaddr, &location).await?;
+            Ok(Either::Left(ok_result_data(token)?))
         }
     }
 }
 
+/*
+ *登录第二步: 提交动态码或者恢复码完成登录
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+#[post("/system/user/loginMfa")]
+pub async fn login_mfa(http_request: HttpRequest, item: ValidatedJson<LoginMfaReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    let rb = &data.batis;
+
+    let challenge = verify_challenge(&data, &item.challenge_token)?;
+    let user = match User::select_by_id(rb, challenge.id).await? {
+        None => return Err(AppError::from(ErrorCode::UserNotFound)),
+        Some(user) => user,
+    };
+
+    let user_agent = http_request.headers().get("User-Agent").and_then(|x| x.to_str().ok()).unwrap_or_default();
+    let agent = UserAgentUtil::new(user_agent);
+    let ipaddr = data.ip_util.client_ip(http_request.peer_addr(), http_request.headers());
+    let location = data.ip_util.location(&ipaddr);
+
+    //发出挑战token之后用户可能已经被禁用
+    if user.status != 1 {
+        add_login_log(rb, user.user_name, 0, "用户已被禁用", agent, &ipaddr, &location).await;
+        return Err(AppError::from(ErrorCode::UserDisabled));
+    }
+
+    //和第一步一样按用户计数,动态码错误也计入用户的登录失败次数;登录日志记录第一步输入的账号
+    let login_name = match challenge.account.is_empty() {
+        true => user.user_name.clone(),
+        false => challenge.account.clone(),
+    };
+    if data.login_limiter.check(&user.user_name, &ipaddr) == LoginCheck::Locked {
+        add_login_log(rb, login_name, 0, LOCKED_MSG, agent, &ipaddr, &location).await;
+        return Err(AppError::from(ErrorCode::AccountLocked));
+    }
+
+    let mfa = match UserMfa::select_by_user_id(rb, challenge.id).await? {
+        None => return Err(AppError::from(ErrorCode::MfaNotBound)),
+        Some(mfa) => mfa,
+    };
+    //挑战token只能使用一次,动态码错误时也失效,需要重新输入密码
+    data.token_store.revoke(&challenge.jti, challenge.exp);
+    if !verify_mfa_code(&data, &mfa, &item.code).await? {
+        add_login_fail(&data, login_name, &user.user_name, "动态码错误", agent, &ipaddr, &location).await;
+        return Err(AppError::from(ErrorCode::MfaCodeInvalid));
+    }
+    data.login_limiter.success(&user.user_name);
+    if mfa.status == 0 {
+        enable_mfa(&data, &mfa).await?;
+    }
+
+    if let Some(expired) = check_password_expired(&data, &user, &login_name, agent.clone(), &ipaddr, &location).await? {
+        return Ok(Either::Right(ok_result_data(expired)?));
+    }
+    Ok(Either::Left(ok_result_data(complete_login(&data, user, login_name, agent, &ipaddr, &location).await?)?))
+}
+
+/*
+ *登录时绑定身份验证器(角色要求必须启用两步验证,但用户还没有绑定时使用)
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+#[post("/system/user/loginMfaSetup")]
+pub async fn login_mfa_setup(item: ValidatedJson<LoginMfaSetupReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    let challenge = verify_challenge(&data, &item.challenge_token)?;
+    let user = match User::select_by_id(&data.batis, challenge.id).await? {
+        None => return Err(AppError::from(ErrorCode::UserNotFound)),
+        Some(user) => user,
+    };
+    ok_result_data(create_mfa_secret(&data, &user).await?)
+}
+
+/*
+ *校验登录第二步的挑战token
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+fn verify_challenge(data: &AppState, challenge_token: &str) -> AppResult<JwtToken> {
+    let token = JwtToken::verify(&data.jwt_keys, challenge_token)?;
+    if token.token_type != MFA_TOKEN || data.token_store.is_revoked(&token) {
+        return Err(AppError::JwtTokenError("挑战token已失效,请重新登录".to_string()));
+    }
+    Ok(token)
+}
+
+/*
+ *完成登录: 缓存权限,登记会话,签发token,记录登录日志
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+pub async fn complete_login(data: &AppState, mut user: User, login_name: String, agent: UserAgentUtil, ipaddr: &str, location: &str) -> AppResult<UserLoginResp> {
+    let rb = &data.batis;
+    let id = user.id.unwrap_or_default();
+
+    let version = data.permission_cache.version();
+    let btn_menu = query_btn_menu(&id, rb).await?;
+
+    if btn_menu.is_empty() {
+        add_login_log(rb, login_name, 0, "用户没有分配角色或者菜单,不能登录", agent, ipaddr, location).await;
+        return Err(AppError::from(ErrorCode::UserNoPermission));
+    }
+
+    let session_id = Uuid::new_v4().to_string();
+    data.permission_cache.put(id, version, btn_menu);
+    let (token, refresh_jti) = issue_token(data, id, &user.user_name, &session_id, &user.locale)?;
+    add_user_session(data, &user, &session_id, &refresh_jti, ipaddr, location, &agent).await?;
+
+    add_login_log(rb, login_name, 1, "登录成功", agent.clone(), ipaddr, location).await;
+    user.login_ip = ipaddr.to_string();
+    user.login_os = agent.os;
+    user.login_browser = agent.browser;
+    user.login_date = Some(DateTime::now());
+    if user.pwd_update_date.is_none() {
+        //历史用户没有密码更新时间,从第一次登录开始计算密码有效期
+        user.pwd_update_date = user.login_date.clone();
+    }
+    User::update_by_map(rb, &user, value! {"id": &user.id}).await?;
+    Ok(token)
+}
+
+/*
+ *校验登录用户的密码是否已过期,过期时签发修改密码token(LDAP和单点登录用户的密码由对应的服务管理,不校验)
+ *author：刘飞华
+ *date：2026/10/18 22:06:52
+ */
+async fn check_password_expired(data: &AppState, user: &User, login_name: &str, agent: UserAgentUtil, ipaddr: &str, location: &str) -> AppResult<Option<PasswordExpiredResp>> {
+    if user.auth_source == LDAP_AUTH || user.auth_source == OIDC_AUTH {
+        return Ok(None);
+    }
+    let pwd_update_time = match &user.pwd_update_date {
+        None => return Ok(None),
+        Some(x) => x.unix_timestamp(),
+    };
+    if !data.password_policy.is_expired(pwd_update_time, DateTime::now().unix_timestamp()) {
+        return Ok(None);
+    }
+
+    add_login_log(&data.batis, login_name.to_string(), 0, "密码已过期,需要修改密码", agent, ipaddr, location).await;
+    let keys = &data.jwt_keys;
+    let ttl = data.password_policy.change_ttl;
+    let change_token = JwtToken::new_password(user.id.unwrap_or_default(), &user.user_name, ttl, keys).create_token(keys)?;
+    Ok(Some(PasswordExpiredResp {
+        password_expired: true,
+        change_token,
+        expires_in: ttl,
+    }))
+}
+
+/*
+ *修改过期的密码(修改成功后需要使用新密码重新登录)
+ *author：刘飞华
+ *date：2026/10/18 22:06:52
+ */
+#[post("/system/user/changeExpiredPassword")]
+pub async fn change_expired_password(item: ValidatedJson<ChangeExpiredPasswordReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    let token = JwtToken::verify(&data.jwt_keys, &item.change_token)?;
+    if token.token_type != PASSWORD_TOKEN || data.token_store.is_revoked(&token) {
+        return Err(AppError::JwtTokenError("修改密码token已失效,请重新登录".to_string()));
+    }
+
+    let mut user = match User::select_by_id(&data.batis, token.id).await? {
+        None => return Err(AppError::from(ErrorCode::UserNotFound)),
+        Some(user) => user,
+    };
+    let password = check_new_password(&data, &user, &item.password).await?;
+    save_password(&data, &mut user, password).await?;
+
+    //修改密码token只能使用一次
+    data.token_store.revoke(&token.jti, token.exp);
+    logout_user_sessions(&data, token.id).await?;
+    ok_result()
+}
+
+/*
+ *校验新密码(密码规则以及不能和最近使用过的密码相同),返回新密码的哈希
+ *author：刘飞华
+ *date：2026/10/18 22:06:52
+ */
+pub async fn check_new_password(data: &AppState, user: &User, password: &str) -> AppResult<String> {
+    let policy = &data.password_policy;
+    policy.validate(password, &user.user_name)?;
+
+    if policy.history_count > 0 {
+        let history = UserPasswordHistory::select_recent(&data.batis, user.id.unwrap_or_default(), policy.history_count as u64).await?;
+        let mut used = vec![user.password.as_str()];
+        used.extend(history.iter().map(|x| x.password.as_str()).filter(|x| *x != user.password));
+        if used.iter().any(|x| data.password_util.verify(password, x) != PasswordVerify::Invalid) {
+            return Err(AppError::PasswordPolicyError(t_args("password.history", &[policy.history_count.to_string()])));
+        }
+    }
+    data.password_util.hash(password)
+}
+
+/*
+ *保存新密码,在同一个事务中记录密码更新时间和历史密码
+ *author：刘飞华
+ *date：2026/10/18 22:06:52
+ */
+pub async fn save_password(data: &AppState, user: &mut User, password: String) -> AppResult<()> {
+    let now = DateTime::now();
+    user.password = password;
+    user.pwd_update_date = Some(now.clone());
+    user.update_time = Some(now);
+    let user = &*user;
+    transaction(&data.batis, async |tx| {
+        User::update_by_map(tx, user, value! {"id": &user.id}).await?;
+        add_password_history(tx, data.password_policy.history_count, user.id.unwrap_or_default(), &user.password).await
+    })
+    .await
+}
+
+/*
+ *记录历史密码,只保留最近count条(count为密码策略的history_count),rb可以是连接池也可以是事务
+ *author：刘飞华
+ *date：2026/10/18 22:06:52
+ */
+pub async fn add_password_history(rb: &dyn Executor, count: usize, user_id: i64, password: &str) -> AppResult<()> {
+    if count == 0 {
+        return Ok(());
+    }
+
+    let history = UserPasswordHistory {
+        id: None,                       //主键
+        user_id,                        //用户ID
+        password: password.to_string(), //密码哈希
+        create_time: None,              //创建时间
+    };
+    UserPasswordHistory::insert(rb, &history).await?;
+
+    let recent = UserPasswordHistory::select_recent(rb, user_id, count as u64).await?;
+    if let Some(oldest) = recent.last().filter(|_| recent.len() == count) {
+        delete_before(rb, user_id, oldest.id.unwrap_or_default()).await?;
+    }
+    Ok(())
+}
+
+/*
+ *刷新token(refresh token轮换,旧的refresh token立即失效)
+ *author：刘飞华
+ *date：2026/10/18 14:20:05
+ */
+#[post("/system/user/refreshToken")]
+pub async fn refresh_token(item: ValidatedJson<RefreshTokenReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    let rb = &data.batis;
+
+    let token = JwtToken::verify(&data.jwt_keys, &item.refresh_token)?;
+    if token.token_type != REFRESH_TOKEN || data.token_store.is_revoked(&token) {
+        return Err(AppError::JwtTokenError("refresh token已失效,请重新登录".to_string()));
+    }
+    log::info!("refresh token for user: {:?}", token.id);
+
+    let session = match UserSession::select_by_session_id(rb, &token.sid).await? {
+        Some(session) if session.status == 1 => session,
+        _ => return Err(AppError::JwtTokenError("会话已下线,请重新登录".to_string())),
+    };
+
+    let user = match User::select_by_id(rb, token.id).await? {
+        None => return Err(AppError::from(ErrorCode::UserNotFound)),
+        Some(user) => user,
+    };
+    if user.status == 0 {
+        return Err(AppError::from(ErrorCode::UserDisabled));
+    }
+
+    if query_user_permissions(&data, token.id).await?.is_empty() {
+        return Err(AppError::from(ErrorCode::UserNoPermission));
+    }
+
+    //refresh token只能使用一次,以会话表中的refresh_jti为准(服务重启,多实例部署时仍然有效)
+    let (resp, refresh_jti) = issue_token(&data, token.id, &user.user_name, &token.sid, &user.locale)?;
+    let expire_time = DateTime::now().add(Duration::from_secs(data.jwt_keys.refresh_ttl));
+    if rotate_refresh_jti(rb, &refresh_jti, &DateTime::now(), &expire_time, &token.sid, &token.jti).await?.rows_affected == 0 {
+        //已经轮换过的refresh token再次被使用时,视为token泄露,下线该用户的全部会话
+        if !session.refresh_jti.is_empty() {
+            log::error!("refresh token reused, revoke all sessions of user: {}", token.id);
+            logout_user_sessions(&data, token.id).await?;
+        }
+        return Err(AppError::JwtTokenError("refresh token已失效,请重新登录".to_string()));
+    }
+    ok_result_data(resp)
+}
+
+/*
+ *退出登录(吊销当前的access token和refresh token)
+ *author：刘飞华
+ *date：2026/10/18 14:20:05
+ */
+#[post("/system/user/logout")]
+pub async fn logout(http_req: HttpRequest, item: ValidatedJson<LogoutReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    let token = http_req.extensions().get::<JwtToken>().cloned();
+    if let Some(token) = token {
+        log::info!("user logout: {:?}", token.id);
+        data.token_store.revoke(&token.jti, token.exp);
+        data.token_store.revoke_session(&token.sid, now_secs() + data.jwt_keys.refresh_ttl as usize);
+        update_session_offline(&data.batis, &token.sid).await?;
+    }
+
+    if let Some(x) = &item.refresh_token {
+        if let Ok(token) = JwtToken::verify(&data.jwt_keys, x) {
+            data.token_store.revoke(&token.jti, token.exp);
+            update_session_offline(&data.batis, &token.sid).await?;
+        }
+    }
+
+    ok_result()
+}
+
+/*
+ *解锁因登录失败次数过多被锁定的用户或者IP
+ *author：刘飞华
+ *date：2026/10/18 19:26:08
+ */
+#[post("/system/user/unlockUser")]
+pub async fn unlock_user(item: ValidatedJson<UnlockUserReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("unlock user params: {:?}", &item);
+    let rb = &data.batis;
+
+    if item.user_id.is_none() && item.ipaddr.as_deref().unwrap_or_default().is_empty() {
+        return Err(AppError::business(ErrorCode::BadRequest, "用户id和IP地址不能同时为空"));
+    }
+
+    if let Some(user_id) = item.user_id {
+        let user = match User::select_by_id(rb, user_id).await? {
+            None => return Err(AppError::from(ErrorCode::UserNotFound)),
+            Some(user) => user,
+        };
+        for account in [&user.mobile, &user.user_name, &user.email] {
+            data.login_limiter.unlock_account(account);
+        }
+    }
+
+    if let Some(ipaddr) = item.ipaddr.as_deref().filter(|x| !x.is_empty()) {
+        data.login_limiter.unlock_ip(ipaddr);
+    }
+
+    ok_result()
+}
+
+/*
+ *管理员模拟登录(以指定用户的身份访问系统,用于排查用户看到的菜单和数据)
+ *模拟登录的token同时携带管理员和被模拟的用户,不签发refresh token,模拟期间的所有请求都记录操作日志
+ *author：刘飞华
+ *date：2026/10/18 23:26:40
+ */
+#[post("/system/user/impersonateUser")]
+pub async fn impersonate_user(http_request: HttpRequest, item: ValidatedJson<ImpersonateUserReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+    log::info!("impersonate user params: {:?}", &item);
+    let rb = &data.batis;
+
+    let admin = match http_request.extensions().get::<JwtToken>() {
+        Some(token) if token.token_type == ACCESS_TOKEN && !token.is_impersonated() => token.clone(),
+        _ => return Err(AppError::business(ErrorCode::ImpersonateDenied, "只能使用自己的登录token模拟登录")),
+    };
+    let reason = item.reason.trim();
+    if reason.is_empty() {
+        return Err(AppError::business(ErrorCode::BadRequest, "请填写模拟登录的原因"));
+    }
+    if item.user_id == admin.id {
+        return Err(AppError::business(ErrorCode::ImpersonateDenied, "不能模拟登录自己"));
+    }
+    if item.user_id == 1 {
+        return Err(AppError::business(ErrorCode::ImpersonateDenied, "不能模拟登录超级管理员"));
+    }
+    let user = match User::select_by_id(rb, item.user_id).await? {
+        None => return Err(AppError::from(ErrorCode::UserNotFound)),
+        Some(user) if user.status != 1 => return Err(AppError::from(ErrorCode::UserDisabled)),
+        Some(user) => user,
+    };
+
+    let user_agent = http_request.headers().get("User-Agent").and_then(|x| x.to_str().ok()).unwrap_or_default();
+    let agent = UserAgentUtil::new(user_agent);
+    let ipaddr = data.ip_util.client_ip(http_request.peer_addr(), http_request.headers());
+    let location = data.ip_util.location(&ipaddr);
+    let msg = format!("管理员{}模拟登录,原因: {}", admin.username, reason.chars().take(150).collect::<String>());
+
+    let version = data.permission_cache.version();
+    let btn_menu = query_btn_menu(&item.user_id, rb).await?;
+    if btn_menu.is_empty() {
+        add_login_log(rb, user.user_name, 0, &format!("{}, 失败: 用户没有分配角色或者菜单", msg), agent, &ipaddr, &location).await;
+        return Err(AppError::business(ErrorCode::ImpersonateDenied, "用户没有分配角色或者菜单,不能模拟登录"));
+    }
+    //不能通过模拟登录获得自己没有的权限
+    let admin_permissions = query_user_permissions(&data, admin.id).await?;
+    if btn_menu.iter().any(|x| !admin_permissions.contains(x)) {
+        add_login_log(rb, user.user_name, 0, &format!("{}, 失败: 用户的权限超出了管理员的权限", msg), agent, &ipaddr, &location).await;
+        return Err(AppError::business(ErrorCode::ImpersonateDenied, "不能模拟登录权限比自己多的用户"));
+    }
+
+    let id = user.id.unwrap_or_default();
+    let keys = &data.jwt_keys;
+    let session_id = Uuid::new_v4().to_string();
+    data.permission_cache.put(id, version, btn_menu);
+    let token = JwtToken::new_impersonate(id, &user.user_name, &session_id, version, admin.id, &admin.username, keys)
+        .with_lang(&user.locale)
+        .create_token(keys)?;
+    add_user_session(&data, &user, &session_id, "", &ipaddr, &location, &agent).await?;
+    add_login_log(rb, user.user_name.clone(), 1, &msg, agent, &ipaddr, &location).await;
+    log::info!("user {} impersonate user {}", admin.id, id);
+
+    ok_result_data(ImpersonateUserResp {
+        token,
+        expires_in: keys.impersonate_ttl,
+        user_id: id,
+        user_name: user.user_name,
+        impersonated_by: admin.username,
+    })
+}
+
+/*
+ *签发access token和refresh token,同时返回refresh token的编号(保存到会话的refresh_jti中)
+ *author：刘飞华
+ *date：2026/10/18 14:20:05
+ */
+fn issue_token(data: &AppState, id: i64, username: &str, session_id: &str, lang: &str) -> AppResult<(UserLoginResp, String)> {
+    let keys = &data.jwt_keys;
+    let token = JwtToken::new(id, username, session_id, data.permission_cache.version(), keys).with_lang(lang).create_token(keys)?;
+
+    let refresh = JwtToken::new_refresh(id, username, session_id, keys);
+    let resp = UserLoginResp {
+        token,
+        refresh_token: refresh.create_token(keys)?,
+        expires_in: keys.ttl,
+    };
+    Ok((resp, refresh.jti))
+}
+
+/*
+ *根据登录账号查询用户(登录账号可以是用户账号,邮箱或者手机号)
+ *author：刘飞华
+ *date：2026/10/18 20:41:17
+ */
+pub async fn select_by_account(rb: &RBatis, account: &str) -> AppResult<Option<User>> {
+    let account = account.trim();
+    if account.contains('@') {
+        return Ok(User::select_by_email(rb, account).await?);
+    }
+    if let Some(user) = User::select_by_user_name(rb, account).await? {
+        return Ok(Some(user));
+    }
+    Ok(User::select_by_mobile(rb, account).await?)
+}
+
+/*
+ *登记在线用户会话(模拟登录不签发refresh token,refresh_jti为空,会话在模拟登录的token过期后失效)
+ *author：刘飞华
+ *date：2026/10/18 15:31:20
+ */
+async fn add_user_session(data: &AppState, user: &User, session_id: &str, refresh_jti: &str, ipaddr: &str, location: &str, agent: &UserAgentUtil) -> AppResult<()> {
+    let keys = &data.jwt_keys;
+    let ttl = if refresh_jti.is_empty() { keys.impersonate_ttl } else { keys.refresh_ttl };
+    let now = DateTime::now();
+    let session = UserSession {
+        id: None,                                             //主键
+        session_id: session_id.to_string(),                   //会话编号
+        user_id: user.id.unwrap_or_default(),                 //用户ID
+        user_name: user.user_name.clone(),                    //用户账号
+        ipaddr: ipaddr.to_string(),                           //登录IP地址
+        login_location: location.to_string(),                 //登录地点
+        browser: agent.browser.clone(),                       //浏览器类型
+        os: agent.os.clone(),                                 //操作系统
+        status: 1,                                            //会话状态(0:已下线,1:在线)
+        login_time: Some(now.clone()),                        //登录时间
+        last_access_time: Some(now.clone()),                  //最后访问时间
+        expire_time: Some(now.add(Duration::from_secs(ttl))), //过期时间
+        refresh_jti: refresh_jti.to_string(),                 //当前有效的refresh token编号
+    };
+
+    UserSession::insert(&data.batis, &session).await?;
+    Ok(())
+}
+
+/*
+ *记录登录失败(按limit_key计数,失败次数过多时锁定账号或者IP)
+ *author：刘飞华
+ *date：2026/10/18 19:26:08
+ */
+async fn add_login_fail(data: &AppState, name: String, limit_key: &str, msg: &str, agent: UserAgentUtil, ipaddr: &str, location: &str) {
+    add_login_log(&data.batis, name.clone(), 0, msg, agent.clone(), ipaddr, location).await;
+    if data.login_limiter.fail(limit_key, ipaddr) {
+        add_login_log(&data.batis, name, 0, LOCKED_MSG, agent, ipaddr, location).await;
+    }
+}
+
 /*
  *添加登录日志
  *author：刘飞华
  *date：2025/01/02 17:01:13
  */
-async fn add_login_log(rb: &RBatis, name: String, status: i8, msg: &str, agent: UserAgentUtil) {
+pub async fn add_login_log(rb: &RBatis, name: String, status: i8, msg: &str, agent: UserAgentUtil, ipaddr: &str, location: &str) {
     let sys_login_log = LoginLog {
         id: None,                             //访问ID
         login_name: name,                     //登录账号
-        ipaddr: "todo".to_string(),           //登录IP地址
-        login_location: "todo".to_string(),   //登录地点
+        ipaddr: ipaddr.to_string(),           //登录IP地址
+        login_location: location.to_string(), //登录地点
         platform: agent.platform,             //平台信息
         browser: agent.browser,               //浏览器类型
         version: agent.version,               //浏览器版本
@@ -357,18 +1081,30 @@ async fn add_login_log(rb: &RBatis, name: String, status: i8, msg: &str, agent:
     }
 }
 
+/*
+ *查询用户的接口权限(优先从权限缓存中获取)
+ *author：刘飞华
+ *date：2026/10/18 17:05:36
+ */
+pub async fn query_user_permissions(data: &AppState, user_id: i64) -> AppResult<Arc<Vec<String>>> {
+    if let Some(permissions) = data.permission_cache.get(user_id) {
+        return Ok(permissions);
+    }
+
+    let version = data.permission_cache.version();
+    let btn_menu = query_btn_menu(&user_id, &data.batis).await?;
+    Ok(data.permission_cache.put(user_id, version, btn_menu))
+}
+
 /*
  *查询按钮权限
  *author：刘飞华
  *date：2024/12/12 14:41:44
  */
-async fn query_btn_menu(id: &i64, rb: RBatis) -> Vec<String> {
-    let count = is_admin(&rb, id).await.unwrap_or_default();
+async fn query_btn_menu(id: &i64, rb: &RBatis) -> AppResult<Vec<String>> {
     let mut btn_menu: Vec<String> = Vec::new();
-    if count == 1 {
-        let data = Menu::select_all(&rb).await;
-
-        for x in data.unwrap_or_default() {
+    if is_admin(rb, id).await? == 1 {
+        for x in Menu::select_all(rb).await? {
             if let Some(api_url) = x.api_url {
                 if api_url != "" {
                     btn_menu.push(api_url);
@@ -376,11 +1112,11 @@ async fn query_btn_menu(id: &i64, rb: RBatis) -> Vec<String> {
             }
         }
         log::info!("admin login: {:?}", id);
-        btn_menu
     } else {
-        let btn_menu_map: Vec<HashMap<String, String>> = rb.query_decode("select distinct u.api_url from sys_user_role t left join sys_role usr on t.role_id = usr.id left join sys_role_menu srm on usr.id = srm.role_id left join sys_menu u on srm.menu_id = u.id where t.user_id = ?", vec![value!(id)]).await.unwrap();
+        //角色没有分配菜单时left join查询出的api_url为null
+        let btn_menu_map: Vec<HashMap<String, Option<String>>> = rb.query_decode("select distinct u.api_url from sys_user_role t left join sys_role usr on t.role_id = usr.id left join sys_role_menu srm on usr.id = srm.role_id left join sys_menu u on srm.menu_id = u.id where t.user_id = ?", vec![value!(id)]).await?;
         for x in btn_menu_map {
-            if let Some(api_url) = x.get("api_url") {
+            if let Some(Some(api_url)) = x.get("api_url") {
                 if api_url.to_string() != "" {
                     btn_menu.push(api_url.to_string());
                 }
@@ -388,8 +1124,8 @@ async fn query_btn_menu(id: &i64, rb: RBatis) -> Vec<String> {
         }
 
         log::info!("ordinary login: {:?}", id);
-        btn_menu
     }
+    Ok(btn_menu)
 }
 
 /*
@@ -398,12 +1134,12 @@ async fn query_btn_menu(id: &i64, rb: RBatis) -> Vec<String> {
  *date：2025/01/08 17:16:44
  */
 #[post("/system/user/queryUserRole")]
-pub async fn query_user_role(item: web::Json<QueryUserRoleReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn query_user_role(item: ValidatedJson<QueryUserRoleReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("query user_role params: {:?}", item);
     let rb = &data.batis;
 
     let mut user_role_ids: Vec<i64> = Vec::new();
-    let sys_role_list = Role::select_all(rb).await.map(|x| x.into_iter().map(|x| x.into()).collect::<Vec<RoleResp>>())?;
+    let sys_role_list = Role::select_all_not_deleted(rb).await.map(|x| x.into_iter().map(|x| x.into()).collect::<Vec<RoleResp>>())?;
 
     if item.user_id != 1 {
         let list = UserRole::select_by_map(rb, value! {"user_id": item.user_id}).await?;
@@ -419,7 +1155,7 @@ pub async fn query_user_role(item: web::Json<QueryUserRoleReq>, data: web::Data<
  *date：2025/01/08 17:16:44
  */
 #[post("/system/user/updateUserRole")]
-pub async fn update_user_role(item: web::Json<UpdateUserRoleReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
+pub async fn update_user_role(item: ValidatedJson<UpdateUserRoleReq>, data: web::Data<AppState>) -> AppResult<impl Responder> {
     log::info!("update_user_role params: {:?}", item);
     let rb = &data.batis;
 
@@ -428,11 +1164,9 @@ pub async fn update_user_role(item: web::Json<UpdateUserRoleReq>, data: web::Dat
     let len = item.role_ids.len();
 
     if user_id == 1 {
-        return Err(AppError::BusinessError("不能修改超级管理员的角色"));
+        return Err(AppError::business(ErrorCode::SuperAdminProtected, "不能修改超级管理员的角色"));
     }
 
-    UserRole::delete_by_map(rb, value! {"user_id": &user_id}).await?;
-
     let mut list: Vec<UserRole> = Vec::new();
     for role_id in role_ids {
         let r_id = role_id.clone();
@@ -444,7 +1178,15 @@ pub async fn update_user_role(item: web::Json<UpdateUserRoleReq>, data: web::Dat
         })
     }
 
-    UserRole::insert_batch(rb, &list, len as u64).await?;
+    transaction(rb, async |tx| {
+        UserRole::delete_by_map(tx, value! {"user_id": &user_id}).await?;
+        if len > 0 {
+            UserRole::insert_batch(tx, &list, len as u64).await?;
+        }
+        Ok(())
+    })
+    .await?;
+    data.permission_cache.invalidate_user(user_id);
 
     ok_result()
 }
@@ -462,9 +1204,12 @@ pub async fn query_user_menu(req: HttpRequest, data: web::Data<AppState>) -> App
 
     log::info!("query user menu params user_id {:?}", user_id);
 
+    //模拟登录时标记实际操作的管理员
+    let impersonated_by = req.extensions().get::<JwtToken>().filter(|x| x.is_impersonated()).map(|x| x.act_name.clone());
+
     //根据id查询用户
-    match User::select_by_id(rb, 1).await? {
-        None => Err(AppError::BusinessError("用户不存在")),
+    match User::select_by_id(rb, user_id).await? {
+        None => Err(AppError::from(ErrorCode::UserNotFound)),
         Some(user) => {
             //role_id为1是超级管理员--判断是不是超级管理员
             let sql_str = "select count(id) from sys_user_role where role_id = 1 and user_id = ?";
@@ -522,6 +1267,8 @@ pub async fn query_user_menu(req: HttpRequest, data: web::Data<AppState>) -> App
                 btn_menu,
                 avatar: user.avatar,
                 name: user.user_name,
+                locale: user.locale,
+                impersonated_by,
             };
 
             ok_result_data(resp)
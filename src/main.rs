@@ -10,16 +10,34 @@ pub mod utils;
 pub mod vo;
 
 use crate::handler::system::{
-    sys_dept_handler, sys_dict_data_handler, sys_dict_type_handler, sys_login_log_handler, sys_menu_handler, sys_notice_handler, sys_operate_log_handler, sys_post_handler, sys_role_handler,
-    sys_user_handler,
+    sys_api_token_handler, sys_captcha_handler, sys_dept_handler, sys_dict_data_handler, sys_dict_type_handler, sys_login_log_handler, sys_menu_handler, sys_mfa_handler, sys_notice_handler,
+    sys_oidc_handler, sys_online_handler, sys_operate_log_handler, sys_post_handler, sys_role_handler, sys_user_handler,
 };
 use actix_web::{get, middleware as md, web, App, HttpResponse, HttpServer, Responder};
-use config::{Config, File};
+use common::error::{AppError, AppResult};
 use middleware::auth;
+use middleware::locale;
+use middleware::operate_log;
+use middleware::request_id;
 use rbatis::RBatis;
-use serde::Deserialize;
 use tracing_actix_web::TracingLogger;
+use utils::api_token_util::ApiTokenConfig;
+use utils::authenticator::AuthenticatorChain;
+use utils::captcha_util::CaptchaStore;
+use utils::cli::{self, Command};
 use utils::db::init_db;
+use utils::ip_util::IpUtil;
+use utils::jwt_util::JwtKeys;
+use utils::login_limit_util::LoginLimiter;
+use utils::migration;
+use utils::oidc_util::OidcClient;
+use utils::password_policy::PasswordPolicyConfig;
+use utils::password_util::PasswordUtil;
+use utils::permission_cache::PermissionCache;
+use utils::permission_util::PermissionMatcher;
+use utils::settings::Settings;
+use utils::token_util::TokenStore;
+use utils::totp_util::MfaConfig;
 
 #[get("/")]
 async fn index() -> impl Responder {
@@ -31,55 +49,82 @@ async fn index() -> impl Responder {
 
 // This struct represents the application state, containing the application name and a database connection.
 pub struct AppState {
-    pub app_name: String, // The name of the application.
-    pub batis: RBatis,    // The database connection instance.
-}
-
-// Config1 represents the overall configuration for the application, including server and database settings.
-#[derive(Debug, Deserialize)]
-struct Config1 {
-    server: ServerConfig, // Configuration for the server.
-    db: DbConfig,         // Configuration for the database.
-}
-
-// ServerConfig contains the server address configuration.
-#[derive(Debug, Deserialize)]
-struct ServerConfig {
-    addr: String, // The address on which the server will listen.
-}
-
-// DbConfig contains the database connection URL configuration.
-#[derive(Debug, Deserialize)]
-struct DbConfig {
-    url: String, // The URL used to connect to the database.
+    pub app_name: String,                      // The name of the application.
+    pub batis: RBatis,                         // The database connection instance.
+    pub password_util: PasswordUtil,           // The password hashing settings.
+    pub password_policy: PasswordPolicyConfig, // The password strength, reuse and expiry rules.
+    pub jwt_keys: JwtKeys,                     // The keys used to sign and verify tokens.
+    pub token_store: TokenStore,               // The revoked tokens and sessions.
+    pub permission_matcher: PermissionMatcher, // The public routes and permission rules.
+    pub permission_cache: PermissionCache,     // The cached permissions of users.
+    pub ip_util: IpUtil,                       // The client ip resolver and ip location database.
+    pub captcha_store: CaptchaStore,           // The login captcha codes.
+    pub login_limiter: LoginLimiter,           // The failed login counters and locks.
+    pub mfa_config: MfaConfig,                 // The two-factor authentication policy.
+    pub authenticators: AuthenticatorChain,    // The password authenticators tried in order.
+    pub oidc_client: OidcClient,               // The OpenID Connect single sign-on client.
+    pub api_token_config: ApiTokenConfig,      // The personal api token limits.
 }
 
 #[actix_web::main] // or #[tokio::main]
 async fn main() -> std::io::Result<()> {
     // env_logger::init_from_env(env_logger::Env::new().default_filter_or("info"));
-    log4rs::init_file("src/config/log4rs.yaml", Default::default()).unwrap();
-    log::info!("starting HTTP server at http://0.0.0.0:8788");
-    let config = Config::builder().add_source(File::with_name("config.toml")).build().unwrap().try_deserialize::<Config1>().unwrap();
-    println!("Config: {:?}", config);
+    let config = exit_on_error("load config", Settings::load());
+    if let Err(err) = log4rs::init_file(&config.log.config_file, Default::default()) {
+        exit_on_error("init log", Err::<(), _>(AppError::ConfigError(err.to_string())));
+    }
+
+    //命令行命令: actix-admin [serve|migrate|create-admin|reset-password|unlock|sync-menus|purge-logs|check-config|help]
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
+    let command = exit_on_error("parse command", Command::parse(&args));
+    match &command {
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            return Ok(());
+        }
+        Command::CheckConfig => {
+            println!("config is valid, profile: {}", if config.profile.is_empty() { "default" } else { &config.profile });
+            return Ok(());
+        }
+        _ => {}
+    }
 
-    let rb = init_db(config.db.url.as_str()).await;
-    HttpServer::new(move || {
+    let rb = exit_on_error("init db", init_db(&config.db).await);
+    if let Command::Migrate(args) = &command {
+        exit_on_error("migrate", migration::run_command(&rb, args).await);
+        return Ok(());
+    }
+    exit_on_error("migrate", migration::run_on_startup(&rb, &config.migration).await);
+    let state = init_state(&config, rb);
+    if command != Command::Serve {
+        exit_on_error("command", cli::run(&state, &config, command).await);
+        return Ok(());
+    }
+
+    log::info!("starting HTTP server at http://{}, profile: {}", config.server.addr, config.profile);
+    let data = web::Data::new(state);
+    let operate_log_config = config.operate_log.clone();
+    let server = HttpServer::new(move || {
         App::new()
-            .app_data(web::Data::new(AppState {
-                app_name: String::from("Actix Web"),
-                batis: rb.clone(),
-            }))
+            .app_data(data.clone())
             .wrap(md::Logger::default())
             .wrap(TracingLogger::default())
+            .wrap(operate_log::Audit::new(&operate_log_config))
             .wrap(auth::Auth)
+            .wrap(request_id::RequestId)
+            .wrap(locale::Localize)
             .service(index)
             .service(
                 (web::scope("/api"))
                     .service(sys_user_handler::add_sys_user)
                     .service(sys_user_handler::delete_sys_user)
+                    .service(sys_user_handler::query_deleted_user_list)
+                    .service(sys_user_handler::restore_sys_user)
+                    .service(sys_user_handler::purge_sys_user)
                     .service(sys_user_handler::update_sys_user)
                     .service(sys_user_handler::update_sys_user_status)
                     .service(sys_user_handler::update_sys_user_password)
+                    .service(sys_user_handler::update_sys_user_locale)
                     .service(sys_user_handler::reset_sys_user_password)
                     .service(sys_user_handler::query_sys_user_detail)
                     .service(sys_user_handler::query_sys_user_list)
@@ -87,14 +132,39 @@ async fn main() -> std::io::Result<()> {
                     .service(sys_user_handler::update_user_role)
                     .service(sys_user_handler::query_user_menu)
                     .service(sys_user_handler::login)
+                    .service(sys_user_handler::refresh_token)
+                    .service(sys_user_handler::logout)
+                    .service(sys_user_handler::unlock_user)
+                    .service(sys_user_handler::impersonate_user)
+                    .service(sys_user_handler::login_mfa)
+                    .service(sys_user_handler::login_mfa_setup)
+                    .service(sys_user_handler::change_expired_password)
+                    .service(sys_mfa_handler::query_mfa_status)
+                    .service(sys_mfa_handler::setup_mfa)
+                    .service(sys_mfa_handler::confirm_mfa)
+                    .service(sys_mfa_handler::disable_mfa)
+                    .service(sys_mfa_handler::regenerate_recovery_codes)
+                    .service(sys_mfa_handler::reset_user_mfa)
+                    .service(sys_captcha_handler::query_captcha)
+                    .service(sys_api_token_handler::add_api_token)
+                    .service(sys_api_token_handler::query_api_token_list)
+                    .service(sys_api_token_handler::revoke_api_token)
+                    .service(sys_api_token_handler::revoke_user_api_token)
+                    .service(sys_oidc_handler::oidc_authorize)
+                    .service(sys_oidc_handler::oidc_callback)
                     .service(sys_role_handler::add_sys_role)
                     .service(sys_role_handler::delete_sys_role)
+                    .service(sys_role_handler::query_deleted_role_list)
+                    .service(sys_role_handler::restore_sys_role)
+                    .service(sys_role_handler::purge_sys_role)
                     .service(sys_role_handler::update_sys_role)
                     .service(sys_role_handler::update_sys_role_status)
                     .service(sys_role_handler::query_sys_role_detail)
                     .service(sys_role_handler::query_sys_role_list)
                     .service(sys_role_handler::query_role_menu)
                     .service(sys_role_handler::update_role_menu)
+                    .service(sys_role_handler::query_role_dept)
+                    .service(sys_role_handler::update_role_dept)
                     .service(sys_role_handler::query_allocated_list)
                     .service(sys_role_handler::query_unallocated_list)
                     .service(sys_role_handler::cancel_auth_user)
@@ -139,13 +209,63 @@ async fn main() -> std::io::Result<()> {
                     .service(sys_dict_data_handler::query_sys_dict_data_list)
                     .service(sys_dept_handler::add_sys_dept)
                     .service(sys_dept_handler::delete_sys_dept)
+                    .service(sys_dept_handler::query_deleted_dept_list)
+                    .service(sys_dept_handler::restore_sys_dept)
+                    .service(sys_dept_handler::purge_sys_dept)
                     .service(sys_dept_handler::update_sys_dept)
                     .service(sys_dept_handler::update_sys_dept_status)
                     .service(sys_dept_handler::query_sys_dept_detail)
-                    .service(sys_dept_handler::query_sys_dept_list),
+                    .service(sys_dept_handler::query_sys_dept_list)
+                    .service(sys_online_handler::query_online_list)
+                    .service(sys_online_handler::query_online_detail)
+                    .service(sys_online_handler::force_logout)
+                    .service(sys_online_handler::force_logout_user),
             )
-    })
-    .bind(config.server.addr)?
-    .run()
-    .await
+    });
+
+    let server = match config.server.workers {
+        0 => server,
+        workers => server.workers(workers),
+    };
+    server.bind(&config.server.addr)?.run().await
+}
+
+// 初始化各个组件,服务和命令行共用
+fn init_state(config: &Settings, rb: RBatis) -> AppState {
+    let password_util = exit_on_error("init password util", PasswordUtil::new(&config.password));
+    let authenticators = exit_on_error("init authenticators", AuthenticatorChain::new(&config.authenticator, &password_util));
+    let jwt = &config.jwt;
+    let max_ttl = [jwt.ttl, jwt.refresh_ttl, jwt.impersonate_ttl, config.mfa.challenge_ttl, config.password_policy.change_ttl]
+        .into_iter()
+        .max()
+        .unwrap_or_default();
+    AppState {
+        app_name: String::from("Actix Web"),
+        batis: rb,
+        password_util,
+        password_policy: config.password_policy.clone(),
+        jwt_keys: exit_on_error("init jwt keys", JwtKeys::from_config(&config.jwt)),
+        token_store: TokenStore::new(max_ttl),
+        permission_matcher: PermissionMatcher::new(&config.auth),
+        permission_cache: PermissionCache::new(&config.permission_cache),
+        ip_util: IpUtil::new(&config.ip),
+        captcha_store: CaptchaStore::new(),
+        login_limiter: LoginLimiter::new(&config.login_limit),
+        mfa_config: config.mfa.clone(),
+        authenticators,
+        oidc_client: exit_on_error("init oidc client", OidcClient::new(&config.oidc)),
+        api_token_config: config.api_token.clone(),
+    }
+}
+
+// 启动时初始化失败直接输出错误信息并退出,不使用unwrap()触发panic
+fn exit_on_error<T>(name: &str, result: AppResult<T>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(err) => {
+            eprintln!("{} error: {}", name, err);
+            log::error!("{} error: {}", name, err);
+            std::process::exit(1)
+        }
+    }
 }
@@ -2,16 +2,28 @@ use std::future::{ready, Ready};
 use std::rc::Rc;
 
 use crate::common::error::AppError;
-use crate::utils::jwt_util::JwtToken;
-use actix_web::error;
+use crate::common::error_code::ErrorCode;
+use crate::common::i18n::{with_locale, Locale};
+use crate::handler::system::sys_api_token_handler::verify_api_token;
+use crate::handler::system::sys_user_handler::query_user_permissions;
+use crate::middleware::operate_log::add_denied_log;
+use crate::model::system::sys_user_session_model::{update_last_access_time, UserSession};
+use crate::utils::api_token_util::API_TOKEN_PREFIX;
+use crate::utils::jwt_util::{JwtToken, ACCESS_TOKEN};
+use crate::utils::token_util::now_secs;
+use crate::AppState;
 use actix_web::http::header;
 use actix_web::http::header::HeaderValue;
 use actix_web::{
     dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
     Error,
 };
+use actix_web::{web, HttpMessage};
 use futures_util::future::LocalBoxFuture;
-use serde_json::json;
+use rbatis::rbdc::DateTime;
+
+//模拟登录的响应头,值为实际操作的管理员id
+pub const IMPERSONATED_BY: &str = "x-impersonated-by";
 
 // There are two steps in middleware processing.
 // 1. Middleware initialization, middleware factory gets called with
@@ -57,66 +69,118 @@ where
         let svc = self.service.clone();
 
         let path = req.path().to_string();
+        let method = req.method().to_string();
 
-        let def = header::HeaderValue::from_str("").unwrap();
-        let token = req.headers().get("Authorization").unwrap_or(&def).to_str().ok().unwrap().replace("Bearer ", "");
+        let token = req.headers().get("Authorization").and_then(|x| x.to_str().ok()).unwrap_or_default().replace("Bearer ", "");
 
         return Box::pin(async move {
             log::info!("Hi from start. You requested path: {}", path);
 
-            if path.contains("login") {
+            let state = match req.app_data::<web::Data<AppState>>() {
+                Some(state) => state.clone(),
+                None => return Err(AppError::ConfigError("app state not configured".to_string()).into()),
+            };
+
+            if state.permission_matcher.is_public(&method, &path) {
                 let fut = svc.call(req);
                 let res = fut.await?;
                 return Ok(res);
             }
 
             if token.len() <= 0 {
-                let res = json!({
-                    "msg": "token不能为空",
-                    "code": 2,
-                    "path": path
-                });
-                return Err(error::ErrorUnauthorized(res.to_string()));
+                return Err(AppError::from(ErrorCode::Unauthorized).into());
             }
 
-            let jwt_token_e = JwtToken::verify("123", &token);
-            let jwt_token = match jwt_token_e {
-                Ok(data) => data,
-                Err(err) => {
-                    let er = match err {
-                        AppError::JwtTokenError(s) => s,
-                        _ => "no math error".to_string(),
-                    };
-                    let john = json!({
-                        "msg": er,
-                        "code": 2,
-                        "path": path
-                    });
-                    log::error!("Hi from start. You requested path: {}, token: {}", path, token);
-                    return Err(error::ErrorUnauthorized(john.to_string()));
+            let (jwt_token, flag) = if token.starts_with(API_TOKEN_PREFIX) {
+                //API令牌: 只能访问令牌权限和用户当前权限都允许的接口,不能访问只需要登录的接口
+                let ipaddr = state.ip_util.client_ip(req.peer_addr(), req.headers());
+                let (jwt_token, scopes) = match verify_api_token(&state, &token, &ipaddr).await {
+                    Ok(data) => data,
+                    Err(err) => {
+                        let er = match err {
+                            AppError::JwtTokenError(s) => s,
+                            err => {
+                                log::error!("verify api token error: {}", err);
+                                "API令牌校验失败".to_string()
+                            }
+                        };
+                        return Err(AppError::JwtTokenError(er).into());
+                    }
+                };
+                let matcher = &state.permission_matcher;
+                let permissions = query_user_permissions(&state, jwt_token.id).await?;
+                let flag = matcher.is_permitted(&scopes, &method, &path) && matcher.is_permitted(&permissions, &method, &path);
+                (jwt_token, flag)
+            } else {
+                let jwt_token_e = JwtToken::verify(&state.jwt_keys, &token);
+                let jwt_token = match jwt_token_e {
+                    Ok(data) => data,
+                    Err(err) => {
+                        let er = match err {
+                            AppError::JwtTokenError(s) => s,
+                            _ => "no math error".to_string(),
+                        };
+                        log::error!("Hi from start. You requested path: {}, token: {}", path, token);
+                        return Err(AppError::JwtTokenError(er).into());
+                    }
+                };
+
+                if jwt_token.token_type != ACCESS_TOKEN || state.token_store.is_revoked(&jwt_token) {
+                    return Err(AppError::from(ErrorCode::TokenInvalid).into());
                 }
-            };
 
-            let mut flag: bool = false;
-            for token_permission in &jwt_token.permissions {
-                if token_permission.to_string() == path {
-                    flag = true;
-                    break;
+                //定期和数据库核对会话状态(服务重启后仍然能识别被强制下线的会话),并刷新最后访问时间
+                if state.token_store.need_check_session(&jwt_token.sid) {
+                    match UserSession::select_by_session_id(&state.batis, &jwt_token.sid).await {
+                        Ok(Some(session)) if session.status == 1 => {
+                            if let Err(err) = update_last_access_time(&state.batis, &DateTime::now(), &jwt_token.sid).await {
+                                log::error!("update session last_access_time error: {:?}", err);
+                            }
+                        }
+                        Ok(_) => {
+                            state.token_store.revoke_session(&jwt_token.sid, now_secs() + state.jwt_keys.refresh_ttl as usize);
+                            return Err(AppError::from(ErrorCode::SessionOffline).into());
+                        }
+                        Err(err) => log::error!("query user session error: {:?}", err),
+                    }
                 }
-            }
+
+                let matcher = &state.permission_matcher;
+                if jwt_token.is_impersonated() && matcher.is_impersonate_denied(&method, &path) {
+                    let err = AppError::from(ErrorCode::ImpersonateDenied);
+                    add_denied_log(&state, &req, &jwt_token, &err);
+                    return Err(err.into());
+                }
+                let mut flag = matcher.is_login_route(&method, &path);
+                if !flag {
+                    //权限从权限缓存中获取,角色,菜单变更后无需重新登录即可生效
+                    let permissions = query_user_permissions(&state, jwt_token.id).await?;
+                    flag = matcher.is_permitted(&permissions, &method, &path);
+                }
+                (jwt_token, flag)
+            };
             req.headers_mut().insert("userId".parse().unwrap(), HeaderValue::from(jwt_token.id));
+            req.extensions_mut().insert(jwt_token.clone());
             if flag {
+                //用户设置了语言偏好时使用用户的语言偏好,否则使用请求头Accept-Language协商的语言
                 let fut = svc.call(req);
-                let res = fut.await?;
+                let mut res = match Locale::parse(&jwt_token.lang) {
+                    Some(locale) => with_locale(locale, fut).await?,
+                    None => fut.await?,
+                };
+                //模拟登录的响应中标记实际操作的管理员,前端可以据此显示模拟登录提示
+                if let Some(act) = jwt_token.act {
+                    res.headers_mut().insert(header::HeaderName::from_static(IMPERSONATED_BY), HeaderValue::from(act));
+                }
                 Ok(res)
             } else {
-                log::error!("Hi from start. You requested path: {:?}", jwt_token.permissions);
-                let res = json!({
-                    "msg": "无权限访问",
-                    "code": 1,
-                    "path": path
-                });
-                Err(error::ErrorUnauthorized(res.to_string()))
+                log::error!("Hi from start. You requested path: {}, user: {}", path, jwt_token.id);
+                let err = AppError::from(ErrorCode::PermissionDenied);
+                //模拟登录时被拒绝的请求也要记录操作日志
+                if jwt_token.is_impersonated() {
+                    add_denied_log(&state, &req, &jwt_token, &err);
+                }
+                Err(err.into())
             }
         });
     }
@@ -0,0 +1,60 @@
+use std::future::{ready, Ready};
+use std::rc::Rc;
+
+use crate::common::i18n::{with_locale, Locale};
+use actix_web::http::header;
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    Error,
+};
+use futures_util::future::LocalBoxFuture;
+
+/*
+ *根据请求头Accept-Language协商响应和错误信息的语言,需要放在request_id外层(最后一个wrap)
+ *登录用户设置了语言偏好时,认证中间件使用用户的语言偏好
+ *author：刘飞华
+ *date：2026/10/19 16:10:42
+ */
+pub struct Localize;
+
+impl<S, B> Transform<S, ServiceRequest> for Localize
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = LocalizeMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(LocalizeMiddleware { service: Rc::new(service) }))
+    }
+}
+
+pub struct LocalizeMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for LocalizeMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let svc = self.service.clone();
+        let accept_language = req.headers().get(header::ACCEPT_LANGUAGE).and_then(|x| x.to_str().ok()).unwrap_or_default();
+        let locale = Locale::negotiate(accept_language);
+
+        Box::pin(with_locale(locale, async move { svc.call(req).await }))
+    }
+}
@@ -1 +1,4 @@
 pub mod auth;
+pub mod locale;
+pub mod operate_log;
+pub mod request_id;
@@ -0,0 +1,369 @@
+use std::future::{ready, Ready};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::common::error::AppError;
+use crate::common::validate::JSON_LIMIT;
+use crate::model::system::sys_dept_model::Dept;
+use crate::model::system::sys_menu_model::Menu;
+use crate::model::system::sys_operate_log_model::OperateLog;
+use crate::model::system::sys_user_model::User;
+use crate::utils::jwt_util::JwtToken;
+use crate::utils::permission_util::RoutePattern;
+use crate::AppState;
+use actix_web::body::{to_bytes, BoxBody, MessageBody};
+use actix_web::dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::error::PayloadError;
+use actix_web::http::Method;
+use actix_web::web::{Bytes, BytesMut};
+use actix_web::{rt, web, Error, HttpMessage};
+use futures_util::future::LocalBoxFuture;
+use futures_util::{stream, Stream, StreamExt};
+use rbatis::rbdc::DateTime;
+use serde::Deserialize;
+use serde_json::Value;
+
+/*
+ *操作日志配置
+ *author：刘飞华
+ *date：2026/10/18 18:20:31
+ */
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct OperateLogConfig {
+    pub enabled: bool,               //是否记录操作日志
+    pub exclude_routes: Vec<String>, //不记录操作日志的接口,格式和[auth]中的接口匹配规则一致
+}
+
+impl Default for OperateLogConfig {
+    fn default() -> Self {
+        OperateLogConfig {
+            enabled: true,
+            exclude_routes: [
+                "POST /api/system/user/login",
+                "POST /api/system/user/loginMfa",
+                "POST /api/system/user/loginMfaSetup",
+                "POST /api/system/user/refreshToken",
+                "POST /api/system/mfa/setupMfa",
+                "POST /api/system/mfa/regenerateRecoveryCodes",
+                "POST /api/system/apiToken/addApiToken",
+            ]
+            .iter()
+            .map(|x| x.to_string())
+            .collect(),
+        }
+    }
+}
+
+//请求参数,返回参数和错误消息的最大长度(和表字段长度一致)
+const MAX_LOG_LEN: usize = 2000;
+//需要脱敏的参数名称(包含即脱敏,不区分大小写),两步验证的二维码和otpauth地址中包含密钥
+const MASK_KEYS: [&str; 7] = ["password", "pwd", "token", "secret", "recovery", "otpauth", "qrcode"];
+
+// 记录增删改接口的操作日志,管理员模拟登录时记录所有接口
+// 需要在Auth之前注册(Auth在外层),这样才能从请求中获取到当前登录用户;模拟登录时被Auth拒绝的请求由Auth记录
+pub struct Audit {
+    enabled: bool,
+    exclude_routes: Arc<Vec<RoutePattern>>,
+}
+
+impl Audit {
+    pub fn new(config: &OperateLogConfig) -> Audit {
+        Audit {
+            enabled: config.enabled,
+            exclude_routes: Arc::new(config.exclude_routes.iter().map(|x| RoutePattern::parse(x)).collect()),
+        }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for Audit
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = AuditMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AuditMiddleware {
+            service: Rc::new(service),
+            enabled: self.enabled,
+            exclude_routes: self.exclude_routes.clone(),
+        }))
+    }
+}
+
+pub struct AuditMiddleware<S> {
+    service: Rc<S>,
+    enabled: bool,
+    exclude_routes: Arc<Vec<RoutePattern>>,
+}
+
+impl<S> AuditMiddleware<S> {
+    // 只记录/api下的增删改接口,查询接口不记录
+    // 管理员模拟登录时记录/api下的所有接口(包括查询接口),不受配置影响
+    fn need_log(&self, method: &Method, path: &str, impersonated: bool) -> bool {
+        if impersonated {
+            return path.starts_with("/api/");
+        }
+        if !self.enabled || !path.starts_with("/api/") || matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+            return false;
+        }
+        if path.rsplit('/').next().unwrap_or_default().starts_with("query") {
+            return false;
+        }
+        !self.exclude_routes.iter().any(|x| x.matches(method.as_str(), path))
+    }
+}
+
+impl<S, B> Service<ServiceRequest> for AuditMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let svc = self.service.clone();
+
+        let impersonated = req.extensions().get::<JwtToken>().is_some_and(|x| x.is_impersonated());
+        if !self.need_log(req.method(), req.path(), impersonated) {
+            return Box::pin(async move { svc.call(req).await.map(|res| res.map_into_boxed_body()) });
+        }
+
+        Box::pin(async move {
+            let start = Instant::now();
+
+            //读取请求参数后放回请求中,供后续的handler使用;超过长度限制时不再读取,剩余的部分直接交给handler(由handler返回请求体过大)
+            let mut payload = req.take_payload();
+            let (body, complete) = read_payload(&mut payload, JSON_LIMIT).await?;
+            let replay = body.clone();
+            let rest = stream::once(async move { Ok::<Bytes, PayloadError>(replay) }).chain(payload);
+            req.set_payload(Payload::from(rest.boxed_local()));
+            let operate_param = match complete {
+                true => mask_json(&body),
+                false => format!("[请求参数超过{}字节,不记录]", JSON_LIMIT),
+            };
+
+            let state = req.app_data::<web::Data<AppState>>().cloned();
+            let mut log = new_log(&req, state.as_ref().map(|x| x.get_ref()), operate_param);
+
+            let res = svc.call(req).await?;
+
+            let user = res.request().extensions().get::<JwtToken>().cloned();
+            let error = res.response().error().map(|x| x.to_string());
+            let http_ok = res.status().is_success();
+            let (http_req, res) = res.into_parts();
+            let (res, body) = res.into_parts();
+            let body = to_bytes(body).await.unwrap_or_default();
+
+            //返回的code为0表示操作成功
+            let result = serde_json::from_slice::<Value>(&body).ok();
+            let code = result.as_ref().and_then(|x| x.get("code")).and_then(|x| x.as_i64());
+            let success = http_ok && error.is_none() && code.unwrap_or_default() == 0;
+            log.json_result = Some(truncate(mask_json(&body)));
+            log.status = Some(if success { 1 } else { 0 });
+            if !success {
+                let msg = result.as_ref().and_then(|x| x.get("msg")).and_then(|x| x.as_str()).map(|x| x.to_string());
+                log.error_msg = Some(truncate(error.or(msg).unwrap_or_default()));
+            }
+            log.cost_time = Some(start.elapsed().as_millis() as i64);
+
+            //异步写入操作日志,不影响接口响应时间
+            if let Some(state) = state {
+                rt::spawn(async move { add_operate_log(&state, log, user).await });
+            }
+
+            Ok(ServiceResponse::new(http_req, res.set_body(BoxBody::new(body))))
+        })
+    }
+}
+
+/*
+ *记录模拟登录时被Auth拒绝的请求(请求没有到达Audit,由Auth调用)
+ *author：刘飞华
+ *date：2026/10/18 18:20:31
+ */
+pub fn add_denied_log(state: &web::Data<AppState>, req: &ServiceRequest, token: &JwtToken, error: &AppError) {
+    let mut log = new_log(req, Some(state), "".to_string());
+    log.status = Some(0);
+    log.error_msg = Some(truncate(error.to_string()));
+    log.cost_time = Some(0);
+
+    let state = state.clone();
+    let token = token.clone();
+    rt::spawn(async move { add_operate_log(&state, log, Some(token)).await });
+}
+
+// 根据请求创建操作日志,执行结果和操作人员在请求处理完之后补充
+fn new_log(req: &ServiceRequest, state: Option<&AppState>, operate_param: String) -> OperateLog {
+    OperateLog {
+        id: None,
+        title: None,
+        business_type: Some(business_type(req.path())),
+        method: req.path().rsplit('/').next().map(|x| x.to_string()),
+        request_method: Some(req.method().to_string()),
+        operator_type: Some(1),
+        operate_name: None,
+        dept_name: None,
+        impersonate_name: None,
+        operate_url: Some(req.path().to_string()),
+        operate_ip: state.map(|x| x.ip_util.client_ip(req.peer_addr(), req.headers())),
+        operate_location: None,
+        operate_param: Some(truncate(operate_param)),
+        json_result: None,
+        status: None,
+        error_msg: None,
+        operate_time: Some(DateTime::now()),
+        cost_time: None,
+    }
+}
+
+/*
+ *保存操作日志(补充模块标题,操作地点,操作人员,部门名称和模拟登录的管理员)
+ *author：刘飞华
+ *date：2026/10/18 18:20:31
+ */
+async fn add_operate_log(state: &AppState, mut log: OperateLog, user: Option<JwtToken>) {
+    let rb = &state.batis;
+    log.operate_location = log.operate_ip.as_ref().map(|x| state.ip_util.location(x));
+    let url = log.operate_url.clone().unwrap_or_default();
+    log.title = match Menu::select_by_api_url(rb, &url).await {
+        Ok(Some(menu)) => Some(menu.menu_name),
+        _ => url.split('/').rev().nth(1).map(|x| x.to_string()),
+    };
+
+    if let Some(token) = user {
+        if token.is_impersonated() {
+            log.impersonate_name = Some(token.act_name);
+        }
+        log.operate_name = Some(token.username);
+        if let Ok(Some(user)) = User::select_by_id(rb, token.id).await {
+            if let Ok(Some(dept)) = Dept::select_by_id(rb, &user.dept_id).await {
+                log.dept_name = Some(dept.dept_name);
+            }
+        }
+    }
+
+    if let Err(err) = OperateLog::insert(rb, &log).await {
+        log::error!("add_operate_log error params: {:?}, error message: {:?}", log, err.to_string());
+    }
+}
+
+// 根据接口名称判断业务类型（0其它 1新增 2修改 3删除）
+fn business_type(path: &str) -> i8 {
+    let name = path.rsplit('/').next().unwrap_or_default();
+    let types = [
+        ("add", 1),
+        ("batchAuth", 1),
+        ("update", 2),
+        ("reset", 2),
+        ("delete", 3),
+        ("batchCancel", 3),
+        ("cancel", 3),
+        ("clean", 3),
+    ];
+    types.iter().find(|(prefix, _)| name.starts_with(prefix)).map(|(_, t)| *t).unwrap_or_default()
+}
+
+// 读取请求参数,最多读取limit字节,返回读取的内容和是否已经读取完整
+async fn read_payload<P>(payload: &mut P, limit: usize) -> Result<(Bytes, bool), PayloadError>
+where
+    P: Stream<Item = Result<Bytes, PayloadError>> + Unpin,
+{
+    let mut body = BytesMut::new();
+    while let Some(chunk) = payload.next().await {
+        body.extend_from_slice(&chunk?);
+        if body.len() > limit {
+            return Ok((body.freeze(), false));
+        }
+    }
+    Ok((body.freeze(), true))
+}
+
+// 请求参数和返回结果中的密码,token,两步验证密钥等敏感信息脱敏
+fn mask_json(body: &[u8]) -> String {
+    match serde_json::from_slice::<Value>(body) {
+        Ok(mut value) => {
+            mask_value(&mut value);
+            value.to_string()
+        }
+        Err(_) => String::from_utf8_lossy(body).to_string(),
+    }
+}
+
+fn mask_value(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                let key = key.to_lowercase();
+                if MASK_KEYS.iter().any(|x| key.contains(x)) {
+                    *v = Value::String("******".to_string());
+                } else {
+                    mask_value(v);
+                }
+            }
+        }
+        Value::Array(list) => list.iter_mut().for_each(mask_value),
+        _ => {}
+    }
+}
+
+fn truncate(s: String) -> String {
+    match s.char_indices().nth(MAX_LOG_LEN) {
+        Some((i, _)) => s[..i].to_string(),
+        None => s,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::middleware::operate_log::{business_type, mask_json, read_payload};
+    use actix_web::error::PayloadError;
+    use actix_web::web::Bytes;
+    use futures_util::stream;
+
+    #[test]
+    fn test_mask_json() {
+        let body = r#"{"userName":"koobe","password":"123456","list":[{"newPwd":"abc"}],"refreshToken":"x"}"#;
+        let masked = mask_json(body.as_bytes());
+        assert!(!masked.contains("123456"));
+        assert!(!masked.contains("abc"));
+        assert!(!masked.contains("\"x\""));
+        assert!(masked.contains("koobe"));
+
+        assert_eq!(business_type("/api/system/user/addUser"), 1);
+        assert_eq!(business_type("/api/system/role/batchCancelAuthUser"), 3);
+        assert_eq!(business_type("/api/system/user/resetUserPassword"), 2);
+        assert_eq!(business_type("/api/system/user/logout"), 0);
+
+        //返回结果中的令牌明文,两步验证密钥和恢复码
+        let result = r#"{"code":0,"data":{"token":"rat_abc","secret":"JBSWY3DP","otpauthUri":"otpauth://totp/x?secret=JBSWY3DP","qrCode":"data:image/png;base64,xx","recoveryCodes":["r1","r2"]}}"#;
+        let masked = mask_json(result.as_bytes());
+        assert!(["rat_abc", "JBSWY3DP", "base64", "r1"].iter().all(|x| !masked.contains(x)), "{}", masked);
+        assert!(masked.contains(r#""code":0"#));
+    }
+
+    #[actix_web::test]
+    async fn test_read_payload() {
+        let chunks = || stream::iter(["ab", "cd", "ef"].map(|x| Ok::<Bytes, PayloadError>(Bytes::from(x))));
+
+        let mut payload = chunks();
+        assert_eq!(read_payload(&mut payload, 6).await.unwrap(), (Bytes::from("abcdef"), true));
+
+        //超过长度限制时停止读取,剩余的部分留在请求中
+        let mut payload = chunks();
+        assert_eq!(read_payload(&mut payload, 3).await.unwrap(), (Bytes::from("abcd"), false));
+        assert_eq!(futures_util::StreamExt::next(&mut payload).await.unwrap().unwrap(), Bytes::from("ef"));
+    }
+}
@@ -0,0 +1,123 @@
+use std::future::{ready, Ready};
+use std::rc::Rc;
+
+use actix_web::error::InternalError;
+use actix_web::http::header::{HeaderMap, HeaderName, HeaderValue};
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    Error, HttpMessage,
+};
+use futures_util::future::LocalBoxFuture;
+
+//请求id的请求头和响应头,请求头中有合法的请求id时(如: 网关生成的请求id)沿用该id
+pub const REQUEST_ID: &str = "x-request-id";
+
+tokio::task_local! {
+    static CURRENT_REQUEST_ID: String;
+}
+
+/*
+ *当前请求的请求id(不在请求中调用时返回None)
+ *author：刘飞华
+ *date：2026/10/19 14:20:18
+ */
+pub fn current_request_id() -> Option<String> {
+    CURRENT_REQUEST_ID.try_with(|x| x.clone()).ok()
+}
+
+/*
+ *请求id,可以在handler中通过 req.extensions().get::<RequestIdValue>() 获取
+ *author：刘飞华
+ *date：2026/10/19 14:20:18
+ */
+#[derive(Debug, Clone)]
+pub struct RequestIdValue(pub String);
+
+/*
+ *为每个请求生成请求id,写入响应头,错误响应中也会返回该id,需要放在最外层(最后一个wrap)
+ *author：刘飞华
+ *date：2026/10/19 14:20:18
+ */
+pub struct RequestId;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestId
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestIdMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestIdMiddleware { service: Rc::new(service) }))
+    }
+}
+
+pub struct RequestIdMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestIdMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let svc = self.service.clone();
+        let request_id = match req.headers().get(REQUEST_ID).and_then(|x| x.to_str().ok()) {
+            Some(id) if is_valid(id) => id.to_string(),
+            _ => uuid::Uuid::new_v4().simple().to_string(),
+        };
+        req.extensions_mut().insert(RequestIdValue(request_id.clone()));
+
+        Box::pin(CURRENT_REQUEST_ID.scope(request_id.clone(), async move {
+            match svc.call(req).await {
+                Ok(mut res) => {
+                    insert_header(res.headers_mut(), &request_id);
+                    Ok(res)
+                }
+                //中间件返回的错误(如: 认证失败)在这里生成响应,保证错误响应中能获取到请求id
+                Err(err) => {
+                    let mut res = err.error_response();
+                    insert_header(res.headers_mut(), &request_id);
+                    Err(InternalError::from_response(err, res).into())
+                }
+            }
+        }))
+    }
+}
+
+fn insert_header(headers: &mut HeaderMap, request_id: &str) {
+    if let Ok(value) = HeaderValue::from_str(request_id) {
+        headers.insert(HeaderName::from_static(REQUEST_ID), value);
+    }
+}
+
+fn is_valid(id: &str) -> bool {
+    !id.is_empty() && id.len() <= 64 && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::middleware::request_id::is_valid;
+
+    #[test]
+    fn test_is_valid() {
+        assert!(is_valid("2f1c6b0e9a7d4c3b8e5f6a7b8c9d0e1f"));
+        assert!(is_valid("gateway-123_abc"));
+        assert!(!is_valid(""));
+        assert!(!is_valid("a b"));
+        assert!(!is_valid(&"a".repeat(65)));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod sys_api_token_model;
 pub mod sys_dept_model;
 pub mod sys_dict_data_model;
 pub mod sys_dict_type_model;
@@ -9,6 +10,11 @@ pub mod sys_post_model;
 pub mod sys_role_dept_model;
 pub mod sys_role_menu_model;
 pub mod sys_role_model;
+pub mod sys_schema_migration_model;
+pub mod sys_user_identity_model;
+pub mod sys_user_mfa_model;
 pub mod sys_user_model;
+pub mod sys_user_password_history_model;
 pub mod sys_user_post_model;
 pub mod sys_user_role_model;
+pub mod sys_user_session_model;
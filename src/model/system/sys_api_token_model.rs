@@ -0,0 +1,91 @@
+// author：刘飞华
+// createTime：2026/10/18 22:48:15
+
+use crate::vo::system::sys_api_token_vo::ApiTokenResp;
+use rbatis::rbdc::datetime::DateTime;
+use rbatis::rbdc::db::ExecResult;
+use rbatis::RBatis;
+use serde::{Deserialize, Serialize};
+/*
+ *API令牌
+ *author：刘飞华
+ *date：2026/10/18 22:48:15
+ */
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub id: Option<i64>,                  //主键
+    pub user_id: i64,                     //所属用户ID
+    pub name: String,                     //令牌名称
+    pub token_prefix: String,             //令牌标识(令牌的前缀部分,明文保存)
+    pub token_hash: String,               //令牌哈希
+    pub permissions: String,              //令牌的权限(所属用户权限的子集,多个用逗号分隔)
+    pub status: i8,                       //状态(0:已吊销,1:正常)
+    pub expire_time: Option<DateTime>,    //过期时间(为空表示永久有效)
+    pub last_used_time: Option<DateTime>, //最后使用时间
+    pub last_used_ip: String,             //最后使用IP
+    pub create_time: Option<DateTime>,    //创建时间
+    pub update_time: Option<DateTime>,    //修改时间
+}
+
+/*
+ *API令牌基本操作
+ *author：刘飞华
+ *date：2026/10/18 22:48:15
+ */
+rbatis::crud!(ApiToken {}, "sys_api_token");
+
+impl From<ApiToken> for ApiTokenResp {
+    fn from(x: ApiToken) -> Self {
+        ApiTokenResp {
+            id: x.id,                                                                                         //主键
+            name: x.name,                                                                                     //令牌名称
+            token_prefix: x.token_prefix,                                                                     //令牌标识
+            permissions: x.permissions.split(',').filter(|x| !x.is_empty()).map(|x| x.to_string()).collect(), //令牌的权限
+            status: x.status,                                                                                 //状态(0:已吊销,1:正常)
+            expire_time: x.expire_time,                                                                       //过期时间
+            last_used_time: x.last_used_time,                                                                 //最后使用时间
+            last_used_ip: x.last_used_ip,                                                                     //最后使用IP
+            create_time: x.create_time,                                                                       //创建时间
+        }
+    }
+}
+
+/*
+ *根据令牌标识查询API令牌
+ *author：刘飞华
+ *date：2026/10/18 22:48:15
+ */
+impl_select!(ApiToken{select_by_token_prefix(token_prefix:&str) -> Option => "`where token_prefix = #{token_prefix} limit 1`"}, "sys_api_token");
+
+/*
+ *查询用户的API令牌
+ *author：刘飞华
+ *date：2026/10/18 22:48:15
+ */
+impl_select!(ApiToken{select_by_user_id(user_id:i64) => "`where user_id = #{user_id} order by id desc`"}, "sys_api_token");
+
+/*
+ *记录API令牌的最后使用时间和IP
+ *author：刘飞华
+ *date：2026/10/18 22:48:15
+ */
+#[sql("update sys_api_token set last_used_time = ?, last_used_ip = ? where id = ?")]
+pub async fn update_last_used(rb: &RBatis, last_used_time: &DateTime, last_used_ip: &str, id: i64) -> rbatis::Result<ExecResult> {
+    impled!()
+}
+
+/*
+ *吊销用户的API令牌
+ *author：刘飞华
+ *date：2026/10/19 10:58:20
+ */
+#[py_sql(
+    "`update sys_api_token set status = 0 where user_id = #{user_id} and id in (`
+     trim ',':
+       for _,item in ids:
+         `#{item},`
+     `)`"
+)]
+pub async fn revoke_api_tokens(rb: &RBatis, user_id: i64, ids: &[i64]) -> rbatis::Result<ExecResult> {
+    impled!()
+}
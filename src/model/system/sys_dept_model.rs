@@ -2,8 +2,12 @@
 // author：刘飞华
 // createTime：2024/12/25 10:01:11
 
+use crate::rbatis::rbatis_codegen::IntoSql;
+use crate::utils::data_scope_util::DataScope;
 use crate::vo::system::sys_dept_vo::{DeptReq, DeptResp};
+use rbatis::executor::Executor;
 use rbatis::rbdc::datetime::DateTime;
+use rbatis::rbdc::db::ExecResult;
 use rbatis::RBatis;
 use serde::{Deserialize, Serialize};
 /*
@@ -75,14 +79,14 @@ impl Into<DeptResp> for Dept {
  *author：刘飞华
  *date：2024/12/25 10:01:11
  */
-impl_select!(Dept{select_by_id(id:&i64) -> Option => "`where id = #{id} limit 1`"}, "sys_dept");
+impl_select!(Dept{select_by_id(id:&i64) -> Option => "`where id = #{id} and del_flag = 1 limit 1`"}, "sys_dept");
 
 /*
  *根据部门名称查询部门
  *author：刘飞华
  *date：2024/12/25 10:01:11
  */
-impl_select!(Dept{select_by_dept_name(dept_name:&str, parent_id:i64) -> Option => "`where dept_name = #{dept_name} and parent_id = #{parent_id} limit 1`"}, "sys_dept");
+impl_select!(Dept{select_by_dept_name(dept_name:&str, parent_id:i64) -> Option => "`where dept_name = #{dept_name} and parent_id = #{parent_id} and del_flag = 1 limit 1`"}, "sys_dept");
 
 /*
  *分页查询部门
@@ -90,7 +94,8 @@ impl_select!(Dept{select_by_dept_name(dept_name:&str, parent_id:i64) -> Option =
  *date：2024/12/25 10:01:11
  */
 impl_select_page!(Dept{select_page() =>"
-     if !sql.contains('count'):
+     ` where del_flag = 1 `
+     if do_count == false:
        order by create_time desc"
 },"sys_dept");
 
@@ -99,13 +104,18 @@ impl_select_page!(Dept{select_page() =>"
  *author：刘飞华
  *date：2024/12/25 10:01:11
  */
-impl_select!(Dept{select_page_dept_list(dept_name:&str, status:i8) =>"
-    where 1=1
+impl_select!(Dept{select_page_dept_list(dept_name:&str, status:i8, scope:&DataScope) =>"
+    where del_flag = 1
      if dept_name != null && dept_name != '':
       ` and dept_name = #{dept_name} `
      if status != 2:
       ` and status = #{status} `
-     if !sql.contains('count'):
+     if scope.all == false:
+      ` and id in (-1`
+      for _,item in scope.dept_ids:
+        `,#{item}`
+      `) `
+     if do_count == false:
       ` order by sort"
 },"sys_dept");
 
@@ -114,8 +124,13 @@ impl_select!(Dept{select_page_dept_list(dept_name:&str, status:i8) =>"
  *author：刘飞华
  *date：2024/12/12 14:41:44
  */
-#[sql("select count(*) from sys_dept where status = 1 and del_flag = '1' and find_in_set(?, ancestors)")]
 pub async fn select_normal_children_dept_by_id(rb: &RBatis, id: &i64) -> rbatis::Result<i64> {
+    let (equal, first, last, middle) = ancestors_patterns(id);
+    count_normal_children_dept(rb, &equal, &first, &last, &middle).await
+}
+
+#[sql("select count(*) from sys_dept where status = 1 and del_flag = 1 and (ancestors = ? or ancestors like ? or ancestors like ? or ancestors like ?)")]
+async fn count_normal_children_dept(rb: &RBatis, equal: &str, first: &str, last: &str, middle: &str) -> rbatis::Result<i64> {
     impled!()
 }
 
@@ -124,7 +139,7 @@ pub async fn select_normal_children_dept_by_id(rb: &RBatis, id: &i64) -> rbatis:
  *author：刘飞华
  *date：2024/12/12 14:41:44
  */
-#[sql("select count(1) from sys_dept where del_flag = '1' and parent_id = ?")]
+#[sql("select count(1) from sys_dept where del_flag = 1 and parent_id = ?")]
 pub async fn select_dept_count(rb: &RBatis, id: &i64) -> rbatis::Result<i64> {
     impled!()
 }
@@ -134,17 +149,104 @@ pub async fn select_dept_count(rb: &RBatis, id: &i64) -> rbatis::Result<i64> {
  *author：刘飞华
  *date：2024/12/12 14:41:44
  */
-#[sql("select count(1) from sys_user where dept_id = ? and del_flag = '1'")]
+#[sql("select count(1) from sys_user where dept_id = ? and del_flag = 1")]
 pub async fn check_dept_exist_user(rb: &RBatis, id: &i64) -> rbatis::Result<i64> {
     impled!()
 }
 
 /*
- * 描述：根据部门id查询是所有下级部门
+ * 描述：根据部门id查询是所有未删除的下级部门
  * author：刘飞华
  * date：2025/1/6 11:29
  */
-#[sql("select * from sys_dept where find_in_set(?, ancestors)")]
 pub async fn select_children_dept_by_id(rb: &RBatis, id: &i64) -> rbatis::Result<Vec<Dept>> {
+    let (equal, first, last, middle) = ancestors_patterns(id);
+    select_dept_by_ancestors(rb, &equal, &first, &last, &middle).await
+}
+
+#[sql("select * from sys_dept where del_flag = 1 and (ancestors = ? or ancestors like ? or ancestors like ? or ancestors like ?)")]
+async fn select_dept_by_ancestors(rb: &RBatis, equal: &str, first: &str, last: &str, middle: &str) -> rbatis::Result<Vec<Dept>> {
+    impled!()
+}
+
+/*
+ *根据部门id查询所有下级部门(包括已删除的部门,移动部门时回收站中的下级部门也要更新祖级列表)
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+pub async fn select_all_children_dept_by_id(rb: &RBatis, id: &i64) -> rbatis::Result<Vec<Dept>> {
+    let (equal, first, last, middle) = ancestors_patterns(id);
+    select_all_dept_by_ancestors(rb, &equal, &first, &last, &middle).await
+}
+
+#[sql("select * from sys_dept where ancestors = ? or ancestors like ? or ancestors like ? or ancestors like ?")]
+async fn select_all_dept_by_ancestors(rb: &RBatis, equal: &str, first: &str, last: &str, middle: &str) -> rbatis::Result<Vec<Dept>> {
+    impled!()
+}
+
+/*
+ *祖级列表包含部门id的匹配条件(代替mysql的find_in_set,各数据库通用): 只有该id、开头、结尾、中间
+ *author：刘飞华
+ *date：2026/10/19 10:42:18
+ */
+fn ancestors_patterns(id: &i64) -> (String, String, String, String) {
+    (id.to_string(), format!("{},%", id), format!("%,{}", id), format!("%,{},%", id))
+}
+
+/*
+ *批量更新部门状态
+ *author：刘飞华
+ *date：2026/10/19 10:58:10
+ */
+#[py_sql(
+    "`update sys_dept set status = #{status}, update_time = #{update_time} where id in (`
+     trim ',':
+       for _,item in ids:
+         `#{item},`
+     `)`"
+)]
+pub async fn update_dept_status(rb: &dyn Executor, ids: &[i64], status: i8, update_time: &DateTime) -> rbatis::Result<ExecResult> {
+    impled!()
+}
+
+/*
+ *查询全部未删除的部门
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+impl_select!(Dept{select_all_not_deleted() => "`where del_flag = 1`"}, "sys_dept");
+
+/*
+ *分页查询已删除的部门(回收站),按删除时间倒序
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+impl_select_page!(Dept{select_deleted_dept_list(dept_name:&str) =>"
+      where del_flag = 0
+     if dept_name != null && dept_name != '':
+       ` and dept_name = #{dept_name} `
+     if do_count == false:
+        ` order by update_time desc `"},"sys_dept");
+
+/*
+ *根据ids查询已删除的部门
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+impl_select!(Dept{select_deleted_by_ids(ids:&[i64]) -> Vec => "`where del_flag = 0 and id in ${ids.sql()}`"}, "sys_dept");
+
+/*
+ *批量更新部门的删除标志(删除: 0, 恢复: 1)
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+#[py_sql(
+    "`update sys_dept set del_flag = #{del_flag}, update_time = #{update_time} where id in (`
+     trim ',':
+       for _,item in ids:
+         `#{item},`
+     `)`"
+)]
+pub async fn update_dept_del_flag(rb: &dyn Executor, ids: &[i64], del_flag: i8, update_time: &DateTime) -> rbatis::Result<ExecResult> {
     impled!()
 }
@@ -2,10 +2,13 @@
 // author：刘飞华
 // createTime：2024/12/25 10:01:11
 
+use crate::common::i18n::{current_locale, Locale};
 use crate::vo::system::sys_dict_data_vo::{DictDataReq, DictDataResp};
 use rbatis::rbdc::datetime::DateTime;
+use rbatis::rbdc::db::ExecResult;
 use rbatis::RBatis;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
 /*
  *字典数据表
  *author：刘飞华
@@ -13,20 +16,31 @@ use serde::{Deserialize, Serialize};
  */
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DictData {
-    pub id: Option<i64>,               //字典编码
-    pub dict_sort: i32,                //字典排序
-    pub dict_label: String,            //字典标签
-    pub dict_value: String,            //字典键值
-    pub dict_type: String,             //字典类型
-    pub css_class: String,             //样式属性（其他样式扩展）
-    pub list_class: String,            //表格回显样式
-    pub is_default: String,            //是否默认（Y是 N否）
-    pub status: i8,                    //状态（0：停用，1:正常）
-    pub remark: Option<String>,        //备注
+    pub id: Option<i64>,        //字典编码
+    pub dict_sort: i32,         //字典排序
+    pub dict_label: String,     //字典标签
+    pub dict_value: String,     //字典键值
+    pub dict_type: String,      //字典类型
+    pub css_class: String,      //样式属性（其他样式扩展）
+    pub list_class: String,     //表格回显样式
+    pub is_default: String,     //是否默认（Y是 N否）
+    pub status: i8,             //状态（0：停用，1:正常）
+    pub remark: Option<String>, //备注
+    #[serde(deserialize_with = "deserialize_json_text")]
+    pub label_i18n: String, //字典标签的多语言翻译(json,如: {"en-US":"Male"}),没有对应语言时使用dict_label
     pub create_time: Option<DateTime>, //创建时间
     pub update_time: Option<DateTime>, //修改时间
 }
 
+//sqlite驱动会把json格式的文本解析成对象,这里统一转回json文本
+fn deserialize_json_text<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(text) => Ok(text),
+        serde_json::Value::Null => Ok(String::new()),
+        value => Ok(value.to_string()),
+    }
+}
+
 /*
  *字典数据表基本操作
  *author：刘飞华
@@ -47,14 +61,26 @@ impl From<DictDataReq> for DictData {
             is_default: item.is_default, //是否默认（Y是 N否）
             status: item.status,         //状态（0：停用，1:正常）
             remark: item.remark,         //备注
+            label_i18n: item.label_i18n, //字典标签的多语言翻译
             create_time: None,           //创建时间
             update_time: None,           //修改时间
         }
     }
 }
 
+impl DictData {
+    /// 指定语言的字典标签,没有该语言的翻译时使用dict_label
+    pub fn label(&self, locale: Locale) -> String {
+        serde_json::from_str::<HashMap<String, String>>(&self.label_i18n)
+            .ok()
+            .and_then(|labels| labels.into_iter().find(|(k, v)| !v.is_empty() && Locale::parse(k) == Some(locale)).map(|(_, v)| v))
+            .unwrap_or_else(|| self.dict_label.clone())
+    }
+}
+
 impl Into<DictDataResp> for DictData {
     fn into(self) -> DictDataResp {
+        let locale_label = self.label(current_locale());
         DictDataResp {
             id: self.id,                   //字典编码
             dict_sort: self.dict_sort,     //字典排序
@@ -66,6 +92,8 @@ impl Into<DictDataResp> for DictData {
             is_default: self.is_default,   //是否默认（Y是 N否）
             status: self.status,           //状态（0：停用，1:正常）
             remark: self.remark,           //备注
+            label_i18n: self.label_i18n,   //字典标签的多语言翻译
+            locale_label,                  //当前语言的字典标签
             create_time: self.create_time, //创建时间
             update_time: self.update_time, //修改时间
         }
@@ -98,7 +126,7 @@ impl_select!(DictData{select_by_dict_value(dict_type:&str, dict_value:&str) -> O
  *date：2024/12/25 10:01:11
  */
 impl_select_page!(DictData{select_page() =>"
-     if !sql.contains('count'):
+     if do_count == false:
        order by create_time desc"
 },"sys_dict_data");
 
@@ -115,7 +143,7 @@ impl_select_page!(DictData{select_dict_data_list(dict_label:&str, dict_type:&str
       ` and dict_type = #{dict_type} `
      if status != 2:
       ` and status = #{status} `
-     if !sql.contains('count'):
+     if do_count == false:
       ` order by create_time desc"
 },"sys_dict_data");
 
@@ -138,3 +166,19 @@ pub async fn update_dict_data_type(rb: &RBatis, new_dict_type: &str, old_dict_ty
 pub async fn count_dict_data_by_type(rb: &RBatis, dict_type: &str) -> rbatis::Result<i64> {
     impled!()
 }
+
+/*
+ *批量更新字典数据状态
+ *author：刘飞华
+ *date：2026/10/19 10:53:03
+ */
+#[py_sql(
+    "`update sys_dict_data set status = #{status} where id in (`
+     trim ',':
+       for _,item in ids:
+         `#{item},`
+     `)`"
+)]
+pub async fn update_dict_data_status(rb: &RBatis, ids: &[i64], status: i8) -> rbatis::Result<ExecResult> {
+    impled!()
+}
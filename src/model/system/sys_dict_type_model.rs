@@ -4,6 +4,8 @@
 
 use crate::vo::system::sys_dict_type_vo::{DictTypeReq, DictTypeResp};
 use rbatis::rbdc::datetime::DateTime;
+use rbatis::rbdc::db::ExecResult;
+use rbatis::RBatis;
 use serde::{Deserialize, Serialize};
 /*
  *字典类型
@@ -76,7 +78,7 @@ impl_select!(DictType{select_by_dict_type(dict_type:&str) -> Option => "`where d
  *date：2024/12/25 10:01:11
  */
 impl_select_page!(DictType{select_page() =>"
-     if !sql.contains('count'):
+     if do_count == false:
        order by create_time desc"
 },"sys_dict_type");
 
@@ -93,6 +95,22 @@ impl_select_page!(DictType{select_dict_type_list(dict_name:&str, dict_type:&str,
       ` and dict_type = #{dict_type} `
      if status != 2:
       ` and status = #{status} `
-     if !sql.contains('count'):
+     if do_count == false:
       ` order by create_time desc"
 },"sys_dict_type");
+
+/*
+ *批量更新字典类型状态
+ *author：刘飞华
+ *date：2026/10/19 10:52:02
+ */
+#[py_sql(
+    "`update sys_dict_type set status = #{status} where id in (`
+     trim ',':
+       for _,item in ids:
+         `#{item},`
+     `)`"
+)]
+pub async fn update_dict_type_status(rb: &RBatis, ids: &[i64], status: i8) -> rbatis::Result<ExecResult> {
+    impled!()
+}
@@ -2,8 +2,10 @@
 // author：刘飞华
 // createTime：2024/12/25 10:01:11
 
+use crate::utils::data_scope_util::DataScope;
 use crate::vo::system::sys_login_log_vo::LoginLogResp;
 use rbatis::rbdc::datetime::DateTime;
+use rbatis::rbdc::db::ExecResult;
 use rbatis::RBatis;
 use serde::{Deserialize, Serialize};
 /*
@@ -70,7 +72,7 @@ impl_select!(LoginLog{select_by_id(id:&i64) -> Option => "`where id = #{id} limi
  *date：2024/12/25 10:01:11
  */
 impl_select_page!(LoginLog{select_page() =>"
-     if !sql.contains('count'):
+     if do_count == false:
        order by login_time desc"
 },"sys_login_log");
 
@@ -79,7 +81,7 @@ impl_select_page!(LoginLog{select_page() =>"
  *author：刘飞华
  *date：2024/12/25 10:01:11
  */
-impl_select_page!(LoginLog{select_login_log_list(name:&str, ipaddr:&str,browser:&str,os:&str,status:&i8) =>"
+impl_select_page!(LoginLog{select_login_log_list(name:&str, ipaddr:&str,browser:&str,os:&str,status:&i8,scope:&DataScope) =>"
     where 1=1
      if name != '':
        ` and login_name = #{name} `
@@ -91,7 +93,12 @@ impl_select_page!(LoginLog{select_login_log_list(name:&str, ipaddr:&str,browser:
        ` and os = #{os} `
      if status != 2:
        ` and status = #{status} `
-     if !sql.contains('count'):
+     if scope.all == false:
+       ` and exists (select 1 from sys_user u where (u.mobile = login_name or u.user_name = login_name) and (u.id = #{scope.user_id} or u.dept_id in (-1`
+       for _,item in scope.dept_ids:
+         `,#{item}`
+       `))) `
+     if do_count == false:
        ` order by login_time desc `"
 },"sys_login_log");
 
@@ -100,7 +107,17 @@ impl_select_page!(LoginLog{select_login_log_list(name:&str, ipaddr:&str,browser:
  *author：刘飞华
  *date：2024/12/12 14:41:44
  */
-#[sql("truncate table sys_login_log")]
+#[sql("delete from sys_login_log")]
 pub async fn clean_login_log(rb: &RBatis) -> Option<i64> {
     impled!()
 }
+
+/*
+ *删除指定时间之前的登录日志
+ *author：刘飞华
+ *date：2026/10/19 09:12:40
+ */
+#[sql("delete from sys_login_log where login_time < ?")]
+pub async fn delete_login_log_before(rb: &RBatis, login_time: &DateTime) -> rbatis::Result<ExecResult> {
+    impled!()
+}
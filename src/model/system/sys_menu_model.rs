@@ -4,6 +4,7 @@
 use crate::rbatis::rbatis_codegen::IntoSql;
 use crate::vo::system::sys_menu_vo::{MenuReq, MenuResp};
 use rbatis::rbdc::datetime::DateTime;
+use rbatis::rbdc::db::ExecResult;
 use rbatis::RBatis;
 use serde::{Deserialize, Serialize};
 /*
@@ -96,6 +97,13 @@ impl_select!(Menu{select_by_menu_name(menu_name:&str) -> Option => "`where menu_
  */
 impl_select!(Menu{select_by_menu_url(menu_url:&str) -> Option => "`where menu_url = #{menu_url} limit 1`"}, "sys_menu");
 
+/*
+ *根据api_url查询菜单信息
+ *author：刘飞华
+ *date：2026/10/18 18:20:31
+ */
+impl_select!(Menu{select_by_api_url(api_url:&str) -> Option => "`where api_url = #{api_url} limit 1`"}, "sys_menu");
+
 /*
  *根据ids查询菜单信息
  *author：刘飞华
@@ -119,3 +127,19 @@ pub async fn select_count_menu_by_parent_id(rb: &RBatis, parent_id: &i64) -> rba
  *date：2025/01/04 22:24:01
  */
 impl_select!(Menu{select_menu_list() -> Vec => "`where menu_type != 3 and status = 1`"}, "sys_menu");
+
+/*
+ *批量更新菜单状态
+ *author：刘飞华
+ *date：2026/10/19 10:51:01
+ */
+#[py_sql(
+    "`update sys_menu set status = #{status} where id in (`
+     trim ',':
+       for _,item in ids:
+         `#{item},`
+     `)`"
+)]
+pub async fn update_menu_status(rb: &RBatis, ids: &[i64], status: i8) -> rbatis::Result<ExecResult> {
+    impled!()
+}
@@ -4,6 +4,8 @@
 
 use crate::vo::system::sys_notice_vo::{NoticeReq, NoticeResp};
 use rbatis::rbdc::datetime::DateTime;
+use rbatis::rbdc::db::ExecResult;
+use rbatis::RBatis;
 use serde::{Deserialize, Serialize};
 /*
  *通知公告表
@@ -90,6 +92,22 @@ impl_select_page!(Notice{select_sys_notice_list(title:Option<String>, notice_typ
       ` and notice_type = #{notice_type} `
      if status != 2 && status != null:
        ` and status = #{status} `
-     if !sql.contains('count'):
+     if do_count == false:
        ` order by create_time desc `"
 },"sys_notice");
+
+/*
+ *批量更新通知公告状态
+ *author：刘飞华
+ *date：2026/10/19 10:55:05
+ */
+#[py_sql(
+    "`update sys_notice set status = #{status} where id in (`
+     trim ',':
+       for _,item in ids:
+         `#{item},`
+     `)`"
+)]
+pub async fn update_notice_status(rb: &RBatis, ids: &[i64], status: i8) -> rbatis::Result<ExecResult> {
+    impled!()
+}
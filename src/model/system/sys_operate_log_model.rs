@@ -2,8 +2,10 @@
 // author：刘飞华
 // createTime：2024/12/25 10:01:11
 
+use crate::utils::data_scope_util::DataScope;
 use crate::vo::system::sys_operate_log_vo::OperateLogResp;
 use rbatis::rbdc::datetime::DateTime;
+use rbatis::rbdc::db::ExecResult;
 use rbatis::RBatis;
 use serde::{Deserialize, Serialize};
 /*
@@ -21,6 +23,7 @@ pub struct OperateLog {
     pub operator_type: Option<i8>,        //操作类别（0其它 1后台用户 2手机端用户）
     pub operate_name: Option<String>,     //操作人员
     pub dept_name: Option<String>,        //部门名称
+    pub impersonate_name: Option<String>, //模拟登录的管理员(管理员模拟该用户操作时记录)
     pub operate_url: Option<String>,      //请求URL
     pub operate_ip: Option<String>,       //主机地址
     pub operate_location: Option<String>, //操作地点
@@ -50,6 +53,7 @@ impl Into<OperateLogResp> for OperateLog {
             operator_type: self.operator_type,       //操作类别（0其它 1后台用户 2手机端用户）
             operate_name: self.operate_name,         //操作人员
             dept_name: self.dept_name,               //部门名称
+            impersonate_name: self.impersonate_name, //模拟登录的管理员
             operate_url: self.operate_url,           //请求URL
             operate_ip: self.operate_ip,             //主机地址
             operate_location: self.operate_location, //操作地点
@@ -75,7 +79,7 @@ impl_select!(OperateLog{select_by_id(id:&i64) -> Option => "`where id = #{id} li
  *date：2024/12/25 10:01:11
  */
 impl_select_page!(OperateLog{select_page() =>"
-     if !sql.contains('count'):
+     if do_count == false:
        order by operate_time desc"
 },"sys_operate_log");
 
@@ -94,7 +98,8 @@ impl_select_page!(OperateLog{select_page_by_name(
     dept_name:&str,
     operate_url:&str,
     operate_ip:&str,
-    status:&i8,) =>"
+    status:&i8,
+    scope:&DataScope,) =>"
     where 1=1
      if title != '':
        ` and title = #{title} `
@@ -116,7 +121,12 @@ impl_select_page!(OperateLog{select_page_by_name(
        ` and operate_ip = #{operate_ip} `
      if status != 2:
        ` and status = #{status} `
-     if !sql.contains('count'):
+     if scope.all == false:
+       ` and operate_name in (select u.user_name from sys_user u where u.id = #{scope.user_id} or u.dept_id in (-1`
+       for _,item in scope.dept_ids:
+         `,#{item}`
+       `)) `
+     if do_count == false:
        ` order by operate_time desc `"
 },"sys_operate_log");
 
@@ -125,7 +135,17 @@ impl_select_page!(OperateLog{select_page_by_name(
  *author：刘飞华
  *date：2024/12/12 14:41:44
  */
-#[sql("truncate table sys_operate_log")]
+#[sql("delete from sys_operate_log")]
 pub async fn clean_operate_log(rb: &RBatis) -> Option<i64> {
     impled!()
 }
+
+/*
+ *删除指定时间之前的操作日志
+ *author：刘飞华
+ *date：2026/10/19 09:12:40
+ */
+#[sql("delete from sys_operate_log where operate_time < ?")]
+pub async fn delete_operate_log_before(rb: &RBatis, operate_time: &DateTime) -> rbatis::Result<ExecResult> {
+    impled!()
+}
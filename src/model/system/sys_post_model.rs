@@ -4,6 +4,8 @@
 
 use crate::vo::system::sys_post_vo::{PostReq, PostResp};
 use rbatis::rbdc::datetime::DateTime;
+use rbatis::rbdc::db::ExecResult;
+use rbatis::RBatis;
 use serde::{Deserialize, Serialize};
 /*
  *岗位信息
@@ -86,7 +88,7 @@ impl_select!(Post{select_by_name(post_name:&str) -> Option => "`where post_name
  *date：2024/12/25 10:01:11
  */
 impl_select_page!(Post{select_page() =>"
-     if !sql.contains('count'):
+     if do_count == false:
        order by create_time desc"
 },"sys_post");
 
@@ -103,6 +105,22 @@ impl_select_page!(Post{select_post_list(post_code:&str, post_name:&str, status:i
       ` and post_name = #{post_name} `
      if status != 2:
       ` and status = #{status} `
-     if !sql.contains('count'):
+     if do_count == false:
       ` order by create_time desc"
 },"sys_post");
+
+/*
+ *批量更新岗位状态
+ *author：刘飞华
+ *date：2026/10/19 10:50:00
+ */
+#[py_sql(
+    "`update sys_post set status = #{status} where id in (`
+     trim ',':
+       for _,item in ids:
+         `#{item},`
+     `)`"
+)]
+pub async fn update_post_status(rb: &RBatis, ids: &[i64], status: i8) -> rbatis::Result<ExecResult> {
+    impled!()
+}
@@ -1,8 +1,12 @@
 // author：刘飞华
 // createTime：2024/12/12 14:41:44
 
+use crate::rbatis::rbatis_codegen::IntoSql;
 use crate::vo::system::sys_role_vo::{RoleReq, RoleResp};
+use rbatis::executor::Executor;
 use rbatis::rbdc::datetime::DateTime;
+use rbatis::rbdc::db::ExecResult;
+use rbatis::RBatis;
 use serde::{Deserialize, Serialize};
 /*
  *角色信息
@@ -14,7 +18,7 @@ pub struct Role {
     pub id: Option<i64>,               //主键
     pub role_name: String,             //名称
     pub role_key: String,              //角色权限字符串
-    pub data_scope: i8,                //数据范围（1：全部数据权限 2：自定数据权限 3：本部门数据权限 4：本部门及以下数据权限）
+    pub data_scope: i8,                //数据范围（1：全部数据权限 2：自定数据权限 3：本部门数据权限 4：本部门及以下数据权限 5：仅本人数据权限）
     pub status: i8,                    //状态(1:正常，0:禁用)
     pub remark: Option<String>,        //备注
     pub del_flag: Option<i8>,          //删除标志（0代表删除 1代表存在）
@@ -35,7 +39,7 @@ impl From<RoleReq> for Role {
             id: item.id,                 //主键
             role_name: item.role_name,   //名称
             role_key: item.role_key,     //角色权限字符串
-            data_scope: item.data_scope, //数据范围（1：全部数据权限 2：自定数据权限 3：本部门数据权限 4：本部门及以下数据权限）
+            data_scope: item.data_scope, //数据范围（1：全部数据权限 2：自定数据权限 3：本部门数据权限 4：本部门及以下数据权限 5：仅本人数据权限）
             status: item.status,         //状态(1:正常，0:禁用)
             remark: item.remark,         //备注
             del_flag: None,              //删除标志（0代表删除 1代表存在）
@@ -51,7 +55,7 @@ impl Into<RoleResp> for Role {
             id: self.id,                   //主键
             role_name: self.role_name,     //名称
             role_key: self.role_key,       //角色权限字符串
-            data_scope: self.data_scope,   //数据范围（1：全部数据权限 2：自定数据权限 3：本部门数据权限 4：本部门及以下数据权限）
+            data_scope: self.data_scope,   //数据范围（1：全部数据权限 2：自定数据权限 3：本部门数据权限 4：本部门及以下数据权限 5：仅本人数据权限）
             status: self.status,           //状态(1:正常，0:禁用)
             remark: self.remark,           //备注
             create_time: self.create_time, //创建时间
@@ -65,21 +69,21 @@ impl Into<RoleResp> for Role {
  *author：刘飞华
  *date：2024/12/12 14:41:44
  */
-impl_select!(Role{select_by_id(id:&i64) -> Option => "`where id = #{id} limit 1`"}, "sys_role");
+impl_select!(Role{select_by_id(id:&i64) -> Option => "`where id = #{id} and del_flag = 1 limit 1`"}, "sys_role");
 
 /*
  *根据role_name查询角色信息
  *author：刘飞华
  *date：2024/12/12 14:41:44
  */
-impl_select!(Role{select_by_role_name(role_name:&str) -> Option => "`where role_name = #{role_name} limit 1`"}, "sys_role");
+impl_select!(Role{select_by_role_name(role_name:&str) -> Option => "`where role_name = #{role_name} and del_flag = 1 limit 1`"}, "sys_role");
 
 /*
  *根据role_key查询角色信息
  *author：刘飞华
  *date：2024/12/12 14:41:44
  */
-impl_select!(Role{select_by_role_key(role_key:&str) -> Option => "`where role_key = #{role_key} limit 1`"}, "sys_role");
+impl_select!(Role{select_by_role_key(role_key:&str) -> Option => "`where role_key = #{role_key} and del_flag = 1 limit 1`"}, "sys_role");
 
 /*
  *分页查询角色信息
@@ -87,7 +91,8 @@ impl_select!(Role{select_by_role_key(role_key:&str) -> Option => "`where role_ke
  *date：2024/12/12 14:41:44
  */
 impl_select_page!(Role{select_page() =>"
-     if !sql.contains('count'):
+     ` where del_flag = 1 `
+     if do_count == false:
        order by create_time desc"
 },"sys_role");
 
@@ -97,12 +102,90 @@ impl_select_page!(Role{select_page() =>"
  *date：2024/12/12 14:41:44
  */
 impl_select_page!(Role{select_sys_role_list(role_name:&str,role_key:&str,status:i8) =>"
-      where 1=1
+      where del_flag = 1
      if role_name != null && role_name != '':
        ` and role_name = #{role_name} `
      if role_key != null && role_key != '':
        ` and role_key = #{role_key} `
      if status != 2:
        ` and status = #{status} `
-     if !sql.contains('count'):
+     if do_count == false:
         ` order by create_time desc `"},"sys_role");
+
+/*
+ *查询用户拥有的正常状态的角色
+ *author：刘飞华
+ *date：2026/10/18 17:42:10
+ */
+#[sql("select r.* from sys_role r inner join sys_user_role ur on r.id = ur.role_id where ur.user_id = ? and r.status = 1 and r.del_flag = 1")]
+pub async fn select_role_by_user_id(rb: &RBatis, user_id: &i64) -> rbatis::Result<Vec<Role>> {
+    impled!()
+}
+
+/*
+ *更新角色的数据范围
+ *author：刘飞华
+ *date：2026/10/18 17:42:10
+ */
+#[sql("update sys_role set data_scope = ?, update_time = ? where id = ?")]
+pub async fn update_data_scope(rb: &dyn Executor, data_scope: &i8, update_time: &DateTime, id: &i64) -> rbatis::Result<ExecResult> {
+    impled!()
+}
+
+/*
+ *批量更新角色状态
+ *author：刘飞华
+ *date：2026/10/19 10:54:04
+ */
+#[py_sql(
+    "`update sys_role set status = #{status} where id in (`
+     trim ',':
+       for _,item in ids:
+         `#{item},`
+     `)`"
+)]
+pub async fn update_role_status(rb: &RBatis, ids: &[i64], status: i8) -> rbatis::Result<ExecResult> {
+    impled!()
+}
+
+/*
+ *查询全部未删除的角色
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+impl_select!(Role{select_all_not_deleted() => "`where del_flag = 1`"}, "sys_role");
+
+/*
+ *分页查询已删除的角色(回收站),按删除时间倒序
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+impl_select_page!(Role{select_deleted_role_list(role_name:&str) =>"
+      where del_flag = 0
+     if role_name != null && role_name != '':
+       ` and role_name = #{role_name} `
+     if do_count == false:
+        ` order by update_time desc `"},"sys_role");
+
+/*
+ *根据ids查询已删除的角色
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+impl_select!(Role{select_deleted_by_ids(ids:&[i64]) -> Vec => "`where del_flag = 0 and id in ${ids.sql()}`"}, "sys_role");
+
+/*
+ *批量更新角色的删除标志(删除: 0, 恢复: 1)
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+#[py_sql(
+    "`update sys_role set del_flag = #{del_flag}, update_time = #{update_time} where id in (`
+     trim ',':
+       for _,item in ids:
+         `#{item},`
+     `)`"
+)]
+pub async fn update_role_del_flag(rb: &dyn Executor, ids: &[i64], del_flag: i8, update_time: &DateTime) -> rbatis::Result<ExecResult> {
+    impled!()
+}
@@ -0,0 +1,24 @@
+// author：刘飞华
+// createTime：2026/10/19 00:31:18
+
+use rbatis::rbdc::datetime::DateTime;
+use serde::{Deserialize, Serialize};
+/*
+ *已执行的数据库迁移
+ *author：刘飞华
+ *date：2026/10/19 00:31:18
+ */
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SchemaMigration {
+    pub version: i64,                   //迁移版本号
+    pub name: String,                   //迁移名称
+    pub checksum: String,               //迁移脚本的sha256,用于发现已执行的脚本被修改
+    pub applied_time: Option<DateTime>, //执行时间
+}
+
+/*
+ *已执行的数据库迁移基本操作
+ *author：刘飞华
+ *date：2026/10/19 00:31:18
+ */
+rbatis::crud!(SchemaMigration {}, "schema_migrations");
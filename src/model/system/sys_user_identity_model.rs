@@ -0,0 +1,34 @@
+// author：刘飞华
+// createTime：2026/10/18 21:25:36
+
+use rbatis::rbdc::datetime::DateTime;
+use serde::{Deserialize, Serialize};
+/*
+ *用户第三方身份(单点登录绑定)
+ *author：刘飞华
+ *date：2026/10/18 21:25:36
+ */
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UserIdentity {
+    pub id: Option<i64>,               //主键
+    pub user_id: i64,                  //用户ID
+    pub issuer: String,                //身份提供方(ID token的iss)
+    pub subject: String,               //用户在身份提供方的唯一标识(ID token的sub)
+    pub email: String,                 //绑定时的邮箱
+    pub create_time: Option<DateTime>, //创建时间
+    pub update_time: Option<DateTime>, //修改时间
+}
+
+/*
+ *用户第三方身份基本操作
+ *author：刘飞华
+ *date：2026/10/18 21:25:36
+ */
+rbatis::crud!(UserIdentity {}, "sys_user_identity");
+
+/*
+ *根据身份提供方和唯一标识查询绑定的用户
+ *author：刘飞华
+ *date：2026/10/18 21:25:36
+ */
+impl_select!(UserIdentity{select_by_subject(issuer:&str, subject:&str) -> Option => "`where issuer = #{issuer} and subject = #{subject} limit 1`"}, "sys_user_identity");
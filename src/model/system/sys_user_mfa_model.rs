@@ -0,0 +1,67 @@
+// author：刘飞华
+// createTime：2026/10/18 20:03:44
+
+use rbatis::rbdc::datetime::DateTime;
+use rbatis::rbdc::db::ExecResult;
+use rbatis::RBatis;
+use serde::{Deserialize, Serialize};
+/*
+ *用户两步验证
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UserMfa {
+    pub id: Option<i64>,               //主键
+    pub user_id: i64,                  //用户ID
+    pub secret: String,                //TOTP密钥(base32)
+    pub status: i8,                    //状态(0:待确认,1:已启用)
+    pub recovery_codes: String,        //恢复码哈希(多个用逗号分隔,使用后删除)
+    pub last_used_step: i64,           //最后使用的动态码时间步(防止动态码重放)
+    pub create_time: Option<DateTime>, //创建时间
+    pub update_time: Option<DateTime>, //修改时间
+}
+
+/*
+ *用户两步验证基本操作
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+rbatis::crud!(UserMfa {}, "sys_user_mfa");
+
+/*
+ *根据用户id查询两步验证
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+impl_select!(UserMfa{select_by_user_id(user_id:i64) -> Option => "`where user_id = #{user_id} limit 1`"}, "sys_user_mfa");
+
+/*
+ *记录最后使用的动态码时间步(只允许时间步递增,并发使用同一个动态码时只有一个能成功)
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+#[sql("update sys_user_mfa set last_used_step = ?, update_time = ? where user_id = ? and last_used_step < ?")]
+pub async fn update_last_used_step(rb: &RBatis, last_used_step: i64, update_time: &DateTime, user_id: i64, step: i64) -> rbatis::Result<ExecResult> {
+    impled!()
+}
+
+/*
+ *更新剩余的恢复码(只有恢复码没有被并发修改时才能更新成功)
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+#[sql("update sys_user_mfa set recovery_codes = ?, update_time = ? where user_id = ? and recovery_codes = ?")]
+pub async fn update_recovery_codes(rb: &RBatis, recovery_codes: &str, update_time: &DateTime, user_id: i64, old_recovery_codes: &str) -> rbatis::Result<ExecResult> {
+    impled!()
+}
+
+/*
+ *更新两步验证状态
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+#[sql("update sys_user_mfa set status = ?, update_time = ? where user_id = ?")]
+pub async fn update_mfa_status(rb: &RBatis, status: i8, update_time: &DateTime, user_id: i64) -> rbatis::Result<ExecResult> {
+    impled!()
+}
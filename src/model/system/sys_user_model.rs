@@ -1,10 +1,14 @@
 // author：刘飞华
 // createTime：2024/12/12 14:41:44
 
+use crate::rbatis::rbatis_codegen::IntoSql;
+use crate::utils::data_scope_util::DataScope;
 use crate::vo::system::sys_user_vo::{UserReq, UserResp};
 use rbatis::executor::Executor;
 use rbatis::rbdc::datetime::DateTime;
+use rbatis::rbdc::db::ExecResult;
 use rbatis::rbdc::Error;
+use rbatis::RBatis;
 use serde::{Deserialize, Serialize};
 /*
  *用户信息
@@ -21,6 +25,7 @@ pub struct User {
     pub email: String,                     //用户邮箱
     pub avatar: Option<String>,            //头像路径
     pub password: String,                  //密码
+    pub auth_source: String,               //认证方式(local:本地密码,ldap:LDAP,oidc:单点登录),为空时使用配置的认证链
     pub status: i8,                        //状态(1:正常，0:禁用)
     pub dept_id: i64,                      //部门ID
     pub login_ip: String,                  //最后登录IP
@@ -29,6 +34,7 @@ pub struct User {
     pub login_os: String,                  //操作系统
     pub pwd_update_date: Option<DateTime>, //密码最后更新时间
     pub remark: Option<String>,            //备注
+    pub locale: String,                    //语言偏好(zh-CN, en-US),为空时根据请求头Accept-Language选择语言
     pub del_flag: i8,                      //删除标志（0代表删除 1代表存在）
     pub create_time: Option<DateTime>,     //创建时间
     pub update_time: Option<DateTime>,     //修改时间
@@ -52,6 +58,7 @@ impl From<UserReq> for User {
             email: item.email,                           //用户邮箱
             avatar: item.avatar,                         //头像路径
             password: item.password.unwrap_or_default(), //密码
+            auth_source: item.auth_source,               //认证方式(local:本地密码,ldap:LDAP,oidc:单点登录),为空时使用配置的认证链
             status: item.status,                         //状态(1:正常，0:禁用)
             dept_id: item.dept_id,                       //部门ID
             login_ip: "".to_string(),                    //最后登录IP
@@ -60,6 +67,7 @@ impl From<UserReq> for User {
             login_os: "".to_string(),                    //操作系统
             pwd_update_date: None,                       //密码最后更新时间
             remark: item.remark,                         //备注
+            locale: item.locale.unwrap_or_default(),     //语言偏好
             del_flag: 1,                                 //删除标志（0代表删除 1代表存在）
             create_time: None,                           //创建时间
             update_time: None,                           //修改时间
@@ -77,6 +85,7 @@ impl Into<UserResp> for User {
             user_type: self.user_type.unwrap_or_default(), //用户类型（00系统用户）
             email: self.email,                             //用户邮箱
             avatar: self.avatar,                           //头像路径
+            auth_source: self.auth_source,                 //认证方式(local:本地密码,ldap:LDAP,oidc:单点登录),为空时使用配置的认证链
             status: self.status,                           //状态(1:正常，0:禁用)
             dept_id: self.dept_id,                         //部门ID
             login_ip: self.login_ip,                       //最后登录IP
@@ -85,6 +94,7 @@ impl Into<UserResp> for User {
             login_os: self.login_os,                       //操作系统
             pwd_update_date: self.pwd_update_date,         //密码最后更新时间
             remark: self.remark,                           //备注
+            locale: self.locale,                           //语言偏好
             create_time: self.create_time,                 //创建时间
             update_time: self.update_time,                 //修改时间
             dept_info: None,
@@ -97,28 +107,28 @@ impl Into<UserResp> for User {
  *author：刘飞华
  *date：2024/12/12 14:41:44
  */
-impl_select!(User{select_by_id(id:i64) -> Option => "`where id = #{id} limit 1`"}, "sys_user");
+impl_select!(User{select_by_id(id:i64) -> Option => "`where id = #{id} and del_flag = 1 limit 1`"}, "sys_user");
 
 /*
  *根据mobile查询用户信息
  *author：刘飞华
  *date：2024/12/12 14:41:44
  */
-impl_select!(User{select_by_mobile(mobile:&str) -> Option => "`where mobile = #{mobile} limit 1`"},"sys_user");
+impl_select!(User{select_by_mobile(mobile:&str) -> Option => "`where mobile = #{mobile} and del_flag = 1 limit 1`"},"sys_user");
 
 /*
  *根据user_name查询用户信息
  *author：刘飞华
  *date：2024/12/12 14:41:44
  */
-impl_select!(User{select_by_user_name(user_name:&str) -> Option => "`where user_name = #{user_name} limit 1`"}, "sys_user");
+impl_select!(User{select_by_user_name(user_name:&str) -> Option => "`where user_name = #{user_name} and del_flag = 1 limit 1`"}, "sys_user");
 
 /*
  *根据email查询用户信息
  *author：刘飞华
  *date：2024/12/12 14:41:44
  */
-impl_select!(User{select_by_email(email:&str) -> Option => "`where email = #{email} limit 1`"}, "sys_user");
+impl_select!(User{select_by_email(email:&str) -> Option => "`where email = #{email} and del_flag = 1 limit 1`"}, "sys_user");
 
 /*
  *分页查询用户信息
@@ -126,7 +136,8 @@ impl_select!(User{select_by_email(email:&str) -> Option => "`where email = #{ema
  *date：2024/12/12 14:41:44
  */
 impl_select_page!(User{select_page() =>"
-     if !sql.contains('count'):
+     ` where del_flag = 1 `
+     if do_count == false:
        order by create_time desc"
 },"sys_user");
 
@@ -135,17 +146,26 @@ impl_select_page!(User{select_page() =>"
  *author：刘飞华
  *date：2024/12/12 14:41:44
  */
-impl_select_page!(User{select_sys_user_list(mobile:&str,user_name:&str,status:i8,dept_id:i64) =>"
-      where 1=1
+impl_select_page!(User{select_sys_user_list(mobile:&str,user_name:&str,status:i8,dept_ids:&[i64],scope:&DataScope) =>"
+      where del_flag = 1
       if mobile != null && mobile != '':
        ` and mobile = #{mobile} `
      if user_name != null && user_name != '':
        ` and user_name = #{user_name} `
      if status != 2:
        ` and status = #{status} `
-     if dept_id != 0:
-       ` and (dept_id = #{dept_id} OR dept_id IN (SELECT id FROM sys_dept WHERE find_in_set(#{dept_id}, ancestors))) `
-     if !sql.contains('count'):
+     if !dept_ids.is_empty():
+       ` and dept_id in (`
+       trim ',':
+         for _,item in dept_ids:
+           `#{item},`
+       `) `
+     if scope.all == false:
+       ` and (id = #{scope.user_id} or dept_id in (-1`
+       for _,item in scope.dept_ids:
+         `,#{item}`
+       `)) `
+     if do_count == false:
         ` order by create_time desc `"},"sys_user");
 
 /*
@@ -159,7 +179,7 @@ impl_select_page!(User{select_sys_user_list(mobile:&str,user_name:&str,status:i8
                 ` and u.mobile = #{mobile} `
             if user_name != '':
                 ` and u.user_name = #{user_name} `
-            limit #{page_no},#{page_size}` "
+            limit #{page_size} offset #{page_no}` "
 )]
 async fn select_allocated_list(rb: &dyn Executor, role_id: i64, user_name: &str, mobile: &str, page_no: u64, page_size: u64) -> Result<Vec<User>, Error> {
     impled!()
@@ -192,7 +212,7 @@ async fn count_allocated_list(rb: &dyn Executor, role_id: i64, user_name: &str,
                 ` and u.mobile = #{mobile} `
             if user_name != '':
                 ` and u.user_name = #{user_name} `
-            limit #{page_no},#{page_size}` "
+            limit #{page_size} offset #{page_no}` "
 )]
 pub async fn select_unallocated_list(rb: &dyn Executor, role_id: i64, user_name: &str, mobile: &str, page_no: u64, page_size: u64) -> rbatis::Result<Vec<User>> {
     impled!()
@@ -213,3 +233,64 @@ pub async fn select_unallocated_list(rb: &dyn Executor, role_id: i64, user_name:
 pub async fn count_unallocated_list(rb: &dyn Executor, role_id: i64, user_name: &str, mobile: &str) -> rbatis::Result<u64> {
     impled!()
 }
+
+/*
+ *批量更新用户状态
+ *author：刘飞华
+ *date：2026/10/19 10:56:06
+ */
+#[py_sql(
+    "`update sys_user set status = #{status} where id in (`
+     trim ',':
+       for _,item in ids:
+         `#{item},`
+     `)`"
+)]
+pub async fn update_user_status(rb: &RBatis, ids: &[i64], status: i8) -> rbatis::Result<ExecResult> {
+    impled!()
+}
+
+/*
+ *更新用户的语言偏好
+ *author：刘飞华
+ *date：2026/10/19 16:10:42
+ */
+#[py_sql("`update sys_user set locale = #{locale} where id = #{id}`")]
+pub async fn update_user_locale(rb: &RBatis, id: i64, locale: &str) -> rbatis::Result<ExecResult> {
+    impled!()
+}
+
+/*
+ *分页查询已删除的用户(回收站),按删除时间倒序
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+impl_select_page!(User{select_deleted_user_list(user_name:&str) =>"
+      where del_flag = 0
+     if user_name != null && user_name != '':
+       ` and user_name = #{user_name} `
+     if do_count == false:
+        ` order by update_time desc `"},"sys_user");
+
+/*
+ *根据ids查询已删除的用户
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+impl_select!(User{select_deleted_by_ids(ids:&[i64]) -> Vec => "`where del_flag = 0 and id in ${ids.sql()}`"}, "sys_user");
+
+/*
+ *批量更新用户的删除标志(删除: 0, 恢复: 1)
+ *author：刘飞华
+ *date：2026/10/19 18:05:21
+ */
+#[py_sql(
+    "`update sys_user set del_flag = #{del_flag}, update_time = #{update_time} where id in (`
+     trim ',':
+       for _,item in ids:
+         `#{item},`
+     `)`"
+)]
+pub async fn update_user_del_flag(rb: &dyn Executor, ids: &[i64], del_flag: i8, update_time: &DateTime) -> rbatis::Result<ExecResult> {
+    impled!()
+}
@@ -0,0 +1,43 @@
+// author：刘飞华
+// createTime：2026/10/18 22:06:52
+
+use rbatis::executor::Executor;
+use rbatis::rbdc::datetime::DateTime;
+use rbatis::rbdc::db::ExecResult;
+use serde::{Deserialize, Serialize};
+/*
+ *用户历史密码
+ *author：刘飞华
+ *date：2026/10/18 22:06:52
+ */
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UserPasswordHistory {
+    pub id: Option<i64>,               //主键
+    pub user_id: i64,                  //用户ID
+    pub password: String,              //密码哈希
+    pub create_time: Option<DateTime>, //创建时间
+}
+
+/*
+ *用户历史密码基本操作
+ *author：刘飞华
+ *date：2026/10/18 22:06:52
+ */
+rbatis::crud!(UserPasswordHistory {}, "sys_user_password_history");
+
+/*
+ *查询用户最近使用过的密码
+ *author：刘飞华
+ *date：2026/10/18 22:06:52
+ */
+impl_select!(UserPasswordHistory{select_recent(user_id:i64, limit:u64) => "`where user_id = #{user_id} order by id desc limit #{limit}`"}, "sys_user_password_history");
+
+/*
+ *删除用户较早的历史密码(只保留id大于等于min_id的记录)
+ *author：刘飞华
+ *date：2026/10/18 22:06:52
+ */
+#[sql("delete from sys_user_password_history where user_id = ? and id < ?")]
+pub async fn delete_before(rb: &dyn Executor, user_id: i64, min_id: i64) -> rbatis::Result<ExecResult> {
+    impled!()
+}
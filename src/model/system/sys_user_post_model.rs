@@ -36,7 +36,7 @@ impl_select!(UserPost{select_by_id(id:&i64) -> Option => "`where id = #{id} limi
  *date：2024/12/25 10:01:11
  */
 impl_select_page!(UserPost{select_page() =>"
-     if !sql.contains('count'):
+     if do_count == false:
        order by create_time desc"
 },"sys_user_post");
 
@@ -1,7 +1,9 @@
 // author：刘飞华
 // createTime：2024/12/12 14:41:44
 
+use rbatis::executor::Executor;
 use rbatis::rbdc::datetime::DateTime;
+use rbatis::rbdc::db::ExecResult;
 use rbatis::RBatis;
 use serde::{Deserialize, Serialize};
 
@@ -40,7 +42,7 @@ pub async fn is_admin(rb: &RBatis, user_id: &i64) -> rbatis::Result<i64> {
  *author：刘飞华
  *date：2024/12/12 14:41:44
  */
-#[sql("select count(1) from sys_user_role where role_id = ?")]
+#[sql("select count(1) from sys_user_role ur inner join sys_user u on ur.user_id = u.id where ur.role_id = ? and u.del_flag = 1")]
 pub async fn count_user_role_by_role_id(rb: &RBatis, role_id: i64) -> rbatis::Result<i64> {
     impled!()
 }
@@ -54,3 +56,19 @@ pub async fn count_user_role_by_role_id(rb: &RBatis, role_id: i64) -> rbatis::Re
 pub async fn delete_user_role_by_role_id_user_id(rb: &RBatis, role_id: i64, user_id: i64) -> Option<i64> {
     impled!()
 }
+
+/*
+ *批量取消用户的角色
+ *author：刘飞华
+ *date：2026/10/19 10:58:40
+ */
+#[py_sql(
+    "`delete from sys_user_role where role_id = #{role_id} and user_id in (`
+     trim ',':
+       for _,item in user_ids:
+         `#{item},`
+     `)`"
+)]
+pub async fn delete_user_roles(rb: &dyn Executor, role_id: i64, user_ids: &[i64]) -> rbatis::Result<ExecResult> {
+    impled!()
+}
@@ -0,0 +1,134 @@
+// author：刘飞华
+// createTime：2026/10/18 15:31:20
+
+use crate::vo::system::sys_online_vo::OnlineResp;
+use rbatis::executor::Executor;
+use rbatis::rbdc::datetime::DateTime;
+use rbatis::rbdc::db::ExecResult;
+use rbatis::RBatis;
+use serde::{Deserialize, Serialize};
+/*
+ *在线用户会话
+ *author：刘飞华
+ *date：2026/10/18 15:31:20
+ */
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UserSession {
+    pub id: Option<i64>,                    //主键
+    pub session_id: String,                 //会话编号
+    pub user_id: i64,                       //用户ID
+    pub user_name: String,                  //用户账号
+    pub ipaddr: String,                     //登录IP地址
+    pub login_location: String,             //登录地点
+    pub browser: String,                    //浏览器类型
+    pub os: String,                         //操作系统
+    pub status: i8,                         //会话状态(0:已下线,1:在线)
+    pub login_time: Option<DateTime>,       //登录时间
+    pub last_access_time: Option<DateTime>, //最后访问时间
+    pub expire_time: Option<DateTime>,      //过期时间
+    pub refresh_jti: String,                //当前有效的refresh token编号(每次刷新后轮换)
+}
+
+/*
+ *在线用户会话基本操作
+ *author：刘飞华
+ *date：2026/10/18 15:31:20
+ */
+rbatis::crud!(UserSession {}, "sys_user_session");
+
+impl From<UserSession> for OnlineResp {
+    fn from(x: UserSession) -> Self {
+        OnlineResp {
+            id: x.id,                             //主键
+            session_id: x.session_id,             //会话编号
+            user_id: x.user_id,                   //用户ID
+            user_name: x.user_name,               //用户账号
+            ipaddr: x.ipaddr,                     //登录IP地址
+            login_location: x.login_location,     //登录地点
+            browser: x.browser,                   //浏览器类型
+            os: x.os,                             //操作系统
+            status: x.status,                     //会话状态(0:已下线,1:在线)
+            login_time: x.login_time,             //登录时间
+            last_access_time: x.last_access_time, //最后访问时间
+            expire_time: x.expire_time,           //过期时间
+        }
+    }
+}
+
+/*
+ *根据会话编号查询在线用户会话
+ *author：刘飞华
+ *date：2026/10/18 15:31:20
+ */
+impl_select!(UserSession{select_by_session_id(session_id:&str) -> Option => "`where session_id = #{session_id} limit 1`"}, "sys_user_session");
+
+/*
+ *根据条件分页查询在线用户
+ *author：刘飞华
+ *date：2026/10/18 15:31:20
+ */
+impl_select_page!(UserSession{select_online_list(user_name:&str, ipaddr:&str, now:&DateTime) =>"
+    where status = 1 and expire_time > #{now}
+     if user_name != '':
+       ` and user_name = #{user_name} `
+     if ipaddr != '':
+       ` and ipaddr = #{ipaddr} `
+     if do_count == false:
+       ` order by last_access_time desc `"
+},"sys_user_session");
+
+/*
+ *更新会话的最后访问时间
+ *author：刘飞华
+ *date：2026/10/18 15:31:20
+ */
+#[sql("update sys_user_session set last_access_time = ? where session_id = ?")]
+pub async fn update_last_access_time(rb: &RBatis, last_access_time: &DateTime, session_id: &str) -> rbatis::Result<ExecResult> {
+    impled!()
+}
+
+/*
+ *轮换会话的refresh token: 只有会话在线并且refresh_jti是当前有效的编号时才更新,影响行数为0表示refresh token已经使用过或者会话已下线
+ *author：刘飞华
+ *date：2026/10/19 21:05:44
+ */
+#[sql("update sys_user_session set refresh_jti = ?, last_access_time = ?, expire_time = ? where session_id = ? and refresh_jti = ? and status = 1")]
+pub async fn rotate_refresh_jti(rb: &RBatis, refresh_jti: &str, last_access_time: &DateTime, expire_time: &DateTime, session_id: &str, old_refresh_jti: &str) -> rbatis::Result<ExecResult> {
+    impled!()
+}
+
+/*
+ *下线会话
+ *author：刘飞华
+ *date：2026/10/18 15:31:20
+ */
+#[sql("update sys_user_session set status = 0 where session_id = ?")]
+pub async fn update_session_offline(rb: &dyn Executor, session_id: &str) -> rbatis::Result<ExecResult> {
+    impled!()
+}
+
+/*
+ *下线用户的全部会话
+ *author：刘飞华
+ *date：2026/10/18 15:31:20
+ */
+#[sql("update sys_user_session set status = 0 where user_id = ? and status = 1")]
+pub async fn update_user_session_offline(rb: &dyn Executor, user_id: i64) -> rbatis::Result<ExecResult> {
+    impled!()
+}
+
+/*
+ *批量下线会话
+ *author：刘飞华
+ *date：2026/10/19 10:58:30
+ */
+#[py_sql(
+    "`update sys_user_session set status = 0 where session_id in (`
+     trim ',':
+       for _,item in session_ids:
+         `#{item},`
+     `)`"
+)]
+pub async fn update_sessions_offline(rb: &RBatis, session_ids: &[String]) -> rbatis::Result<ExecResult> {
+    impled!()
+}
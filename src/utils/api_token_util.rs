@@ -0,0 +1,80 @@
+use rand::Rng;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+/*
+ *API令牌配置
+ *author：刘飞华
+ *date：2026/10/18 22:48:15
+ */
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ApiTokenConfig {
+    pub enabled: bool,           //是否允许使用API令牌访问接口
+    pub max_expire_days: u64,    //令牌最长有效期(天),0表示允许永久有效
+    pub max_tokens: u64,         //每个用户最多可以创建的有效令牌数量
+    pub last_used_interval: u64, //记录最后使用时间的间隔(秒),避免每次请求都更新数据库
+}
+
+impl Default for ApiTokenConfig {
+    fn default() -> Self {
+        ApiTokenConfig {
+            enabled: true,
+            max_expire_days: 365,
+            max_tokens: 20,
+            last_used_interval: 60,
+        }
+    }
+}
+
+//令牌前缀,用于和jwt区分,也方便在代码仓库和日志中识别泄露的令牌
+pub const API_TOKEN_PREFIX: &str = "rat_";
+//令牌编号长度(令牌编号明文保存,用于查找令牌)
+const TOKEN_ID_LEN: usize = 8;
+//令牌密钥长度
+const TOKEN_SECRET_LEN: usize = 32;
+const TOKEN_CHARS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// 生成API令牌,返回(令牌明文, 令牌标识, 令牌哈希),令牌格式为: rat_{编号}_{密钥}
+/// 令牌明文只在创建时返回一次,数据库中只保存令牌标识(rat_{编号})和哈希
+pub fn generate_api_token() -> (String, String, String) {
+    let mut rng = rand::thread_rng();
+    let mut random = |len: usize| (0..len).map(|_| TOKEN_CHARS[rng.gen_range(0..TOKEN_CHARS.len())] as char).collect::<String>();
+    let token_prefix = format!("{}{}", API_TOKEN_PREFIX, random(TOKEN_ID_LEN));
+    let token = format!("{}_{}", token_prefix, random(TOKEN_SECRET_LEN));
+    let token_hash = hash_api_token(&token);
+    (token, token_prefix, token_hash)
+}
+
+/// 解析令牌标识(rat_{编号}),格式不正确时返回None
+pub fn parse_api_token(token: &str) -> Option<&str> {
+    let prefix_len = API_TOKEN_PREFIX.len() + TOKEN_ID_LEN;
+    if !token.starts_with(API_TOKEN_PREFIX) || token.len() != prefix_len + 1 + TOKEN_SECRET_LEN || token.as_bytes()[prefix_len] != b'_' {
+        return None;
+    }
+    Some(&token[..prefix_len])
+}
+
+/// 令牌哈希(令牌是高强度随机数,使用sha256即可,不需要慢哈希)
+pub fn hash_api_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes()).iter().map(|x| format!("{:02x}", x)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::api_token_util::{generate_api_token, hash_api_token, parse_api_token};
+
+    #[test]
+    fn test_api_token() {
+        let (token, token_prefix, token_hash) = generate_api_token();
+        assert!(token.starts_with(&format!("{}_", token_prefix)));
+        assert_eq!(parse_api_token(&token), Some(token_prefix.as_str()));
+        assert_eq!(hash_api_token(&token), token_hash);
+        assert_eq!(token_hash.len(), 64);
+
+        assert_eq!(parse_api_token("rat_abc"), None);
+        assert_eq!(parse_api_token(&token.replacen('_', "-", 2)), None);
+        assert_eq!(parse_api_token("eyJhbGciOiJIUzI1NiJ9.e30.x"), None);
+        assert_ne!(generate_api_token().0, token);
+    }
+}
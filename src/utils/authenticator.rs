@@ -0,0 +1,303 @@
+use crate::common::error::{AppError, AppResult};
+use crate::model::system::sys_user_model::User;
+use crate::utils::password_util::{PasswordUtil, PasswordVerify};
+use async_trait::async_trait;
+use ldap3::{ldap_escape, LdapConnAsync, LdapConnSettings, Scope, SearchEntry};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub const LOCAL_AUTH: &str = "local";
+pub const LDAP_AUTH: &str = "ldap";
+
+//LDAP内存目录的地址,用于本地开发和测试
+const LDAP_MEMORY_URL: &str = "memory://";
+//LDAP错误码: 账号或者密码不正确
+const LDAP_INVALID_CREDENTIALS: u32 = 49;
+
+/*
+ *认证方式配置
+ *author：刘飞华
+ *date：2026/10/18 20:41:17
+ */
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AuthenticatorConfig {
+    pub chain: Vec<String>, //默认的认证链,按顺序尝试,任意一个认证通过即可登录(用户指定了认证方式时只使用该方式)
+    pub ldap: LdapConfig,   //LDAP认证配置
+}
+
+impl Default for AuthenticatorConfig {
+    fn default() -> Self {
+        AuthenticatorConfig {
+            chain: vec![LOCAL_AUTH.to_string()],
+            ldap: LdapConfig::default(),
+        }
+    }
+}
+
+/*
+ *LDAP认证配置
+ *author：刘飞华
+ *date：2026/10/18 20:41:17
+ */
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct LdapConfig {
+    pub url: String,                    //LDAP服务地址,如: ldap://127.0.0.1:389, ldaps://127.0.0.1:636, 为空时不启用LDAP认证, memory://表示使用内存目录
+    pub start_tls: bool,                //ldap://地址是否使用StartTLS
+    pub bind_dn: String,                //查询用户使用的账号,为空时匿名查询
+    pub bind_password: String,          //查询用户使用的密码
+    pub base_dn: String,                //查询用户的根节点,如: ou=users,dc=example,dc=org
+    pub user_filter: String,            //查询用户的过滤条件,{user_name}, {email}, {mobile}会替换为用户的账号,邮箱和手机号
+    pub timeout: u64,                   //连接超时时间(秒)
+    pub users: HashMap<String, String>, //内存目录中的用户(用户账号 -> 密码),只在url为memory://时使用
+}
+
+impl Default for LdapConfig {
+    fn default() -> Self {
+        LdapConfig {
+            url: "".to_string(),
+            start_tls: false,
+            bind_dn: "".to_string(),
+            bind_password: "".to_string(),
+            base_dn: "".to_string(),
+            user_filter: "(uid={user_name})".to_string(),
+            timeout: 5,
+            users: HashMap::new(),
+        }
+    }
+}
+
+/*
+ *认证方式,校验用户的密码(用户需要先在系统中存在,认证方式只负责校验密码)
+ *author：刘飞华
+ *date：2026/10/18 20:41:17
+ */
+#[async_trait]
+pub trait Authenticator: Send + Sync {
+    /// 认证方式名称,对应认证链配置和用户的认证方式(auth_source)
+    fn name(&self) -> &str;
+
+    /// 校验密码,服务不可用等异常时返回Err,认证链会继续尝试下一个认证方式
+    async fn authenticate(&self, user: &User, password: &str) -> AppResult<PasswordVerify>;
+}
+
+/*
+ *本地密码认证(sys_user表中的密码)
+ *author：刘飞华
+ *date：2026/10/18 20:41:17
+ */
+pub struct LocalAuthenticator {
+    password_util: PasswordUtil,
+}
+
+impl LocalAuthenticator {
+    pub fn new(password_util: &PasswordUtil) -> LocalAuthenticator {
+        LocalAuthenticator { password_util: password_util.clone() }
+    }
+}
+
+#[async_trait]
+impl Authenticator for LocalAuthenticator {
+    fn name(&self) -> &str {
+        LOCAL_AUTH
+    }
+
+    async fn authenticate(&self, user: &User, password: &str) -> AppResult<PasswordVerify> {
+        Ok(self.password_util.verify(password, &user.password))
+    }
+}
+
+/*
+ *LDAP认证: 先查询用户的dn,再使用dn和密码绑定
+ *author：刘飞华
+ *date：2026/10/18 20:41:17
+ */
+pub struct LdapAuthenticator {
+    config: LdapConfig,
+}
+
+impl LdapAuthenticator {
+    pub fn new(config: &LdapConfig) -> LdapAuthenticator {
+        LdapAuthenticator { config: config.clone() }
+    }
+
+    async fn bind(&self, user: &User, password: &str) -> Result<PasswordVerify, ldap3::LdapError> {
+        let config = &self.config;
+        let settings = LdapConnSettings::new().set_conn_timeout(Duration::from_secs(config.timeout)).set_starttls(config.start_tls);
+        let (conn, mut ldap) = LdapConnAsync::with_settings(settings, &config.url).await?;
+        ldap3::drive!(conn);
+
+        if !config.bind_dn.is_empty() {
+            ldap.simple_bind(&config.bind_dn, &config.bind_password).await?.success()?;
+        }
+
+        let filter = config
+            .user_filter
+            .replace("{user_name}", &ldap_escape(user.user_name.as_str()))
+            .replace("{email}", &ldap_escape(user.email.as_str()))
+            .replace("{mobile}", &ldap_escape(user.mobile.as_str()));
+        let (entries, _) = ldap.search(&config.base_dn, Scope::Subtree, &filter, vec!["1.1"]).await?.success()?;
+        let dn = match entries.into_iter().next() {
+            None => {
+                log::info!("ldap user not found: {}", filter);
+                let _ = ldap.unbind().await;
+                return Ok(PasswordVerify::Invalid);
+            }
+            Some(entry) => SearchEntry::construct(entry).dn,
+        };
+
+        let result = ldap.simple_bind(&dn, password).await?;
+        let _ = ldap.unbind().await;
+        match result.rc {
+            0 => Ok(PasswordVerify::Valid),
+            LDAP_INVALID_CREDENTIALS => Ok(PasswordVerify::Invalid),
+            _ => result.success().map(|_| PasswordVerify::Invalid),
+        }
+    }
+}
+
+#[async_trait]
+impl Authenticator for LdapAuthenticator {
+    fn name(&self) -> &str {
+        LDAP_AUTH
+    }
+
+    async fn authenticate(&self, user: &User, password: &str) -> AppResult<PasswordVerify> {
+        //空密码的简单绑定会被LDAP服务当作匿名绑定,直接拒绝
+        if password.is_empty() {
+            return Ok(PasswordVerify::Invalid);
+        }
+
+        if self.config.url == LDAP_MEMORY_URL {
+            return match self.config.users.get(&user.user_name) {
+                Some(x) if x == password => Ok(PasswordVerify::Valid),
+                _ => Ok(PasswordVerify::Invalid),
+            };
+        }
+
+        self.bind(user, password).await.map_err(|e| AppError::AuthError(format!("ldap: {}", e)))
+    }
+}
+
+/*
+ *认证链,按顺序尝试认证方式(新的认证方式实现Authenticator后在new中注册)
+ *author：刘飞华
+ *date：2026/10/18 20:41:17
+ */
+#[derive(Clone)]
+pub struct AuthenticatorChain {
+    chain: Vec<String>,
+    providers: HashMap<String, Arc<dyn Authenticator>>,
+}
+
+impl AuthenticatorChain {
+    pub fn new(config: &AuthenticatorConfig, password_util: &PasswordUtil) -> AppResult<AuthenticatorChain> {
+        let mut chain = AuthenticatorChain {
+            chain: config.chain.clone(),
+            providers: HashMap::new(),
+        }
+        .register(Arc::new(LocalAuthenticator::new(password_util)));
+        if !config.ldap.url.is_empty() {
+            chain = chain.register(Arc::new(LdapAuthenticator::new(&config.ldap)));
+        }
+
+        if chain.chain.is_empty() {
+            return Err(AppError::AuthError("authenticator chain is empty".to_string()));
+        }
+        if let Some(name) = chain.chain.iter().find(|x| !chain.providers.contains_key(*x)) {
+            return Err(AppError::AuthError(format!("authenticator {} is not configured", name)));
+        }
+        Ok(chain)
+    }
+
+    /// 注册认证方式,名称相同时覆盖已有的认证方式
+    pub fn register(mut self, provider: Arc<dyn Authenticator>) -> AuthenticatorChain {
+        self.providers.insert(provider.name().to_string(), provider);
+        self
+    }
+
+    /// 校验用户的密码,用户指定了认证方式(auth_source)时只使用该方式,否则按认证链依次尝试
+    pub async fn authenticate(&self, user: &User, password: &str) -> PasswordVerify {
+        let names = match user.auth_source.as_str() {
+            "" => self.chain.clone(),
+            name => vec![name.to_string()],
+        };
+
+        for name in names {
+            let provider = match self.providers.get(&name) {
+                Some(provider) => provider,
+                None => {
+                    log::error!("authenticator {} of user {} is not configured", name, user.user_name);
+                    continue;
+                }
+            };
+            match provider.authenticate(user, password).await {
+                Ok(PasswordVerify::Invalid) => {}
+                Ok(verify) => return verify,
+                Err(err) => log::error!("authenticate user {} by {} error: {}", user.user_name, name, err),
+            }
+        }
+        PasswordVerify::Invalid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::system::sys_user_model::User;
+    use crate::utils::authenticator::{AuthenticatorChain, AuthenticatorConfig, LdapConfig};
+    use crate::utils::password_util::{PasswordConfig, PasswordUtil, PasswordVerify};
+
+    fn user(user_name: &str, password: &str, auth_source: &str) -> User {
+        User {
+            id: Some(2),
+            mobile: "18613030222".to_string(),
+            user_name: user_name.to_string(),
+            nick_name: user_name.to_string(),
+            user_type: None,
+            email: "".to_string(),
+            avatar: None,
+            password: password.to_string(),
+            auth_source: auth_source.to_string(),
+            status: 1,
+            dept_id: 1,
+            login_ip: "".to_string(),
+            login_date: None,
+            login_browser: "".to_string(),
+            login_os: "".to_string(),
+            pwd_update_date: None,
+            remark: None,
+            locale: "".to_string(),
+            del_flag: 1,
+            create_time: None,
+            update_time: None,
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_authenticator_chain() {
+        let password_util = PasswordUtil::new(&PasswordConfig::default()).unwrap();
+        let mut config = AuthenticatorConfig {
+            chain: vec!["ldap".to_string(), "local".to_string()],
+            ldap: LdapConfig::default(),
+        };
+
+        // 没有配置LDAP服务时不能使用LDAP认证
+        assert!(AuthenticatorChain::new(&config, &password_util).is_err());
+
+        config.ldap.url = "memory://".to_string();
+        config.ldap.users.insert("koobe".to_string(), "ldap123".to_string());
+        let chain = AuthenticatorChain::new(&config, &password_util).unwrap();
+
+        let hash = password_util.hash("local123").unwrap();
+        assert_eq!(chain.authenticate(&user("koobe", &hash, ""), "ldap123").await, PasswordVerify::Valid);
+        assert_eq!(chain.authenticate(&user("koobe", &hash, ""), "local123").await, PasswordVerify::Valid);
+        assert_eq!(chain.authenticate(&user("koobe", &hash, ""), "").await, PasswordVerify::Invalid);
+
+        // 用户指定了认证方式时只使用该方式
+        assert_eq!(chain.authenticate(&user("koobe", &hash, "ldap"), "local123").await, PasswordVerify::Invalid);
+        assert_eq!(chain.authenticate(&user("koobe", "local123", "local"), "local123").await, PasswordVerify::NeedsRehash);
+    }
+}
@@ -0,0 +1,180 @@
+use crate::common::error::{AppError, AppResult};
+use crate::utils::token_util::now_secs;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use rand::Rng;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+//验证码字符(去掉了容易混淆的0,O,1,I)
+const CAPTCHA_CHARS: &[u8] = b"23456789ABCDEFGHJKLMNPQRSTUVWXYZ";
+//验证码长度
+const CAPTCHA_LEN: usize = 4;
+//验证码有效期(秒)
+const CAPTCHA_TTL: usize = 300;
+//图片大小和字符放大倍数
+const WIDTH: usize = 120;
+const HEIGHT: usize = 40;
+const SCALE: usize = 4;
+
+// 5x7点阵字体,每行低5位表示像素
+fn glyph(c: u8) -> [u8; 7] {
+    match c {
+        b'2' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        b'3' => [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        b'4' => [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        b'5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        b'6' => [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        b'7' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        b'8' => [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        b'9' => [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+        b'A' => [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        b'B' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+        b'C' => [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110],
+        b'D' => [0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100],
+        b'E' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        b'F' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+        b'G' => [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111],
+        b'H' => [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        b'J' => [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100],
+        b'K' => [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001],
+        b'L' => [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+        b'M' => [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+        b'N' => [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001],
+        b'P' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000],
+        b'Q' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101],
+        b'R' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+        b'S' => [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+        b'T' => [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+        b'U' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        b'V' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+        b'W' => [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+        b'X' => [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001],
+        b'Y' => [0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100, 0b00100],
+        b'Z' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111],
+        _ => [0; 7],
+    }
+}
+
+/*
+ *图片验证码(进程内存储,一次有效)
+ *author：刘飞华
+ *date：2026/10/18 19:26:08
+ */
+#[derive(Clone, Default)]
+pub struct CaptchaStore {
+    inner: Arc<Mutex<HashMap<String, (String, usize)>>>, //验证码编号 -> (验证码, 过期时间)
+}
+
+impl CaptchaStore {
+    pub fn new() -> CaptchaStore {
+        CaptchaStore::default()
+    }
+
+    /// 生成验证码,返回验证码编号和base64编码的png图片(data url)
+    pub fn generate(&self) -> AppResult<(String, String)> {
+        let mut rng = rand::thread_rng();
+        let code = (0..CAPTCHA_LEN).map(|_| CAPTCHA_CHARS[rng.gen_range(0..CAPTCHA_CHARS.len())] as char).collect::<String>();
+        let image = format!("data:image/png;base64,{}", STANDARD.encode(render_png(&code)?));
+
+        let id = Uuid::new_v4().to_string();
+        let now = now_secs();
+        let mut inner = self.inner.lock().unwrap();
+        inner.retain(|_, (_, exp)| *exp > now);
+        inner.insert(id.clone(), (code, now + CAPTCHA_TTL));
+        Ok((id, image))
+    }
+
+    /// 校验验证码(不区分大小写),无论成功与否验证码都会失效
+    pub fn verify(&self, id: &str, code: &str) -> bool {
+        match self.inner.lock().unwrap().remove(id) {
+            Some((expected, exp)) => exp > now_secs() && expected.eq_ignore_ascii_case(code.trim()),
+            None => false,
+        }
+    }
+}
+
+// 绘制验证码图片: 随机颜色和位置的放大点阵字符,加上干扰线和噪点
+fn render_png(code: &str) -> AppResult<Vec<u8>> {
+    let mut rng = rand::thread_rng();
+    let mut pixels = vec![0u8; WIDTH * HEIGHT * 3];
+    let put = |pixels: &mut [u8], x: usize, y: usize, color: [u8; 3]| {
+        if x < WIDTH && y < HEIGHT {
+            let i = (y * WIDTH + x) * 3;
+            pixels[i..i + 3].copy_from_slice(&color);
+        }
+    };
+
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            put(&mut pixels, x, y, [240, 244, 250]);
+        }
+    }
+
+    for _ in 0..6 {
+        let color = [rng.gen_range(120..220), rng.gen_range(120..220), rng.gen_range(120..220)];
+        let (x0, y0) = (rng.gen_range(0..WIDTH) as f32, rng.gen_range(0..HEIGHT) as f32);
+        let (x1, y1) = (rng.gen_range(0..WIDTH) as f32, rng.gen_range(0..HEIGHT) as f32);
+        for step in 0..=WIDTH {
+            let t = step as f32 / WIDTH as f32;
+            put(&mut pixels, (x0 + (x1 - x0) * t) as usize, (y0 + (y1 - y0) * t) as usize, color);
+        }
+    }
+
+    let char_width = WIDTH / CAPTCHA_LEN;
+    for (n, c) in code.bytes().enumerate() {
+        let color = [rng.gen_range(0..100), rng.gen_range(0..100), rng.gen_range(0..150)];
+        let left = n * char_width + rng.gen_range(2..char_width - 5 * SCALE + 1);
+        let top = rng.gen_range(1..HEIGHT - 7 * SCALE + 1);
+        for (row, bits) in glyph(c).iter().enumerate() {
+            for col in 0..5 {
+                if bits & (0b10000 >> col) == 0 {
+                    continue;
+                }
+                for dy in 0..SCALE {
+                    for dx in 0..SCALE {
+                        put(&mut pixels, left + col * SCALE + dx, top + row * SCALE + dy, color);
+                    }
+                }
+            }
+        }
+    }
+
+    for _ in 0..120 {
+        let color = [rng.gen_range(0..255), rng.gen_range(0..255), rng.gen_range(0..255)];
+        put(&mut pixels, rng.gen_range(0..WIDTH), rng.gen_range(0..HEIGHT), color);
+    }
+
+    encode_png(&pixels, WIDTH as u32, HEIGHT as u32, png::ColorType::Rgb)
+}
+
+/// 把像素数据编码为png图片
+pub fn encode_png(pixels: &[u8], width: u32, height: u32, color: png::ColorType) -> AppResult<Vec<u8>> {
+    let mut data = Vec::new();
+    let mut encoder = png::Encoder::new(&mut data, width, height);
+    encoder.set_color(color);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|e| AppError::ImageError(e.to_string()))?;
+    writer.write_image_data(pixels).map_err(|e| AppError::ImageError(e.to_string()))?;
+    writer.finish().map_err(|e| AppError::ImageError(e.to_string()))?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::captcha_util::CaptchaStore;
+
+    #[test]
+    fn test_captcha() {
+        let store = CaptchaStore::new();
+        let (id, image) = store.generate().unwrap();
+        assert!(image.starts_with("data:image/png;base64,"));
+
+        let code = store.inner.lock().unwrap().get(&id).unwrap().0.clone();
+        assert!(store.verify(&id, &code.to_lowercase()));
+
+        // 验证码只能使用一次
+        assert!(!store.verify(&id, &code));
+    }
+}
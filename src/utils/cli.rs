@@ -0,0 +1,486 @@
+use crate::common::error::{AppError, AppResult};
+use crate::common::error_code::ErrorCode;
+use crate::handler::system::sys_online_handler::logout_user_sessions;
+use crate::handler::system::sys_user_handler::{add_password_history, check_new_password, save_password, select_by_account};
+use crate::model::system::sys_login_log_model::delete_login_log_before;
+use crate::model::system::sys_menu_model::Menu;
+use crate::model::system::sys_operate_log_model::delete_operate_log_before;
+use crate::model::system::sys_user_model::User;
+use crate::model::system::sys_user_role_model::UserRole;
+use crate::utils::db::{last_insert_id, transaction, DbKind};
+use crate::utils::migration::MIGRATIONS;
+use crate::utils::settings::Settings;
+use crate::AppState;
+use rbatis::rbdc::datetime::DateTime;
+use std::collections::HashMap;
+use std::io::{BufRead, IsTerminal, Write};
+use std::time::Duration;
+
+pub const USAGE: &str = "用法: actix-admin [命令] [参数]
+
+命令:
+  serve                                      启动HTTP服务(没有指定命令时默认执行)
+  migrate [up|down [n]|status|check|baseline [v]]
+                                             执行数据库迁移
+  create-admin --user-name <账号> [--password <密码>] [--nick-name <昵称>] [--mobile <手机号>] [--email <邮箱>]
+                                             创建超级管理员,没有指定密码时从标准输入读取
+  reset-password <账号> [--password <密码>]   重置用户密码并下线该用户的全部会话,没有指定密码时从标准输入读取
+  unlock <账号> [--ip <IP>]                  解除账号(和IP)因登录失败次数过多的锁定
+  sync-menus [--dry-run]                     把内置菜单中缺少的菜单添加到菜单表
+  purge-logs --older-than <时长> [--type login|operate|all]
+                                             删除指定时长之前的登录日志和操作日志,时长如: 90d, 12h, 30m
+  check-config                               校验配置文件和环境变量
+  help                                       显示帮助
+
+账号可以是用户账号,手机号或者邮箱";
+
+//不需要值的参数
+const FLAGS: &[&str] = &["dry-run"];
+
+/*
+ *命令行命令
+ *author：刘飞华
+ *date：2026/10/19 09:12:40
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Serve,                //启动HTTP服务
+    Migrate(Vec<String>), //执行数据库迁移
+    CreateAdmin {
+        user_name: String,        //用户账号
+        password: Option<String>, //密码,为空时从标准输入读取
+        nick_name: String,        //用户昵称
+        mobile: String,           //手机号
+        email: String,            //邮箱
+    },
+    ResetPassword {
+        account: String,          //登录账号
+        password: Option<String>, //新密码,为空时从标准输入读取
+    },
+    Unlock {
+        account: String,        //登录账号
+        ipaddr: Option<String>, //同时解锁的IP
+    },
+    SyncMenus {
+        dry_run: bool, //只输出差异,不修改菜单表
+    },
+    PurgeLogs {
+        older_than: Duration, //删除该时长之前的日志
+        log_type: String,     //日志类型: login, operate, all
+    },
+    CheckConfig, //校验配置
+    Help,        //显示帮助
+}
+
+impl Command {
+    /// 解析命令行参数(不包含程序名)
+    pub fn parse(args: &[String]) -> AppResult<Command> {
+        let name = match args.first() {
+            None => return Ok(Command::Serve),
+            Some(name) => name.as_str(),
+        };
+        if name == "migrate" {
+            return Ok(Command::Migrate(args[1..].to_vec()));
+        }
+
+        let (positional, mut options) = parse_args(&args[1..])?;
+        let mut take = |key: &str| options.remove(key);
+        let command = match name {
+            "serve" => Command::Serve,
+            "check-config" => Command::CheckConfig,
+            "help" | "--help" | "-h" => Command::Help,
+            "create-admin" => Command::CreateAdmin {
+                user_name: take("user-name").ok_or_else(|| required("--user-name"))?,
+                password: take("password"),
+                nick_name: take("nick-name").unwrap_or_default(),
+                mobile: take("mobile").unwrap_or_default(),
+                email: take("email").unwrap_or_default(),
+            },
+            "reset-password" => Command::ResetPassword {
+                account: positional.first().cloned().ok_or_else(|| required("<账号>"))?,
+                password: take("password"),
+            },
+            "unlock" => Command::Unlock {
+                account: positional.first().cloned().ok_or_else(|| required("<账号>"))?,
+                ipaddr: take("ip"),
+            },
+            "sync-menus" => Command::SyncMenus { dry_run: take("dry-run").is_some() },
+            "purge-logs" => Command::PurgeLogs {
+                older_than: parse_duration(&take("older-than").ok_or_else(|| required("--older-than"))?)?,
+                log_type: match take("type").as_deref().unwrap_or("all") {
+                    x @ ("login" | "operate" | "all") => x.to_string(),
+                    x => return Err(AppError::CommandError(format!("--type 只能是 login, operate 或者 all,不能是 {}", x))),
+                },
+            },
+            x => return Err(AppError::CommandError(format!("未知的命令: {}\n\n{}", x, USAGE))),
+        };
+
+        let max_positional = match command {
+            Command::ResetPassword { .. } | Command::Unlock { .. } => 1,
+            _ => 0,
+        };
+        if let Some(x) = positional.get(max_positional) {
+            return Err(AppError::CommandError(format!("{} 不支持参数 {}", name, x)));
+        }
+        if let Some(x) = options.keys().next() {
+            return Err(AppError::CommandError(format!("{} 不支持参数 --{}", name, x)));
+        }
+        Ok(command)
+    }
+}
+
+fn required(name: &str) -> AppError {
+    AppError::CommandError(format!("缺少参数 {}", name))
+}
+
+/// 把参数拆分成位置参数和 --名称 值(或者 --名称=值) 形式的参数
+fn parse_args(args: &[String]) -> AppResult<(Vec<String>, HashMap<String, String>)> {
+    let mut positional = Vec::new();
+    let mut options = HashMap::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let key = match arg.strip_prefix("--") {
+            None => {
+                positional.push(arg.clone());
+                continue;
+            }
+            Some(key) => key,
+        };
+        let (key, value) = match key.split_once('=') {
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            None if FLAGS.contains(&key) => (key.to_string(), "true".to_string()),
+            None => (key.to_string(), iter.next().cloned().ok_or_else(|| AppError::CommandError(format!("参数 --{} 缺少值", key)))?),
+        };
+        options.insert(key, value);
+    }
+    Ok((positional, options))
+}
+
+/// 解析时长,如: 90d, 12h, 30m, 45s,没有单位时按天计算
+pub fn parse_duration(value: &str) -> AppResult<Duration> {
+    let value = value.trim();
+    let (number, unit) = match value.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => value.split_at(index),
+        None => (value, "d"),
+    };
+    let seconds = match unit {
+        "d" => 86400,
+        "h" => 3600,
+        "m" => 60,
+        "s" => 1,
+        _ => 0,
+    };
+    match number.parse::<u64>() {
+        Ok(number) if number > 0 && seconds > 0 => Ok(Duration::from_secs(number * seconds)),
+        _ => Err(AppError::CommandError(format!("{} 不是有效的时长,格式如: 90d, 12h, 30m", value))),
+    }
+}
+
+/// 执行需要连接数据库的命令(serve, migrate, check-config和help由main处理)
+pub async fn run(state: &AppState, config: &Settings, command: Command) -> AppResult<()> {
+    match command {
+        Command::CreateAdmin {
+            user_name,
+            password,
+            nick_name,
+            mobile,
+            email,
+        } => create_admin(state, user_name, password, nick_name, mobile, email).await,
+        Command::ResetPassword { account, password } => reset_password(state, &account, password).await,
+        Command::Unlock { account, ipaddr } => unlock(state, config, &account, ipaddr).await,
+        Command::SyncMenus { dry_run } => sync_menus(state, dry_run).await,
+        Command::PurgeLogs { older_than, log_type } => purge_logs(state, older_than, &log_type).await,
+        command => Err(AppError::CommandError(format!("{:?} 不能在这里执行", command))),
+    }
+}
+
+/// 创建超级管理员(分配role_id=1的超级管理员角色)
+async fn create_admin(state: &AppState, user_name: String, password: Option<String>, nick_name: String, mobile: String, email: String) -> AppResult<()> {
+    let rb = &state.batis;
+    if User::select_by_user_name(rb, &user_name).await?.is_some() {
+        return Err(AppError::with_args(ErrorCode::DuplicateUserName, &[&user_name]));
+    }
+    if !mobile.is_empty() && User::select_by_mobile(rb, &mobile).await?.is_some() {
+        return Err(AppError::with_args(ErrorCode::DuplicateMobile, &[&mobile]));
+    }
+    if !email.is_empty() && User::select_by_email(rb, &email).await?.is_some() {
+        return Err(AppError::with_args(ErrorCode::DuplicateEmail, &[&email]));
+    }
+
+    let password = read_password(password)?;
+    state.password_policy.validate(&password, &user_name)?;
+
+    let now = DateTime::now();
+    let user = User {
+        id: None,                                                                    //主键
+        mobile,                                                                      //手机
+        nick_name: if nick_name.is_empty() { user_name.clone() } else { nick_name }, //用户昵称
+        user_name,                                                                   //用户账号
+        user_type: Some("00".to_string()),                                           //用户类型（00系统用户）
+        email,                                                                       //用户邮箱
+        avatar: None,                                                                //头像路径
+        password: state.password_util.hash(&password)?,                              //密码
+        auth_source: "".to_string(),                                                 //认证方式,为空时使用配置的认证链
+        status: 1,                                                                   //状态(1:正常，0:禁用)
+        dept_id: 1,                                                                  //部门ID
+        login_ip: "".to_string(),                                                    //最后登录IP
+        login_date: None,                                                            //最后登录时间
+        login_browser: "".to_string(),                                               //浏览器类型
+        login_os: "".to_string(),                                                    //操作系统
+        pwd_update_date: Some(now.clone()),                                          //密码最后更新时间
+        remark: Some("命令行创建的超级管理员".to_string()),                          //备注
+        locale: "".to_string(),                                                      //语言偏好,为空时根据请求头Accept-Language选择语言
+        del_flag: 1,                                                                 //删除标志（0代表删除 1代表存在）
+        create_time: Some(now.clone()),                                              //创建时间
+        update_time: None,                                                           //修改时间
+    };
+    let id = transaction(rb, async |tx| {
+        let result = User::insert(tx, &user).await?;
+        let id = last_insert_id(tx, &result).await?;
+        add_password_history(tx, state.password_policy.history_count, id, &user.password).await?;
+
+        let user_role = UserRole {
+            id: None,               //主键
+            user_id: id,            //用户ID
+            role_id: 1,             //角色ID
+            create_time: Some(now), //创建时间
+        };
+        UserRole::insert(tx, &user_role).await?;
+        Ok(id)
+    })
+    .await?;
+    println!("created admin {} (id: {})", user.user_name, id);
+    Ok(())
+}
+
+/// 重置用户密码,校验密码规则和历史密码,并下线该用户的全部会话
+async fn reset_password(state: &AppState, account: &str, password: Option<String>) -> AppResult<()> {
+    let mut user = match select_by_account(&state.batis, account).await? {
+        None => return Err(AppError::from(ErrorCode::UserNotFound)),
+        Some(user) => user,
+    };
+    let password = read_password(password)?;
+    let password = check_new_password(state, &user, &password).await?;
+    save_password(state, &mut user, password).await?;
+
+    let user_id = user.id.unwrap_or_default();
+    logout_user_sessions(state, user_id).await?;
+    println!("reset password of {} (id: {}), all sessions are logged out", user.user_name, user_id);
+    Ok(())
+}
+
+/// 解除账号(和IP)的登录锁定,登录失败次数保存在服务进程内时命令行无法解锁
+async fn unlock(state: &AppState, config: &Settings, account: &str, ipaddr: Option<String>) -> AppResult<()> {
+    if config.login_limit.redis_url.is_empty() {
+        return Err(AppError::CommandError(
+            "没有配置 login_limit.redis_url(或 redis.url),登录失败次数保存在服务进程内,请使用解锁接口或者重启服务".to_string(),
+        ));
+    }
+
+    let mut accounts = vec![account.to_string()];
+    if let Some(user) = select_by_account(&state.batis, account).await? {
+        accounts.extend([user.user_name, user.mobile, user.email].into_iter().filter(|x| !x.is_empty()));
+    }
+    accounts.dedup();
+    for account in accounts.iter() {
+        state.login_limiter.unlock_account(account);
+    }
+    println!("unlocked account {}", accounts.join(", "));
+
+    if let Some(ipaddr) = ipaddr.filter(|x| !x.is_empty()) {
+        state.login_limiter.unlock_ip(&ipaddr);
+        println!("unlocked ip {}", ipaddr);
+    }
+    Ok(())
+}
+
+/// 把迁移脚本中的内置菜单同步到菜单表: 只添加缺少的菜单,已经存在的菜单即使和内置菜单不同也不覆盖
+async fn sync_menus(state: &AppState, dry_run: bool) -> AppResult<()> {
+    let rb = &state.batis;
+    let existing = Menu::select_all(rb).await?;
+
+    let mut added = 0;
+    for menu in builtin_menus()? {
+        let api_url = menu.api_url.clone().unwrap_or_default();
+        let same_api = existing.iter().find(|x| !api_url.is_empty() && x.api_url.as_deref() == Some(api_url.as_str()));
+        match (existing.iter().find(|x| x.id == menu.id), same_api) {
+            (Some(x), _) if (&x.menu_url, &x.api_url, x.parent_id) != (&menu.menu_url, &menu.api_url, menu.parent_id) => {
+                println!("changed  {:>4} {} (kept the existing menu)", x.id.unwrap_or_default(), x.menu_name);
+            }
+            (Some(_), _) => {}
+            (None, Some(x)) => println!("exists   {:>4} {} (as menu {})", menu.id.unwrap_or_default(), menu.menu_name, x.id.unwrap_or_default()),
+            (None, None) => {
+                println!("add      {:>4} {}", menu.id.unwrap_or_default(), menu.menu_name);
+                if !dry_run {
+                    Menu::insert(rb, &menu).await?;
+                }
+                added += 1;
+            }
+        }
+    }
+
+    if added > 0 && !dry_run {
+        //postgres指定主键插入不会更新自增序列,需要把序列设置为当前的最大值
+        if DbKind::of(rb)? == DbKind::Postgres {
+            rb.exec("select setval(pg_get_serial_sequence('sys_menu', 'id'), (select max(id) from sys_menu))", vec![]).await?;
+        }
+        state.permission_cache.invalidate_all();
+    }
+    println!("{} {} menus", if dry_run { "would add" } else { "added" }, added);
+    Ok(())
+}
+
+/// 解析迁移脚本中的内置菜单(每行一条 INSERT INTO sys_menu (列名) VALUES (值); 语句),各数据库的初始数据相同,使用mysql的脚本
+pub fn builtin_menus() -> AppResult<Vec<Menu>> {
+    let mut list = Vec::new();
+    for line in MIGRATIONS.iter().flat_map(|x| x.up(DbKind::Mysql).lines()) {
+        let line = match line.trim().strip_prefix("INSERT INTO sys_menu (") {
+            None => continue,
+            Some(line) => line,
+        };
+        let invalid = || AppError::CommandError(format!("内置菜单格式不正确: {}", line));
+        let (columns, values) = line.split_once(") VALUES (").ok_or_else(invalid)?;
+        let values = split_values(values.trim_end_matches(';').strip_suffix(')').ok_or_else(invalid)?);
+        let columns = columns.split(',').map(|x| x.trim()).collect::<Vec<&str>>();
+        if columns.len() != values.len() {
+            return Err(invalid());
+        }
+
+        let row = columns.into_iter().zip(values).collect::<HashMap<&str, String>>();
+        let text = |key: &str| row.get(key).cloned().unwrap_or_default();
+        let number = |key: &str, default: i64| row.get(key).map(|x| x.parse::<i64>().map_err(|_| invalid())).unwrap_or(Ok(default));
+        list.push(Menu {
+            id: Some(number("id", 0)?),               //主键
+            menu_name: text("menu_name"),             //菜单名称
+            menu_type: number("menu_type", 1)? as i8, //菜单类型(1：目录   2：菜单   3：按钮)
+            visible: number("visible", 1)? as i8,     //菜单状态（0:隐藏, 显示:1）
+            status: number("status", 1)? as i8,       //状态(1:正常，0:禁用)
+            sort: number("sort", 1)? as i32,          //排序
+            parent_id: Some(number("parent_id", 0)?), //父ID
+            menu_url: Some(text("menu_url")),         //路由路径
+            api_url: Some(text("api_url")),           //接口URL
+            menu_icon: Some(text("menu_icon")),       //菜单图标
+            remark: Some(text("remark")),             //备注
+            create_time: Some(DateTime::now()),       //创建时间
+            update_time: None,                        //修改时间
+        });
+    }
+    Ok(list)
+}
+
+/// 按逗号拆分VALUES中的值,去掉字符串两边的单引号('' 表示一个单引号)
+fn split_values(values: &str) -> Vec<String> {
+    let mut list = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut chars = values.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' if quoted && chars.peek() == Some(&'\'') => {
+                current.push('\'');
+                chars.next();
+            }
+            '\'' => quoted = !quoted,
+            ',' if !quoted => list.push(std::mem::take(&mut current).trim().to_string()),
+            c => current.push(c),
+        }
+    }
+    list.push(current.trim().to_string());
+    list
+}
+
+/// 删除指定时长之前的登录日志和操作日志
+async fn purge_logs(state: &AppState, older_than: Duration, log_type: &str) -> AppResult<()> {
+    let rb = &state.batis;
+    let before = DateTime::now().sub(older_than);
+    if log_type != "operate" {
+        let rows = delete_login_log_before(rb, &before).await?.rows_affected;
+        println!("deleted {} login logs before {}", rows, before);
+    }
+    if log_type != "login" {
+        let rows = delete_operate_log_before(rb, &before).await?.rows_affected;
+        println!("deleted {} operate logs before {}", rows, before);
+    }
+    Ok(())
+}
+
+/// 没有通过参数指定密码时从标准输入读取(终端中会提示输入),避免密码出现在命令历史中
+fn read_password(password: Option<String>) -> AppResult<String> {
+    let password = match password {
+        Some(password) => password,
+        None => {
+            let stdin = std::io::stdin();
+            if stdin.is_terminal() {
+                eprint!("password: ");
+                let _ = std::io::stderr().flush();
+            }
+            let mut line = String::new();
+            stdin.lock().read_line(&mut line).map_err(|e| AppError::CommandError(format!("读取密码失败: {}", e)))?;
+            line.trim_end_matches(['\r', '\n']).to_string()
+        }
+    };
+    match password.is_empty() {
+        true => Err(AppError::business(ErrorCode::BadRequest, "密码不能为空")),
+        false => Ok(password),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::cli::{builtin_menus, parse_duration, split_values, Command};
+    use std::time::Duration;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(Command::parse(&[]).unwrap(), Command::Serve);
+        assert_eq!(Command::parse(&args("migrate down 2")).unwrap(), Command::Migrate(args("down 2")));
+        assert_eq!(
+            Command::parse(&args("reset-password admin --password=Abc123456")).unwrap(),
+            Command::ResetPassword {
+                account: "admin".to_string(),
+                password: Some("Abc123456".to_string())
+            }
+        );
+        assert_eq!(Command::parse(&args("sync-menus --dry-run")).unwrap(), Command::SyncMenus { dry_run: true });
+        assert_eq!(
+            Command::parse(&args("purge-logs --older-than 30d --type login")).unwrap(),
+            Command::PurgeLogs {
+                older_than: Duration::from_secs(30 * 86400),
+                log_type: "login".to_string()
+            }
+        );
+        assert!(Command::parse(&args("create-admin")).is_err());
+        assert!(Command::parse(&args("unlock")).is_err());
+        assert!(Command::parse(&args("unlock admin test")).is_err());
+        assert!(Command::parse(&args("check-config --force")).is_err());
+        assert!(Command::parse(&args("purge-logs --older-than 30d --type all2")).is_err());
+        assert!(Command::parse(&args("start")).is_err());
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90 * 86400));
+        assert_eq!(parse_duration("12h").unwrap(), Duration::from_secs(12 * 3600));
+        assert_eq!(parse_duration("30m").unwrap(), Duration::from_secs(30 * 60));
+        assert!(parse_duration("0d").is_err());
+        assert!(parse_duration("1w").is_err());
+        assert!(parse_duration("d").is_err());
+    }
+
+    #[test]
+    fn test_builtin_menus() {
+        assert_eq!(split_values("1, '首页', 'it''s, ok', ''"), vec!["1", "首页", "it's, ok", ""]);
+
+        let menus = builtin_menus().unwrap();
+        assert!(menus.len() >= 98);
+        let user = menus.iter().find(|x| x.id == Some(3)).unwrap();
+        assert_eq!((user.menu_name.as_str(), user.menu_url.as_deref(), user.parent_id), ("用户管理", Some("/system/user"), Some(2)));
+        let mut ids = menus.iter().map(|x| x.id).collect::<Vec<_>>();
+        ids.dedup();
+        assert_eq!(ids.len(), menus.len());
+    }
+}
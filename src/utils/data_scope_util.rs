@@ -0,0 +1,83 @@
+use crate::common::error::AppResult;
+use crate::model::system::sys_dept_model::select_children_dept_by_id;
+use crate::model::system::sys_role_dept_model::RoleDept;
+use crate::model::system::sys_role_model::select_role_by_user_id;
+use crate::model::system::sys_user_model::User;
+use rbatis::RBatis;
+use rbs::value;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+pub const DATA_SCOPE_ALL: i8 = 1; //全部数据权限
+pub const DATA_SCOPE_CUSTOM: i8 = 2; //自定数据权限
+pub const DATA_SCOPE_DEPT: i8 = 3; //本部门数据权限
+pub const DATA_SCOPE_DEPT_AND_CHILD: i8 = 4; //本部门及以下数据权限
+pub const DATA_SCOPE_SELF: i8 = 5; //仅本人数据权限
+
+/*
+ *数据权限,列表查询时按部门和本人过滤数据
+ *author：刘飞华
+ *date：2026/10/18 17:42:10
+ */
+#[derive(Debug, Clone, Serialize)]
+pub struct DataScope {
+    pub all: bool,          //是否拥有全部数据权限
+    pub user_id: i64,       //可以查看本人的数据(0表示没有本人数据权限)
+    pub dept_ids: Vec<i64>, //可以查看的部门id
+}
+
+impl DataScope {
+    pub fn all() -> DataScope {
+        DataScope {
+            all: true,
+            user_id: 0,
+            dept_ids: vec![],
+        }
+    }
+}
+
+/*
+ *根据用户的角色计算数据权限(多个角色取并集)
+ *author：刘飞华
+ *date：2026/10/18 17:42:10
+ */
+pub async fn query_data_scope(rb: &RBatis, user_id: i64) -> AppResult<DataScope> {
+    //超级管理员拥有全部数据权限
+    if user_id == 1 {
+        return Ok(DataScope::all());
+    }
+
+    let roles = select_role_by_user_id(rb, &user_id).await?;
+    if roles.iter().any(|x| x.id == Some(1) || x.data_scope == DATA_SCOPE_ALL) {
+        return Ok(DataScope::all());
+    }
+
+    let dept_id = User::select_by_id(rb, user_id).await?.map(|x| x.dept_id).unwrap_or_default();
+
+    let mut scope_user_id = 0;
+    let mut dept_ids: BTreeSet<i64> = BTreeSet::new();
+    for role in roles {
+        match role.data_scope {
+            DATA_SCOPE_CUSTOM => {
+                let list = RoleDept::select_by_map(rb, value! {"role_id": role.id}).await?;
+                dept_ids.extend(list.into_iter().map(|x| x.dept_id));
+            }
+            DATA_SCOPE_DEPT => {
+                dept_ids.insert(dept_id);
+            }
+            DATA_SCOPE_DEPT_AND_CHILD => {
+                dept_ids.insert(dept_id);
+                let children = select_children_dept_by_id(rb, &dept_id).await?;
+                dept_ids.extend(children.into_iter().filter_map(|x| x.id));
+            }
+            DATA_SCOPE_SELF => scope_user_id = user_id,
+            _ => log::error!("unknown data_scope {} of role {:?}", role.data_scope, role.id),
+        }
+    }
+
+    Ok(DataScope {
+        all: false,
+        user_id: scope_user_id,
+        dept_ids: dept_ids.into_iter().collect(),
+    })
+}
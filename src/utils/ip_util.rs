@@ -0,0 +1,274 @@
+use actix_web::http::header::HeaderMap;
+use serde::Deserialize;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+/*
+ *客户端IP和IP归属地配置
+ *author：刘飞华
+ *date：2026/10/18 18:52:47
+ */
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct IpConfig {
+    pub trusted_proxies: Vec<String>, //可信代理的IP或者网段(如: 10.0.0.0/8),只有来自可信代理的请求才使用X-Forwarded-For等请求头
+    pub db_file: String,              //离线IP库文件(ip2region xdb格式),为空时不查询IP归属地
+}
+
+impl Default for IpConfig {
+    fn default() -> Self {
+        IpConfig {
+            trusted_proxies: vec!["127.0.0.1".to_string(), "::1".to_string()],
+            db_file: "".to_string(),
+        }
+    }
+}
+
+/*
+ *获取客户端真实IP和IP归属地
+ *author：刘飞华
+ *date：2026/10/18 18:52:47
+ */
+#[derive(Clone)]
+pub struct IpUtil {
+    trusted_proxies: Vec<(IpAddr, u8)>,
+    db: Option<Arc<Vec<u8>>>,
+}
+
+// ip2region xdb文件的格式: 256字节的头部, 256*256*8字节的向量索引, 之后是14字节一条的二分索引和归属地数据
+const HEADER_LEN: usize = 256;
+const VECTOR_INDEX_COLS: usize = 256;
+const VECTOR_INDEX_SIZE: usize = 8;
+const SEGMENT_INDEX_SIZE: usize = 14;
+
+impl IpUtil {
+    pub fn new(config: &IpConfig) -> IpUtil {
+        let trusted_proxies = config.trusted_proxies.iter().filter_map(|x| parse_cidr(x)).collect();
+
+        let db = match config.db_file.as_str() {
+            "" => None,
+            file => match std::fs::read(file) {
+                Ok(data) if data.len() > HEADER_LEN + VECTOR_INDEX_COLS * VECTOR_INDEX_COLS * VECTOR_INDEX_SIZE => Some(Arc::new(data)),
+                Ok(_) => {
+                    log::error!("ip db file {} is not a valid xdb file", file);
+                    None
+                }
+                Err(err) => {
+                    log::error!("read ip db file {} error: {:?}", file, err);
+                    None
+                }
+            },
+        };
+
+        IpUtil { trusted_proxies, db }
+    }
+
+    /// 获取客户端真实IP
+    /// 直连的地址不是可信代理时直接使用直连地址,避免客户端伪造请求头
+    pub fn client_ip(&self, peer_addr: Option<SocketAddr>, headers: &HeaderMap) -> String {
+        let peer = match peer_addr {
+            Some(addr) => addr.ip(),
+            None => return "".to_string(),
+        };
+        if !self.is_trusted(&peer) {
+            return peer.to_string();
+        }
+
+        //代理链从右往左,跳过可信代理,第一个不可信的地址就是客户端地址
+        let chain = forwarded_chain(headers);
+        if let Some(ip) = chain.iter().rev().find(|x| !self.is_trusted(x)).or(chain.first()) {
+            return ip.to_string();
+        }
+
+        let real_ip = headers.get("X-Real-IP").and_then(|x| x.to_str().ok()).and_then(parse_ip);
+        real_ip.unwrap_or(peer).to_string()
+    }
+
+    /// 查询IP归属地,如: 中国 广东省 深圳市 电信
+    pub fn location(&self, ip: &str) -> String {
+        let ip = match ip.parse::<IpAddr>() {
+            Ok(ip) => ip,
+            Err(_) => return "".to_string(),
+        };
+        if is_internal(&ip) {
+            return "内网IP".to_string();
+        }
+
+        let region = match (&self.db, ip) {
+            (Some(db), IpAddr::V4(v4)) => search(db, u32::from(v4)),
+            _ => None,
+        };
+        match region {
+            Some(region) => region.split('|').filter(|x| !x.is_empty() && *x != "0").collect::<Vec<&str>>().join(" "),
+            None => "未知".to_string(),
+        }
+    }
+
+    fn is_trusted(&self, ip: &IpAddr) -> bool {
+        self.trusted_proxies.iter().any(|(net, prefix)| in_cidr(ip, net, *prefix))
+    }
+}
+
+// 依次从Forwarded, X-Forwarded-For请求头中获取代理链
+fn forwarded_chain(headers: &HeaderMap) -> Vec<IpAddr> {
+    let forwarded = headers
+        .get_all("Forwarded")
+        .filter_map(|x| x.to_str().ok())
+        .flat_map(|x| x.split(','))
+        .filter_map(|x| x.split(';').find_map(|p| p.trim().strip_prefix("for=").or(p.trim().strip_prefix("For="))))
+        .filter_map(parse_ip)
+        .collect::<Vec<IpAddr>>();
+    if !forwarded.is_empty() {
+        return forwarded;
+    }
+
+    headers
+        .get_all("X-Forwarded-For")
+        .filter_map(|x| x.to_str().ok())
+        .flat_map(|x| x.split(','))
+        .filter_map(parse_ip)
+        .collect()
+}
+
+// 解析IP,兼容带引号,中括号和端口的写法: "[2001:db8::1]:4711", 192.0.2.60:8080
+fn parse_ip(s: &str) -> Option<IpAddr> {
+    let s = s.trim().trim_matches('"');
+    if let Ok(ip) = s.parse::<IpAddr>() {
+        return Some(ip);
+    }
+    if let Ok(addr) = s.parse::<SocketAddr>() {
+        return Some(addr.ip());
+    }
+    s.trim_start_matches('[').split(']').next().and_then(|x| x.parse::<IpAddr>().ok())
+}
+
+fn parse_cidr(s: &str) -> Option<(IpAddr, u8)> {
+    let (ip, prefix) = match s.split_once('/') {
+        Some((ip, prefix)) => (ip.trim().parse::<IpAddr>().ok()?, prefix.trim().parse::<u8>().ok()?),
+        None => {
+            let ip = s.trim().parse::<IpAddr>().ok()?;
+            (ip, if ip.is_ipv4() { 32 } else { 128 })
+        }
+    };
+    if s.contains('/') && prefix > if ip.is_ipv4() { 32 } else { 128 } {
+        log::error!("invalid trusted proxy: {}", s);
+        return None;
+    }
+    Some((ip, prefix))
+}
+
+fn in_cidr(ip: &IpAddr, net: &IpAddr, prefix: u8) -> bool {
+    match (ip, net) {
+        (IpAddr::V4(ip), IpAddr::V4(net)) => {
+            let mask = if prefix == 0 { 0 } else { u32::MAX << (32 - prefix as u32) };
+            u32::from(*ip) & mask == u32::from(*net) & mask
+        }
+        (IpAddr::V6(ip), IpAddr::V6(net)) => {
+            let mask = if prefix == 0 { 0 } else { u128::MAX << (128 - prefix as u32) };
+            u128::from(*ip) & mask == u128::from(*net) & mask
+        }
+        _ => false,
+    }
+}
+
+fn is_internal(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_private() || v4.is_loopback() || v4.is_link_local() || v4.is_unspecified(),
+        IpAddr::V6(v6) => v6.is_loopback() || v6.is_unspecified() || (v6.segments()[0] & 0xfe00) == 0xfc00,
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4).map(|x| u32::from_le_bytes([x[0], x[1], x[2], x[3]]))
+}
+
+// 在ip2region xdb数据中查询IPv4的归属地
+fn search(db: &[u8], ip: u32) -> Option<String> {
+    let il0 = (ip >> 24) as usize;
+    let il1 = ((ip >> 16) & 0xff) as usize;
+    let idx = HEADER_LEN + il0 * VECTOR_INDEX_COLS * VECTOR_INDEX_SIZE + il1 * VECTOR_INDEX_SIZE;
+    let start_ptr = read_u32(db, idx)? as usize;
+    let end_ptr = read_u32(db, idx + 4)? as usize;
+    if end_ptr < start_ptr {
+        return None;
+    }
+
+    let (mut low, mut high) = (0, (end_ptr - start_ptr) / SEGMENT_INDEX_SIZE);
+    while low <= high {
+        let mid = (low + high) / 2;
+        let p = start_ptr + mid * SEGMENT_INDEX_SIZE;
+        let start_ip = read_u32(db, p)?;
+        let end_ip = read_u32(db, p + 4)?;
+        if ip < start_ip {
+            if mid == 0 {
+                break;
+            }
+            high = mid - 1;
+        } else if ip > end_ip {
+            low = mid + 1;
+        } else {
+            let data_len = u16::from_le_bytes([*db.get(p + 8)?, *db.get(p + 9)?]) as usize;
+            let data_ptr = read_u32(db, p + 10)? as usize;
+            return db.get(data_ptr..data_ptr + data_len).map(|x| String::from_utf8_lossy(x).to_string());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::ip_util::{search, IpConfig, IpUtil, HEADER_LEN, VECTOR_INDEX_COLS, VECTOR_INDEX_SIZE};
+    use actix_web::http::header::{HeaderMap, HeaderName, HeaderValue};
+    use std::net::SocketAddr;
+
+    fn headers(list: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (k, v) in list {
+            map.append(HeaderName::from_static(k), HeaderValue::from_static(v));
+        }
+        map
+    }
+
+    #[test]
+    fn test_client_ip() {
+        let util = IpUtil::new(&IpConfig {
+            trusted_proxies: vec!["10.0.0.0/8".to_string()],
+            db_file: "".to_string(),
+        });
+        let proxy: SocketAddr = "10.0.0.2:5000".parse().unwrap();
+        let client: SocketAddr = "1.2.3.4:5000".parse().unwrap();
+
+        // 不是可信代理时忽略请求头
+        assert_eq!(util.client_ip(Some(client), &headers(&[("x-forwarded-for", "8.8.8.8")])), "1.2.3.4");
+
+        // 跳过链路中的可信代理,客户端伪造的最左侧地址不会被采用
+        assert_eq!(util.client_ip(Some(proxy), &headers(&[("x-forwarded-for", "8.8.8.8, 5.6.7.8, 10.0.0.3")])), "5.6.7.8");
+        assert_eq!(util.client_ip(Some(proxy), &headers(&[("forwarded", "for=\"[2001:db8::1]:4711\";proto=https")])), "2001:db8::1");
+        assert_eq!(util.client_ip(Some(proxy), &headers(&[("x-real-ip", "5.6.7.8")])), "5.6.7.8");
+        assert_eq!(util.client_ip(Some(proxy), &headers(&[])), "10.0.0.2");
+    }
+
+    #[test]
+    fn test_search() {
+        // 构造只有一条记录的xdb数据: 1.2.3.0 - 1.2.3.255
+        let region = "中国|0|广东省|深圳市|电信".as_bytes();
+        let segment_ptr = HEADER_LEN + VECTOR_INDEX_COLS * VECTOR_INDEX_COLS * VECTOR_INDEX_SIZE;
+        let data_ptr = segment_ptr + 14;
+        let mut db = vec![0u8; data_ptr];
+        let idx = HEADER_LEN + VECTOR_INDEX_COLS * VECTOR_INDEX_SIZE + 2 * VECTOR_INDEX_SIZE;
+        db[idx..idx + 4].copy_from_slice(&(segment_ptr as u32).to_le_bytes());
+        db[idx + 4..idx + 8].copy_from_slice(&(segment_ptr as u32).to_le_bytes());
+        db[segment_ptr..segment_ptr + 4].copy_from_slice(&0x01020300u32.to_le_bytes());
+        db[segment_ptr + 4..segment_ptr + 8].copy_from_slice(&0x010203ffu32.to_le_bytes());
+        db[segment_ptr + 8..segment_ptr + 10].copy_from_slice(&(region.len() as u16).to_le_bytes());
+        db[segment_ptr + 10..segment_ptr + 14].copy_from_slice(&(data_ptr as u32).to_le_bytes());
+        db.extend_from_slice(region);
+
+        assert_eq!(search(&db, 0x01020304).unwrap(), "中国|0|广东省|深圳市|电信");
+        assert!(search(&db, 0x01020404).is_none());
+
+        let util = IpUtil::new(&IpConfig::default());
+        assert_eq!(util.location("192.168.1.1"), "内网IP");
+        assert_eq!(util.location("1.2.3.4"), "未知");
+    }
+}
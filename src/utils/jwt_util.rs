@@ -1,18 +1,191 @@
 use crate::common::error::AppError;
 use crate::common::error::AppError::JwtTokenError;
-use jsonwebtoken::{decode, encode, errors::ErrorKind, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::{decode, decode_header, encode, errors::ErrorKind, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use uuid::Uuid;
+
+//HS256密钥的最小长度(字节),和签名使用的SHA-256输出长度一致
+const MIN_SECRET_LEN: usize = 32;
+
+/*
+ *jwt配置
+ *author：刘飞华
+ *date：2026/10/18 11:02:15
+ */
+#[derive(Debug, Clone, Deserialize)]
+pub struct JwtConfig {
+    #[serde(default = "default_algorithm")]
+    pub algorithm: String, //签名算法(HS256/RS256/EdDSA)
+    pub kid: String, //当前用于签名的密钥id
+    #[serde(default = "default_ttl")]
+    pub ttl: u64, //access token有效期(秒)
+    #[serde(default = "default_refresh_ttl")]
+    pub refresh_ttl: u64, //refresh token有效期(秒)
+    #[serde(default = "default_impersonate_ttl")]
+    pub impersonate_ttl: u64, //模拟登录token有效期(秒),到期后不能续期,需要重新模拟登录
+    #[serde(default = "default_issuer")]
+    pub issuer: String, //签发人
+    #[serde(default = "default_audience")]
+    pub audience: String, //受众
+    #[serde(default)]
+    pub secret: String, //HS256默认密钥,jwt.keys中没有配置secret和secret_env的HS256密钥使用它,通过环境变量APP_JWT__SECRET设置
+    pub keys: Vec<JwtKeyConfig>, //密钥列表(轮换时旧密钥保留到签发的token全部过期)
+}
+
+/*
+ *jwt密钥配置,密钥可以直接配置,也可以从文件或者环境变量中读取
+ *author：刘飞华
+ *date：2026/10/18 11:02:15
+ */
+#[derive(Debug, Clone, Deserialize)]
+pub struct JwtKeyConfig {
+    pub kid: String,                      //密钥id
+    pub algorithm: Option<String>,        //签名算法,不配置时使用jwt.algorithm
+    pub secret: Option<String>,           //HS256密钥
+    pub secret_env: Option<String>,       //HS256密钥所在的环境变量
+    pub private_key_file: Option<String>, //RS256/EdDSA私钥(PEM)文件,只用于验签的旧密钥可以不配置
+    pub private_key_env: Option<String>,  //RS256/EdDSA私钥(PEM)所在的环境变量
+    pub public_key_file: Option<String>,  //RS256/EdDSA公钥(PEM)文件
+    pub public_key_env: Option<String>,   //RS256/EdDSA公钥(PEM)所在的环境变量
+}
+
+fn default_algorithm() -> String {
+    "HS256".to_string()
+}
+
+fn default_ttl() -> u64 {
+    1800
+}
+
+fn default_refresh_ttl() -> u64 {
+    7 * 24 * 3600
+}
+
+fn default_impersonate_ttl() -> u64 {
+    1800
+}
+
+fn default_issuer() -> String {
+    "koobe".to_string()
+}
+
+fn default_audience() -> String {
+    "rust_admin".to_string()
+}
+
+/*
+ *jwt签名和验签使用的密钥集合
+ *author：刘飞华
+ *date：2026/10/18 11:02:15
+ */
+#[derive(Clone)]
+pub struct JwtKeys {
+    kid: String,
+    algorithm: Algorithm,
+    encoding_key: EncodingKey,
+    decoding_keys: HashMap<String, (Algorithm, DecodingKey)>,
+    pub ttl: u64,
+    pub refresh_ttl: u64,
+    pub impersonate_ttl: u64,
+    pub issuer: String,
+    pub audience: String,
+}
+
+impl JwtKeys {
+    pub fn from_config(config: &JwtConfig) -> Result<JwtKeys, AppError> {
+        let mut encoding_key = None;
+        let mut decoding_keys = HashMap::new();
+
+        for key in &config.keys {
+            let name = key.algorithm.as_deref().unwrap_or(&config.algorithm);
+            let algorithm = Algorithm::from_str(name).map_err(|_| JwtTokenError(format!("unsupported jwt algorithm: {}", name)))?;
+
+            let decoding_key = match algorithm {
+                Algorithm::HS256 => Ok(DecodingKey::from_secret(read_secret(config, key)?.as_bytes())),
+                Algorithm::RS256 => DecodingKey::from_rsa_pem(read_pem(&key.kid, &key.public_key_file, &key.public_key_env)?.as_bytes()),
+                Algorithm::EdDSA => DecodingKey::from_ed_pem(read_pem(&key.kid, &key.public_key_file, &key.public_key_env)?.as_bytes()),
+                _ => return Err(JwtTokenError(format!("unsupported jwt algorithm: {}", name))),
+            }
+            .map_err(|e| JwtTokenError(format!("load jwt public key {} error: {}", key.kid, e)))?;
+            decoding_keys.insert(key.kid.clone(), (algorithm, decoding_key));
+
+            if key.kid == config.kid {
+                let result = match algorithm {
+                    Algorithm::HS256 => Ok(EncodingKey::from_secret(read_secret(config, key)?.as_bytes())),
+                    Algorithm::RS256 => EncodingKey::from_rsa_pem(read_pem(&key.kid, &key.private_key_file, &key.private_key_env)?.as_bytes()),
+                    _ => EncodingKey::from_ed_pem(read_pem(&key.kid, &key.private_key_file, &key.private_key_env)?.as_bytes()),
+                };
+                let k = result.map_err(|e| JwtTokenError(format!("load jwt private key {} error: {}", key.kid, e)))?;
+                encoding_key = Some((algorithm, k));
+            }
+        }
+
+        match encoding_key {
+            None => Err(JwtTokenError(format!("jwt signing key {} not found in jwt.keys", config.kid))),
+            Some((algorithm, encoding_key)) => Ok(JwtKeys {
+                kid: config.kid.clone(),
+                algorithm,
+                encoding_key,
+                decoding_keys,
+                ttl: config.ttl,
+                refresh_ttl: config.refresh_ttl,
+                impersonate_ttl: config.impersonate_ttl,
+                issuer: config.issuer.clone(),
+                audience: config.audience.clone(),
+            }),
+        }
+    }
+}
+
+// 读取HS256密钥: secret_env -> secret -> jwt.secret,拒绝空密钥和长度不足32字节的弱密钥
+fn read_secret(config: &JwtConfig, key: &JwtKeyConfig) -> Result<String, AppError> {
+    let secret = match (&key.secret_env, &key.secret) {
+        (Some(name), _) => std::env::var(name).map_err(|_| JwtTokenError(format!("jwt secret env {} of key {} is not set", name, key.kid)))?,
+        (None, Some(secret)) if !secret.is_empty() => secret.clone(),
+        _ => config.secret.clone(),
+    };
+    match secret.len() {
+        0 => Err(JwtTokenError(format!("jwt key {} has no secret, set jwt.secret or APP_JWT__SECRET", key.kid))),
+        n if n < MIN_SECRET_LEN => Err(JwtTokenError(format!(
+            "jwt secret of key {} is too short ({} bytes), at least {} bytes are required",
+            key.kid, n, MIN_SECRET_LEN
+        ))),
+        _ => Ok(secret),
+    }
+}
+
+fn read_pem(kid: &str, file: &Option<String>, env: &Option<String>) -> Result<String, AppError> {
+    if let Some(name) = env {
+        return std::env::var(name).map_err(|_| JwtTokenError(format!("jwt key env {} of key {} is not set", name, kid)));
+    }
+    match file {
+        Some(path) => std::fs::read_to_string(path).map_err(|e| JwtTokenError(format!("read jwt key file {} error: {}", path, e))),
+        None => Err(JwtTokenError(format!("jwt key {} has no pem file or env", kid))),
+    }
+}
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct JwtToken {
     pub id: i64,
     pub username: String,
-    pub permissions: Vec<String>,
+    pub pv: u64,            //签发时的权限版本,权限从权限缓存中获取
+    pub token_type: String, //token类型(access/refresh/mfa/password/api)
+    pub sid: String,        //会话编号
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<i64>, //模拟登录时实际操作的管理员id
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub act_name: String, //模拟登录时实际操作的管理员账号
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub lang: String, //用户的语言偏好,为空时根据请求头Accept-Language选择语言
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub account: String, //登录时输入的账号(只在两步验证的挑战token中携带,两步登录共用同一个失败次数)
     aud: String,
     // (audience)：受众
-    exp: usize,
-    iat: usize,
+    pub exp: usize,
+    pub iat: usize,
     // (Issued At)：签发时间
     iss: String,
     // (issuer)：签发人
@@ -20,70 +193,184 @@ pub struct JwtToken {
     // (Not Before)：生效时间
     sub: String,
     // (subject)：主题
-    jti: String, // (JWT ID)：编号
+    pub jti: String, // (JWT ID)：编号
 }
 
+pub const ACCESS_TOKEN: &str = "access";
+pub const REFRESH_TOKEN: &str = "refresh";
+pub const MFA_TOKEN: &str = "mfa";
+pub const PASSWORD_TOKEN: &str = "password";
+pub const API_TOKEN: &str = "api";
+
 impl JwtToken {
-    pub fn new(id: i64, username: &str, permissions: Vec<String>) -> JwtToken {
+    pub fn new(id: i64, username: &str, sid: &str, pv: u64, keys: &JwtKeys) -> JwtToken {
+        JwtToken::build(id, username, sid, pv, ACCESS_TOKEN, keys.ttl, keys)
+    }
+
+    /// refresh token只用于换取新的access token,不携带权限
+    pub fn new_refresh(id: i64, username: &str, sid: &str, keys: &JwtKeys) -> JwtToken {
+        JwtToken::build(id, username, sid, 0, REFRESH_TOKEN, keys.refresh_ttl, keys)
+    }
+
+    /// 两步验证的挑战token,密码校验通过后签发,只能用于提交动态码完成登录,account为登录时输入的账号
+    pub fn new_mfa(id: i64, username: &str, account: &str, ttl: u64, keys: &JwtKeys) -> JwtToken {
+        let mut token = JwtToken::build(id, username, "", 0, MFA_TOKEN, ttl, keys);
+        token.account = String::from(account);
+        token
+    }
+
+    /// 密码过期的修改密码token,登录校验通过但密码已过期时签发,只能用于修改密码
+    pub fn new_password(id: i64, username: &str, ttl: u64, keys: &JwtKeys) -> JwtToken {
+        JwtToken::build(id, username, "", 0, PASSWORD_TOKEN, ttl, keys)
+    }
+
+    /// API令牌请求的身份(不会签发,只用于在请求中传递当前用户),会话编号为令牌标识
+    pub fn new_api(id: i64, username: &str, token_prefix: &str, keys: &JwtKeys) -> JwtToken {
+        JwtToken::build(id, username, token_prefix, 0, API_TOKEN, 0, keys)
+    }
+
+    /// 管理员模拟登录的token,id为被模拟的用户,act为实际操作的管理员,不签发refresh token,不能续期
+    pub fn new_impersonate(id: i64, username: &str, sid: &str, pv: u64, act: i64, act_name: &str, keys: &JwtKeys) -> JwtToken {
+        let mut token = JwtToken::build(id, username, sid, pv, ACCESS_TOKEN, keys.impersonate_ttl, keys);
+        token.act = Some(act);
+        token.act_name = String::from(act_name);
+        token
+    }
+
+    /// 携带用户的语言偏好
+    pub fn with_lang(mut self, lang: &str) -> JwtToken {
+        self.lang = String::from(lang);
+        self
+    }
+
+    /// 是否是管理员模拟登录的token
+    pub fn is_impersonated(&self) -> bool {
+        self.act.is_some()
+    }
+
+    fn build(id: i64, username: &str, sid: &str, pv: u64, token_type: &str, ttl: u64, keys: &JwtKeys) -> JwtToken {
         let now = SystemTime::now();
         //过期时间
-        let m30 = Duration::from_secs(1800000);
+        let ttl = Duration::from_secs(ttl);
         let now = now.duration_since(UNIX_EPOCH).expect("获取系统时间失败");
 
         JwtToken {
             id,
             username: String::from(username),
-            permissions,
-            aud: String::from("rust_admin"), // (audience)：受众
-            exp: (now + m30).as_secs() as usize,
+            pv,
+            token_type: String::from(token_type),
+            sid: String::from(sid),
+            act: None,
+            act_name: String::new(),
+            lang: String::new(),
+            account: String::new(),
+            aud: keys.audience.clone(), // (audience)：受众
+            exp: (now + ttl).as_secs() as usize,
             iat: now.as_secs() as usize,     // (Issued At)：签发时间
-            iss: String::from("koobe"),      // (issuer)：签发人
+            iss: keys.issuer.clone(),        // (issuer)：签发人
             nbf: now.as_secs() as usize,     // (Not Before)：生效时间
             sub: String::from("rust_admin"), // (subject)：主题
-            jti: String::from("ignore"),     // (JWT ID)：编号
+            jti: Uuid::new_v4().to_string(), // (JWT ID)：编号
         }
     }
 
     /// create token
-    /// secret: your secret string
-    pub fn create_token(&self, secret: &str) -> Result<String, AppError> {
-        return match encode(&Header::default(), self, &EncodingKey::from_secret(secret.as_ref())) {
+    /// keys: 使用当前kid对应的密钥签名,并把kid写入header
+    pub fn create_token(&self, keys: &JwtKeys) -> Result<String, AppError> {
+        let mut header = Header::new(keys.algorithm);
+        header.kid = Some(keys.kid.clone());
+        match encode(&header, self, &keys.encoding_key) {
             Ok(t) => Ok(t),
             Err(_) => Err(JwtTokenError("create token error".to_string())),
-        };
+        }
     }
     /// verify token invalid
-    /// secret: your secret string
-    pub fn verify(secret: &str, token: &str) -> Result<JwtToken, AppError> {
-        let mut validation = Validation::new(Algorithm::HS256);
+    /// keys: 根据header中的kid选择验签密钥,没有kid的旧token使用当前密钥
+    pub fn verify(keys: &JwtKeys, token: &str) -> Result<JwtToken, AppError> {
+        let kid = match decode_header(token) {
+            Ok(header) => header.kid.unwrap_or(keys.kid.clone()),
+            Err(_) => return Err(JwtTokenError("InvalidToken".to_string())),
+        };
+        let (algorithm, decoding_key) = match keys.decoding_keys.get(&kid) {
+            Some(key) => key,
+            None => return Err(JwtTokenError("InvalidToken".to_string())),
+        };
+
+        let mut validation = Validation::new(*algorithm);
         validation.sub = Some("rust_admin".to_string());
-        validation.set_audience(&["rust_admin"]);
+        validation.set_audience(&[&keys.audience]);
+        validation.set_issuer(&[&keys.issuer]);
         validation.set_required_spec_claims(&["exp", "sub", "aud"]);
-        return match decode::<JwtToken>(&token, &DecodingKey::from_secret(secret.as_ref()), &validation) {
+        match decode::<JwtToken>(token, decoding_key, &validation) {
             Ok(c) => Ok(c.claims),
 
             Err(err) => match *err.kind() {
-                ErrorKind::InvalidToken => return Err(JwtTokenError("InvalidToken".to_string())), // Example on how to handle a specific error
-                ErrorKind::InvalidIssuer => return Err(JwtTokenError("InvalidIssuer".to_string())), // Example on how to handle a specific error
-                ErrorKind::ExpiredSignature => return Err(JwtTokenError("token 已经超时了".to_string())), // Example on how to handle a specific error
+                ErrorKind::InvalidToken => Err(JwtTokenError("InvalidToken".to_string())),         // Example on how to handle a specific error
+                ErrorKind::InvalidIssuer => Err(JwtTokenError("InvalidIssuer".to_string())),       // Example on how to handle a specific error
+                ErrorKind::ExpiredSignature => Err(JwtTokenError("token 已经超时了".to_string())), // Example on how to handle a specific error
                 // _ => return Err(Error::from("InvalidToken other errors")),
                 _ => Err(JwtTokenError("create token error".to_string())),
             },
-        };
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::jwt_util::JwtToken;
+    use crate::utils::jwt_util::{JwtConfig, JwtKeyConfig, JwtKeys, JwtToken, MFA_TOKEN};
+
+    fn hs256_key(kid: &str, secret: &str) -> JwtKeyConfig {
+        JwtKeyConfig {
+            kid: kid.to_string(),
+            algorithm: None,
+            secret: Some(secret.to_string()),
+            secret_env: None,
+            private_key_file: None,
+            private_key_env: None,
+            public_key_file: None,
+            public_key_env: None,
+        }
+    }
+
+    fn config(kid: &str, keys: Vec<JwtKeyConfig>) -> JwtConfig {
+        JwtConfig {
+            algorithm: "HS256".to_string(),
+            kid: kid.to_string(),
+            ttl: 1800,
+            refresh_ttl: 3600,
+            impersonate_ttl: 600,
+            issuer: "koobe".to_string(),
+            audience: "rust_admin".to_string(),
+            secret: "".to_string(),
+            keys,
+        }
+    }
+
+    const SECRET_1: &str = "k1-0123456789abcdef0123456789abcdef";
+    const SECRET_2: &str = "k2-0123456789abcdef0123456789abcdef";
+
+    #[test]
+    fn test_jwt_secret() {
+        // 弱密钥和空密钥启动时拒绝
+        assert!(JwtKeys::from_config(&config("k1", vec![hs256_key("k1", "123")])).is_err());
+        assert!(JwtKeys::from_config(&config("k1", vec![hs256_key("k1", "")])).is_err());
+
+        // 没有配置密钥时使用jwt.secret
+        let mut cfg = config("k1", vec![hs256_key("k1", "")]);
+        cfg.secret = SECRET_1.to_string();
+        let keys = JwtKeys::from_config(&cfg).unwrap();
+        let token = JwtToken::new(1, "koobe", "s1", 0, &keys).create_token(&keys).unwrap();
+        assert!(JwtToken::verify(&JwtKeys::from_config(&config("k1", vec![hs256_key("k1", SECRET_1)])).unwrap(), &token).is_ok());
+    }
 
     #[test]
     fn test_jwt() {
-        let jwt = JwtToken::new(1, "koobe", vec![]);
-        let res = jwt.create_token("123");
+        let keys = JwtKeys::from_config(&config("k1", vec![hs256_key("k1", SECRET_1)])).unwrap();
+        let jwt = JwtToken::new(1, "koobe", "s1", 0, &keys);
+        let res = jwt.create_token(&keys);
         match res {
             Ok(token) => {
-                let result = JwtToken::verify("123", &token);
+                let result = JwtToken::verify(&keys, &token);
                 println!("{:?}", result)
             }
             Err(err) => {
@@ -91,4 +378,42 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_jwt_impersonate() {
+        let keys = JwtKeys::from_config(&config("k1", vec![hs256_key("k1", SECRET_1)])).unwrap();
+        let token = JwtToken::new(1, "koobe", "s1", 0, &keys);
+        assert!(!JwtToken::verify(&keys, &token.create_token(&keys).unwrap()).unwrap().is_impersonated());
+
+        let token = JwtToken::new_impersonate(2, "test", "s2", 0, 1, "admin", &keys);
+        let claims = JwtToken::verify(&keys, &token.create_token(&keys).unwrap()).unwrap();
+        assert!(claims.is_impersonated());
+        assert_eq!((claims.id, claims.act, claims.act_name.as_str()), (2, Some(1), "admin"));
+        assert_eq!(claims.exp - claims.iat, 600);
+    }
+
+    #[test]
+    fn test_jwt_mfa() {
+        let keys = JwtKeys::from_config(&config("k1", vec![hs256_key("k1", SECRET_1)])).unwrap();
+        let challenge = JwtToken::new_mfa(1, "admin", "admin@example.com", 300, &keys).create_token(&keys).unwrap();
+        let claims = JwtToken::verify(&keys, &challenge).unwrap();
+        assert_eq!((claims.token_type.as_str(), claims.account.as_str()), (MFA_TOKEN, "admin@example.com"));
+
+        let access = JwtToken::new(1, "admin", "s1", 0, &keys).create_token(&keys).unwrap();
+        assert!(JwtToken::verify(&keys, &access).unwrap().account.is_empty());
+    }
+
+    #[test]
+    fn test_jwt_rotation() {
+        let old_keys = JwtKeys::from_config(&config("k1", vec![hs256_key("k1", SECRET_1)])).unwrap();
+        let token = JwtToken::new(1, "koobe", "s1", 0, &old_keys).create_token(&old_keys).unwrap();
+
+        // 新密钥k2用于签名,旧密钥k1保留用于验签
+        let new_keys = JwtKeys::from_config(&config("k2", vec![hs256_key("k1", SECRET_1), hs256_key("k2", SECRET_2)])).unwrap();
+        assert!(JwtToken::verify(&new_keys, &token).is_ok());
+
+        // 旧密钥下线后,旧token不再有效
+        let rotated_keys = JwtKeys::from_config(&config("k2", vec![hs256_key("k2", SECRET_2)])).unwrap();
+        assert!(JwtToken::verify(&rotated_keys, &token).is_err());
+    }
 }
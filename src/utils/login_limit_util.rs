@@ -0,0 +1,245 @@
+use crate::utils::token_util::now_secs;
+use redis::Commands;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/*
+ *登录失败限制配置
+ *author：刘飞华
+ *date：2026/10/18 19:26:08
+ */
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct LoginLimitConfig {
+    pub captcha_after: u32,        //账号或者IP登录失败N次后需要输入验证码(0表示一直需要验证码)
+    pub max_account_failures: u32, //账号登录失败N次后锁定账号
+    pub max_ip_failures: u32,      //IP登录失败N次后锁定IP
+    pub window: u64,               //失败次数统计时间窗口(秒)
+    pub lock_time: u64,            //锁定时长(秒)
+    pub redis_url: String,         //多实例部署时使用redis保存失败次数,为空时保存在进程内
+}
+
+impl Default for LoginLimitConfig {
+    fn default() -> Self {
+        LoginLimitConfig {
+            captcha_after: 3,
+            max_account_failures: 5,
+            max_ip_failures: 20,
+            window: 900,
+            lock_time: 1800,
+            redis_url: "".to_string(),
+        }
+    }
+}
+
+/*
+ *登录前的检查结果
+ *author：刘飞华
+ *date：2026/10/18 19:26:08
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub enum LoginCheck {
+    Allowed,         //允许登录
+    CaptchaRequired, //需要输入验证码
+    Locked,          //账号或者IP已被锁定
+}
+
+const KEY_PREFIX: &str = "rust_admin:login";
+
+/*
+ *登录失败次数限制(账号和IP分别计数,超过次数后锁定)
+ *author：刘飞华
+ *date：2026/10/18 19:26:08
+ */
+#[derive(Clone)]
+pub struct LoginLimiter {
+    config: LoginLimitConfig,
+    redis: Option<redis::Client>,
+    inner: Arc<Mutex<LoginLimiterInner>>,
+}
+
+#[derive(Default)]
+struct LoginLimiterInner {
+    redis_conn: Option<redis::Connection>,
+    counters: HashMap<String, (u32, usize)>, //key -> (次数, 过期时间)
+}
+
+impl LoginLimiter {
+    pub fn new(config: &LoginLimitConfig) -> LoginLimiter {
+        let redis = match config.redis_url.as_str() {
+            "" => None,
+            url => match redis::Client::open(url) {
+                Ok(client) => Some(client),
+                Err(err) => {
+                    log::error!("open login limit redis error: {:?}", err);
+                    None
+                }
+            },
+        };
+
+        LoginLimiter {
+            config: config.clone(),
+            redis,
+            inner: Arc::new(Mutex::new(LoginLimiterInner::default())),
+        }
+    }
+
+    /// 登录前检查账号和IP是否被锁定,是否需要输入验证码
+    pub fn check(&self, account: &str, ip: &str) -> LoginCheck {
+        if self.get(&lock_key("account", account)) > 0 || self.get(&lock_key("ip", ip)) > 0 {
+            return LoginCheck::Locked;
+        }
+
+        let failures = self.get(&fail_key("account", account)).max(self.get(&fail_key("ip", ip)));
+        if failures >= self.config.captcha_after {
+            return LoginCheck::CaptchaRequired;
+        }
+        LoginCheck::Allowed
+    }
+
+    /// 记录登录失败,返回true表示账号或者IP因此被锁定
+    pub fn fail(&self, account: &str, ip: &str) -> bool {
+        let account_failures = self.incr(&fail_key("account", account), self.config.window);
+        let ip_failures = self.incr(&fail_key("ip", ip), self.config.window);
+
+        let mut locked = false;
+        if account_failures >= self.config.max_account_failures {
+            log::error!("lock account {} after {} failures", account, account_failures);
+            self.incr(&lock_key("account", account), self.config.lock_time);
+            self.del(&fail_key("account", account));
+            locked = true;
+        }
+        if ip_failures >= self.config.max_ip_failures {
+            log::error!("lock ip {} after {} failures", ip, ip_failures);
+            self.incr(&lock_key("ip", ip), self.config.lock_time);
+            self.del(&fail_key("ip", ip));
+            locked = true;
+        }
+        locked
+    }
+
+    /// 登录成功后清除账号的失败次数
+    pub fn success(&self, account: &str) {
+        self.del(&fail_key("account", account));
+    }
+
+    /// 解锁账号
+    pub fn unlock_account(&self, account: &str) {
+        self.del(&lock_key("account", account));
+        self.del(&fail_key("account", account));
+    }
+
+    /// 解锁IP
+    pub fn unlock_ip(&self, ip: &str) {
+        self.del(&lock_key("ip", ip));
+        self.del(&fail_key("ip", ip));
+    }
+
+    fn get(&self, key: &str) -> u32 {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(conn) = self.redis_conn(&mut inner) {
+            return match conn.get::<_, Option<u32>>(key) {
+                Ok(count) => count.unwrap_or_default(),
+                Err(err) => {
+                    log::error!("get login limit {} error: {:?}", key, err);
+                    inner.redis_conn = None;
+                    0
+                }
+            };
+        }
+
+        match inner.counters.get(key) {
+            Some((count, exp)) if *exp > now_secs() => *count,
+            _ => 0,
+        }
+    }
+
+    // 计数加1,第一次计数时开始计算过期时间
+    fn incr(&self, key: &str, ttl: u64) -> u32 {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(conn) = self.redis_conn(&mut inner) {
+            let result = conn.incr::<_, _, u32>(key, 1).and_then(|count| {
+                if count == 1 {
+                    conn.expire::<_, ()>(key, ttl as i64)?;
+                }
+                Ok(count)
+            });
+            return match result {
+                Ok(count) => count,
+                Err(err) => {
+                    log::error!("incr login limit {} error: {:?}", key, err);
+                    inner.redis_conn = None;
+                    0
+                }
+            };
+        }
+
+        let now = now_secs();
+        inner.counters.retain(|_, (_, exp)| *exp > now);
+        let counter = inner.counters.entry(key.to_string()).or_insert((0, now + ttl as usize));
+        counter.0 += 1;
+        counter.0
+    }
+
+    fn del(&self, key: &str) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(conn) = self.redis_conn(&mut inner) {
+            if let Err(err) = conn.del::<_, ()>(key) {
+                log::error!("del login limit {} error: {:?}", key, err);
+                inner.redis_conn = None;
+            }
+            return;
+        }
+        inner.counters.remove(key);
+    }
+
+    fn redis_conn<'a>(&self, inner: &'a mut LoginLimiterInner) -> Option<&'a mut redis::Connection> {
+        let client = self.redis.as_ref()?;
+        if inner.redis_conn.is_none() {
+            match client.get_connection() {
+                Ok(conn) => inner.redis_conn = Some(conn),
+                Err(err) => log::error!("get login limit redis connection error: {:?}", err),
+            }
+        }
+        inner.redis_conn.as_mut()
+    }
+}
+
+//账号前后加空格或者改变大小写时使用同一个计数(和按账号查询用户时一致)
+fn fail_key(kind: &str, value: &str) -> String {
+    format!("{}:fail:{}:{}", KEY_PREFIX, kind, value.trim().to_lowercase())
+}
+
+fn lock_key(kind: &str, value: &str) -> String {
+    format!("{}:lock:{}:{}", KEY_PREFIX, kind, value.trim().to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::login_limit_util::{LoginCheck, LoginLimitConfig, LoginLimiter};
+
+    #[test]
+    fn test_login_limit() {
+        let limiter = LoginLimiter::new(&LoginLimitConfig {
+            captcha_after: 2,
+            max_account_failures: 3,
+            max_ip_failures: 10,
+            ..LoginLimitConfig::default()
+        });
+
+        assert_eq!(limiter.check("koobe", "1.2.3.4"), LoginCheck::Allowed);
+        assert!(!limiter.fail("koobe", "1.2.3.4"));
+        assert!(!limiter.fail(" Koobe ", "1.2.3.4"));
+        assert_eq!(limiter.check("koobe", "1.2.3.4"), LoginCheck::CaptchaRequired);
+
+        // 达到最大失败次数后锁定账号
+        assert!(limiter.fail("koobe", "1.2.3.4"));
+        assert_eq!(limiter.check("koobe", "5.6.7.8"), LoginCheck::Locked);
+        assert_eq!(limiter.check("koobe  ", "5.6.7.8"), LoginCheck::Locked);
+
+        // 管理员解锁后恢复
+        limiter.unlock_account("koobe");
+        assert_eq!(limiter.check("koobe", "5.6.7.8"), LoginCheck::Allowed);
+    }
+}
@@ -0,0 +1,492 @@
+use crate::common::error::{AppError, AppResult};
+use crate::model::system::sys_schema_migration_model::SchemaMigration;
+use crate::utils::db::{transaction, DbKind};
+use rbatis::rbdc::DateTime;
+use rbatis::RBatis;
+use rbs::value;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+/*
+ *数据库迁移配置
+ *author：刘飞华
+ *date：2026/10/19 00:31:18
+ */
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MigrationConfig {
+    pub mode: String, //启动时的迁移方式: apply(执行未执行的迁移), check(有未执行或者被修改的迁移时不启动), off(不处理)
+}
+
+impl Default for MigrationConfig {
+    fn default() -> Self {
+        MigrationConfig { mode: "apply".to_string() }
+    }
+}
+
+pub const MIGRATION_MODES: [&str; 3] = ["apply", "check", "off"];
+
+/*
+ *数据库迁移,脚本编译到程序中,按版本号顺序执行
+ *每个迁移在migrations/{mysql,postgres,sqlite}目录下各有一份脚本,按连接的数据库类型执行对应的脚本
+ *author：刘飞华
+ *date：2026/10/19 00:31:18
+ */
+pub struct Migration {
+    pub version: i64,                       //版本号
+    pub name: &'static str,                 //名称
+    mysql: (&'static str, &'static str),    //mysql的执行脚本和回滚脚本
+    postgres: (&'static str, &'static str), //postgres的执行脚本和回滚脚本
+    sqlite: (&'static str, &'static str),   //sqlite的执行脚本和回滚脚本
+}
+
+impl Migration {
+    fn scripts(&self, kind: DbKind) -> (&'static str, &'static str) {
+        match kind {
+            DbKind::Mysql => self.mysql,
+            DbKind::Postgres => self.postgres,
+            DbKind::Sqlite => self.sqlite,
+        }
+    }
+
+    /// 执行脚本
+    pub fn up(&self, kind: DbKind) -> &'static str {
+        self.scripts(kind).0
+    }
+
+    /// 回滚脚本
+    pub fn down(&self, kind: DbKind) -> &'static str {
+        self.scripts(kind).1
+    }
+
+    /// 执行脚本的sha256,执行后脚本被修改时校验不通过
+    pub fn checksum(&self, kind: DbKind) -> String {
+        Sha256::digest(self.up(kind).as_bytes()).iter().map(|x| format!("{:02x}", x)).collect()
+    }
+}
+
+macro_rules! migration {
+    ($version:expr, $name:literal, $file:literal) => {
+        Migration {
+            version: $version,
+            name: $name,
+            mysql: (
+                include_str!(concat!("../../migrations/mysql/", $file, ".up.sql")),
+                include_str!(concat!("../../migrations/mysql/", $file, ".down.sql")),
+            ),
+            postgres: (
+                include_str!(concat!("../../migrations/postgres/", $file, ".up.sql")),
+                include_str!(concat!("../../migrations/postgres/", $file, ".down.sql")),
+            ),
+            sqlite: (
+                include_str!(concat!("../../migrations/sqlite/", $file, ".up.sql")),
+                include_str!(concat!("../../migrations/sqlite/", $file, ".down.sql")),
+            ),
+        }
+    };
+}
+
+//新增迁移时在migrations的每个数据库目录下添加 {版本号}_{名称}.up.sql 和 {版本号}_{名称}.down.sql,并追加到这里,已发布的迁移不能再修改
+pub const MIGRATIONS: &[Migration] = &[
+    migration!(1, "init_schema", "0001_init_schema"),
+    migration!(2, "seed_data", "0002_seed_data"),
+    migration!(3, "account_security", "0003_account_security"),
+    migration!(4, "i18n", "0004_i18n"),
+    migration!(5, "recycle_bin", "0005_recycle_bin"),
+    migration!(6, "session_refresh", "0006_session_refresh"),
+    migration!(7, "password_hash", "0007_password_hash"),
+];
+
+//没有迁移功能之前手动执行sql脚本建好的数据库,相当于已经执行了这些版本的迁移(只包含原有的sql脚本,之后新增的表和字段必须放在新的迁移中)
+const BASELINE_VERSION: i64 = 2;
+
+// 迁移记录表
+fn create_migration_table(kind: DbKind) -> &'static str {
+    match kind {
+        DbKind::Mysql => {
+            "create table if not exists schema_migrations
+(
+    version      bigint                             not null comment '迁移版本号'
+        primary key,
+    name         varchar(100)                       not null comment '迁移名称',
+    checksum     char(64)                           not null comment '迁移脚本的sha256',
+    applied_time datetime default CURRENT_TIMESTAMP not null comment '执行时间'
+) comment = '已执行的数据库迁移'"
+        }
+        DbKind::Postgres => {
+            "create table if not exists schema_migrations
+(
+    version      bigint primary key,                          -- 迁移版本号
+    name         varchar(100)                        not null, -- 迁移名称
+    checksum     char(64)                            not null, -- 迁移脚本的sha256
+    applied_time timestamp default CURRENT_TIMESTAMP not null  -- 执行时间
+)"
+        }
+        DbKind::Sqlite => {
+            "create table if not exists schema_migrations
+(
+    version      bigint primary key,    -- 迁移版本号
+    name         varchar(100) not null, -- 迁移名称
+    checksum     char(64)     not null, -- 迁移脚本的sha256
+    applied_time datetime     not null  -- 执行时间
+)"
+        }
+    }
+}
+
+/*
+ *迁移状态
+ *author：刘飞华
+ *date：2026/10/19 00:31:18
+ */
+#[derive(Debug)]
+pub struct MigrationStatus {
+    pub version: i64,                   //版本号
+    pub name: String,                   //名称
+    pub applied_time: Option<DateTime>, //执行时间,为空表示未执行
+    pub modified: bool,                 //执行后脚本被修改
+}
+
+/*
+ *启动时按配置执行或者校验迁移
+ *author：刘飞华
+ *date：2026/10/19 00:31:18
+ */
+pub async fn run_on_startup(rb: &RBatis, config: &MigrationConfig) -> AppResult<()> {
+    match config.mode.as_str() {
+        "apply" => {
+            for version in apply(rb).await? {
+                log::info!("applied migration: {}", version);
+            }
+            Ok(())
+        }
+        "check" => check(rb).await,
+        _ => Ok(()),
+    }
+}
+
+/*
+ *命令行执行迁移: up(默认), status, check, down [回滚的个数,默认1], baseline [版本号,默认为初始迁移的版本]
+ *author：刘飞华
+ *date：2026/10/19 00:31:18
+ */
+pub async fn run_command(rb: &RBatis, args: &[String]) -> AppResult<()> {
+    let number = |default: i64| {
+        args.get(1)
+            .map(|x| x.parse::<i64>().map_err(|_| AppError::CommandError(format!("{} 不是有效的数字", x))))
+            .unwrap_or(Ok(default))
+    };
+    match args.first().map(|x| x.as_str()).unwrap_or("up") {
+        "up" => print_versions("applied", apply(rb).await?),
+        "down" => print_versions("rolled back", rollback(rb, number(1)? as usize).await?),
+        "baseline" => print_versions("baselined", baseline(rb, number(BASELINE_VERSION)?).await?),
+        "check" => {
+            check(rb).await?;
+            println!("all migrations are applied");
+        }
+        "status" => {
+            for x in status(rb).await? {
+                let state = match (&x.applied_time, x.modified) {
+                    (None, _) => "pending".to_string(),
+                    (Some(time), false) => format!("applied at {}", time),
+                    (Some(time), true) => format!("applied at {}, modified after applied", time),
+                };
+                println!("{:04} {:<20} {}", x.version, x.name, state);
+            }
+        }
+        command => return Err(AppError::CommandError(format!("未知的迁移命令: {},可选: up, down, status, check, baseline", command))),
+    }
+    Ok(())
+}
+
+fn print_versions(action: &str, versions: Vec<i64>) {
+    if versions.is_empty() {
+        println!("nothing to do");
+    }
+    for version in versions {
+        println!("{} migration {:04}", action, version);
+    }
+}
+
+/// 查询所有迁移的执行状态
+pub async fn status(rb: &RBatis) -> AppResult<Vec<MigrationStatus>> {
+    let kind = DbKind::of(rb)?;
+    let applied = applied(rb).await?;
+    Ok(MIGRATIONS
+        .iter()
+        .map(|m| {
+            let row = applied.iter().find(|x| x.version == m.version);
+            MigrationStatus {
+                version: m.version,
+                name: m.name.to_string(),
+                applied_time: row.and_then(|x| x.applied_time.clone()),
+                modified: row.is_some_and(|x| x.checksum != m.checksum(kind)),
+            }
+        })
+        .collect())
+}
+
+/// 校验所有迁移都已执行并且执行后没有被修改
+pub async fn check(rb: &RBatis) -> AppResult<()> {
+    let list = status(rb).await?;
+    let mut errors = Vec::new();
+    for x in list.iter() {
+        match (&x.applied_time, x.modified) {
+            (None, _) => errors.push(format!("迁移 {:04}_{} 未执行", x.version, x.name)),
+            (Some(_), true) => errors.push(format!("迁移 {:04}_{} 执行后被修改", x.version, x.name)),
+            _ => {}
+        }
+    }
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(AppError::ConfigError(errors.join("; "))),
+    }
+}
+
+/// 按版本号顺序执行未执行的迁移,返回执行的版本号
+/// 还没有迁移记录但已经存在系统表时(手动执行脚本建的库),先把初始迁移标记为已执行
+pub async fn apply(rb: &RBatis) -> AppResult<Vec<i64>> {
+    let kind = DbKind::of(rb)?;
+    let mut applied = applied(rb).await?;
+    if applied.is_empty() && table_exists(rb, "sys_user").await? {
+        log::warn!("database was created without migrations, baseline to version {}", BASELINE_VERSION);
+        baseline(rb, BASELINE_VERSION).await?;
+        applied = self::applied(rb).await?;
+    }
+
+    if let Some(x) = MIGRATIONS.iter().find(|m| applied.iter().any(|x| x.version == m.version && x.checksum != m.checksum(kind))) {
+        return Err(AppError::ConfigError(format!("迁移 {:04}_{} 执行后被修改,请新增迁移而不是修改已执行的迁移", x.version, x.name)));
+    }
+
+    let mut versions = Vec::new();
+    for m in MIGRATIONS.iter().filter(|m| !applied.iter().any(|x| x.version == m.version)) {
+        execute(rb, m, m.up(kind), true).await?;
+        versions.push(m.version);
+    }
+    Ok(versions)
+}
+
+/// 按版本号倒序回滚最近执行的steps个迁移,返回回滚的版本号
+pub async fn rollback(rb: &RBatis, steps: usize) -> AppResult<Vec<i64>> {
+    let kind = DbKind::of(rb)?;
+    let applied = applied(rb).await?;
+    let mut versions = Vec::new();
+    for m in MIGRATIONS.iter().rev().filter(|m| applied.iter().any(|x| x.version == m.version)).take(steps) {
+        execute(rb, m, m.down(kind), false).await?;
+        versions.push(m.version);
+    }
+    Ok(versions)
+}
+
+/// 把版本号小于等于version的迁移标记为已执行(不执行脚本),用于已有的数据库
+pub async fn baseline(rb: &RBatis, version: i64) -> AppResult<Vec<i64>> {
+    let kind = DbKind::of(rb)?;
+    let applied = applied(rb).await?;
+    let mut versions = Vec::new();
+    for m in MIGRATIONS.iter().filter(|m| m.version <= version && !applied.iter().any(|x| x.version == m.version)) {
+        SchemaMigration::insert(rb, &record(m, kind)).await?;
+        versions.push(m.version);
+    }
+    Ok(versions)
+}
+
+// 查询已执行的迁移(迁移记录表不存在时先创建)
+async fn applied(rb: &RBatis) -> AppResult<Vec<SchemaMigration>> {
+    rb.exec(create_migration_table(DbKind::of(rb)?), vec![]).await?;
+    Ok(SchemaMigration::select_all(rb).await?)
+}
+
+async fn table_exists(rb: &RBatis, table: &str) -> AppResult<bool> {
+    let sql = match DbKind::of(rb)? {
+        DbKind::Mysql => "select count(1) from information_schema.tables where table_schema = database() and table_name = ?",
+        DbKind::Postgres => "select count(1) from information_schema.tables where table_schema = current_schema() and table_name = ?",
+        DbKind::Sqlite => "select count(1) from sqlite_master where type = 'table' and name = ?",
+    };
+    Ok(rb.query_decode::<i64>(sql, vec![value!(table)]).await? > 0)
+}
+
+fn record(m: &Migration, kind: DbKind) -> SchemaMigration {
+    SchemaMigration {
+        version: m.version,                  //迁移版本号
+        name: m.name.to_string(),            //迁移名称
+        checksum: m.checksum(kind),          //迁移脚本的sha256
+        applied_time: Some(DateTime::now()), //执行时间
+    }
+}
+
+// 在事务中执行迁移脚本并更新迁移记录(mysql的DDL会隐式提交,表结构的变更不能回滚,postgres和sqlite可以整体回滚)
+async fn execute(rb: &RBatis, m: &Migration, sql: &str, up: bool) -> AppResult<()> {
+    let kind = DbKind::of(rb)?;
+    transaction(rb, async |tx| {
+        for statement in split_statements(sql) {
+            if let Err(err) = tx.exec(&statement, vec![]).await {
+                let preview = statement.chars().take(200).collect::<String>();
+                return Err(AppError::ConfigError(format!("执行迁移 {:04}_{} 失败: {}\n{}", m.version, m.name, err, preview)));
+            }
+        }
+        match up {
+            true => SchemaMigration::insert(tx, &record(m, kind)).await?,
+            false => SchemaMigration::delete_by_map(tx, value! {"version": m.version}).await?,
+        };
+        Ok(())
+    })
+    .await
+}
+
+/// 把脚本拆分成单条语句: 按不在引号(包括postgres的$$)和注释中的分号拆分,并去掉注释
+pub fn split_statements(sql: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut chars = sql.chars().peekable();
+    let mut quote: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        match quote {
+            Some(q) => {
+                current.push(c);
+                if c == '\\' {
+                    if let Some(next) = chars.next() {
+                        current.push(next);
+                    }
+                } else if c == q {
+                    quote = None;
+                }
+            }
+            None => match c {
+                '\'' | '"' | '`' => {
+                    quote = Some(c);
+                    current.push(c);
+                }
+                '-' if chars.peek() == Some(&'-') => {
+                    //行注释: 跳过到行尾
+                    for next in chars.by_ref() {
+                        if next == '\n' {
+                            current.push('\n');
+                            break;
+                        }
+                    }
+                }
+                '$' if chars.peek() == Some(&'$') => {
+                    //postgres的$$字符串(函数体),到下一个$$结束,中间的分号和引号不处理
+                    chars.next();
+                    current.push_str("$$");
+                    let mut prev = ' ';
+                    for next in chars.by_ref() {
+                        current.push(next);
+                        if prev == '$' && next == '$' {
+                            break;
+                        }
+                        prev = next;
+                    }
+                }
+                '/' if chars.peek() == Some(&'*') => {
+                    //块注释
+                    chars.next();
+                    let mut prev = ' ';
+                    for next in chars.by_ref() {
+                        if prev == '*' && next == '/' {
+                            break;
+                        }
+                        prev = next;
+                    }
+                }
+                ';' => {
+                    statements.push(current.trim().to_string());
+                    current.clear();
+                }
+                _ => current.push(c),
+            },
+        }
+    }
+    statements.push(current.trim().to_string());
+    statements.retain(|x| !x.is_empty());
+    statements
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::db::{init_db, DbKind};
+    use crate::utils::migration::{apply, check, rollback, split_statements, table_exists, BASELINE_VERSION, MIGRATIONS};
+    use crate::utils::settings::DbConfig;
+
+    #[test]
+    fn test_split_statements() {
+        let sql = "-- 注释;\ncreate table t (a varchar(10) default ';' comment 'x;y');\n/* 块注释; */\ninsert into t values ('it''s;', \"a;b\", 'c\\';d');\ninsert into t values (1)";
+        let statements = split_statements(sql);
+        assert_eq!(statements.len(), 3, "{:?}", statements);
+        assert_eq!(statements[0], "create table t (a varchar(10) default ';' comment 'x;y')");
+        assert_eq!(statements[1], "insert into t values ('it''s;', \"a;b\", 'c\\';d')");
+        assert_eq!(statements[2], "insert into t values (1)");
+
+        let sql = "create function f() returns trigger as\n$$\nbegin\n    new.a = 'x;';\n    return new;\nend;\n$$ language plpgsql;\ncreate table t (a int)";
+        let statements = split_statements(sql);
+        assert_eq!(statements.len(), 2, "{:?}", statements);
+        assert!(statements[0].ends_with("end;\n$$ language plpgsql"));
+    }
+
+    #[test]
+    fn test_migrations() {
+        for kind in [DbKind::Mysql, DbKind::Postgres, DbKind::Sqlite] {
+            for (i, m) in MIGRATIONS.iter().enumerate() {
+                assert_eq!(m.version, i as i64 + 1);
+                assert!(!m.up(kind).trim().is_empty() && !m.down(kind).trim().is_empty());
+            }
+            //初始表结构和初始数据
+            assert_eq!(split_statements(MIGRATIONS[0].up(kind)).iter().filter(|x| x.starts_with("create table")).count(), 14);
+            assert!(split_statements(MIGRATIONS[1].up(kind)).iter().any(|x| x.contains("'admin'")));
+            //各数据库的初始数据一致
+            let count = |kind| split_statements(MIGRATIONS[1].up(kind)).iter().filter(|x| x.starts_with("INSERT")).count();
+            assert_eq!(count(kind), count(DbKind::Mysql));
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_apply_sqlite() {
+        let rb = init_db(&DbConfig {
+            url: "sqlite://:memory:".to_string(),
+            max_open_conns: 1,
+            timeout: 0,
+        })
+        .await
+        .unwrap();
+        assert_eq!(apply(&rb).await.unwrap(), vec![1, 2, 3, 4, 5, 6, 7]);
+        assert!(apply(&rb).await.unwrap().is_empty());
+        check(&rb).await.unwrap();
+        assert_eq!(rb.query_decode::<i64>("select count(1) from sys_user", vec![]).await.unwrap(), 2);
+
+        let label = r#"select count(1) from sys_dict_data where dict_type = 'sys_user_sex' and label_i18n = '{"en-US":"Male"}'"#;
+        assert_eq!(rb.query_decode::<i64>(label, vec![]).await.unwrap(), 1);
+
+        //删除的角色不占用角色名称
+        rb.exec("insert into sys_role (role_name, role_key, remark, del_flag) values ('超级管理员', 'admin', '', 0)", vec![])
+            .await
+            .unwrap();
+        rb.exec("delete from sys_role where del_flag = 0", vec![]).await.unwrap();
+
+        assert_eq!(rollback(&rb, 7).await.unwrap(), vec![7, 6, 5, 4, 3, 2, 1]);
+        assert!(!table_exists(&rb, "sys_user").await.unwrap());
+        assert!(check(&rb).await.is_err());
+    }
+
+    #[actix_web::test]
+    async fn test_apply_baseline_sqlite() {
+        let rb = init_db(&DbConfig {
+            url: "sqlite://:memory:".to_string(),
+            max_open_conns: 1,
+            timeout: 0,
+        })
+        .await
+        .unwrap();
+        //手动执行原有sql脚本建的库,初始迁移之后新增的表和字段由后面的迁移补齐
+        for m in &MIGRATIONS[..BASELINE_VERSION as usize] {
+            for statement in split_statements(m.up(DbKind::Sqlite)) {
+                rb.exec(&statement, vec![]).await.unwrap();
+            }
+        }
+        assert_eq!(apply(&rb).await.unwrap(), vec![3, 4, 5, 6, 7]);
+        check(&rb).await.unwrap();
+        assert!(table_exists(&rb, "sys_user_session").await.unwrap());
+        let sql = "select count(1) from sys_user where auth_source = '' and locale = ''";
+        assert_eq!(rb.query_decode::<i64>(sql, vec![]).await.unwrap(), 2);
+    }
+}
@@ -1,5 +1,21 @@
+pub mod api_token_util;
+pub mod authenticator;
+pub mod captcha_util;
+pub mod cli;
+pub mod data_scope_util;
 pub mod db;
+pub mod ip_util;
 pub mod jwt_util;
+pub mod login_limit_util;
+pub mod migration;
+pub mod oidc_util;
+pub mod password_policy;
+pub mod password_util;
+pub mod permission_cache;
+pub mod permission_util;
 pub mod redis_util;
+pub mod settings;
 pub mod time_util;
+pub mod token_util;
+pub mod totp_util;
 pub mod user_agent_util;
@@ -0,0 +1,110 @@
+use crate::common::error::{AppError, AppResult};
+use crate::common::i18n::{t, t_args};
+use serde::Deserialize;
+
+/*
+ *密码规则配置
+ *author：刘飞华
+ *date：2026/10/18 22:06:52
+ */
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PasswordPolicyConfig {
+    pub min_length: usize,       //最小长度
+    pub max_length: usize,       //最大长度
+    pub require_lowercase: bool, //必须包含小写字母
+    pub require_uppercase: bool, //必须包含大写字母
+    pub require_digit: bool,     //必须包含数字
+    pub require_special: bool,   //必须包含特殊字符
+    pub deny_user_name: bool,    //不能包含用户账号
+    pub deny_list: Vec<String>,  //禁止使用的密码(不区分大小写)
+    pub history_count: usize,    //不能和最近几次使用过的密码相同,0表示不限制
+    pub max_age_days: u64,       //密码有效期(天),过期后登录时必须先修改密码,0表示不过期
+    pub change_ttl: u64,         //密码过期后修改密码的token有效期(秒)
+}
+
+impl Default for PasswordPolicyConfig {
+    fn default() -> Self {
+        PasswordPolicyConfig {
+            min_length: 8,
+            max_length: 64,
+            require_lowercase: true,
+            require_uppercase: false,
+            require_digit: true,
+            require_special: false,
+            deny_user_name: true,
+            deny_list: ["12345678", "123456789", "password", "password1", "qwerty123", "admin123", "abc12345", "a1234567"]
+                .iter()
+                .map(|x| x.to_string())
+                .collect(),
+            history_count: 5,
+            max_age_days: 90,
+            change_ttl: 600,
+        }
+    }
+}
+
+impl PasswordPolicyConfig {
+    /// 校验密码是否符合规则(不包含历史密码的校验)
+    pub fn validate(&self, password: &str, user_name: &str) -> AppResult<()> {
+        let length = password.chars().count();
+        if length < self.min_length {
+            return Err(AppError::PasswordPolicyError(t_args("password.min_length", &[self.min_length.to_string()])));
+        }
+        if self.max_length > 0 && length > self.max_length {
+            return Err(AppError::PasswordPolicyError(t_args("password.max_length", &[self.max_length.to_string()])));
+        }
+
+        let rules = [
+            (self.require_lowercase, password.chars().any(|x| x.is_ascii_lowercase()), "password.lowercase"),
+            (self.require_uppercase, password.chars().any(|x| x.is_ascii_uppercase()), "password.uppercase"),
+            (self.require_digit, password.chars().any(|x| x.is_ascii_digit()), "password.digit"),
+            (self.require_special, password.chars().any(|x| !x.is_ascii_alphanumeric()), "password.special"),
+        ];
+        let missing = rules.iter().filter(|(required, found, _)| *required && !*found).map(|(_, _, name)| t(name)).collect::<Vec<String>>();
+        if !missing.is_empty() {
+            return Err(AppError::PasswordPolicyError(t_args("password.require", &[missing.join(&t("password.separator"))])));
+        }
+
+        let lower = password.to_lowercase();
+        if self.deny_user_name && !user_name.is_empty() && lower.contains(&user_name.to_lowercase()) {
+            return Err(AppError::PasswordPolicyError(t("password.contains_user_name")));
+        }
+        if self.deny_list.iter().any(|x| x.to_lowercase() == lower) {
+            return Err(AppError::PasswordPolicyError(t("password.too_simple")));
+        }
+        Ok(())
+    }
+
+    /// 密码是否已过期(pwd_update_time和now为秒级时间戳)
+    pub fn is_expired(&self, pwd_update_time: i64, now: i64) -> bool {
+        self.max_age_days > 0 && now - pwd_update_time >= (self.max_age_days * 86400) as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::password_policy::PasswordPolicyConfig;
+
+    #[test]
+    fn test_password_policy() {
+        let mut policy = PasswordPolicyConfig::default();
+        assert!(policy.validate("koobe2026", "admin").is_ok());
+        assert!(policy.validate("k1", "admin").is_err());
+        assert!(policy.validate("koobekoobe", "admin").is_err());
+        assert!(policy.validate("12345678", "admin").is_err());
+        assert!(policy.validate("Password1", "admin").is_err());
+        assert!(policy.validate("myadmin2026", "Admin").is_err());
+
+        policy.require_uppercase = true;
+        policy.require_special = true;
+        let err = policy.validate("koobe2026", "admin").unwrap_err().to_string();
+        assert!(err.contains("大写字母,特殊字符"), "{}", err);
+        assert!(policy.validate("Koobe#2026", "admin").is_ok());
+
+        assert!(!policy.is_expired(0, 89 * 86400));
+        assert!(policy.is_expired(0, 90 * 86400));
+        policy.max_age_days = 0;
+        assert!(!policy.is_expired(0, 900 * 86400));
+    }
+}
@@ -0,0 +1,149 @@
+use crate::common::error::{AppError, AppResult};
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use serde::Deserialize;
+
+/*
+ *密码加密配置(Argon2id)
+ *author：刘飞华
+ *date：2026/10/18 10:12:31
+ */
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PasswordConfig {
+    pub memory_cost: u32, //内存开销(KiB)
+    pub time_cost: u32,   //迭代次数
+    pub parallelism: u32, //并行度
+}
+
+impl Default for PasswordConfig {
+    fn default() -> Self {
+        PasswordConfig {
+            memory_cost: Params::DEFAULT_M_COST,
+            time_cost: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+/*
+ *密码校验结果
+ *author：刘飞华
+ *date：2026/10/18 10:12:31
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordVerify {
+    Invalid,     //密码不正确
+    Valid,       //密码正确
+    NeedsRehash, //密码正确,但存储的是明文或者较弱的哈希,需要重新加密
+}
+
+/*
+ *密码加密工具
+ *author：刘飞华
+ *date：2026/10/18 10:12:31
+ */
+#[derive(Debug, Clone)]
+pub struct PasswordUtil {
+    params: Params,
+}
+
+impl PasswordUtil {
+    pub fn new(config: &PasswordConfig) -> AppResult<PasswordUtil> {
+        match Params::new(config.memory_cost, config.time_cost, config.parallelism, None) {
+            Ok(params) => Ok(PasswordUtil { params }),
+            Err(err) => Err(AppError::PasswordError(format!("invalid argon2 params: {}", err))),
+        }
+    }
+
+    fn argon2(&self) -> Argon2<'_> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+    }
+
+    /// 生成PHC格式的密码哈希,如: $argon2id$v=19$m=19456,t=2,p=1$...
+    pub fn hash(&self, password: &str) -> AppResult<String> {
+        let salt = SaltString::generate(&mut OsRng);
+        match self.argon2().hash_password(password.as_bytes(), &salt) {
+            Ok(hash) => Ok(hash.to_string()),
+            Err(err) => Err(AppError::PasswordError(format!("hash password error: {}", err))),
+        }
+    }
+
+    /// 校验密码,兼容历史的明文密码以及参数较弱的哈希
+    pub fn verify(&self, password: &str, stored: &str) -> PasswordVerify {
+        if !stored.starts_with('$') {
+            return if constant_time_eq(password.as_bytes(), stored.as_bytes()) {
+                PasswordVerify::NeedsRehash
+            } else {
+                PasswordVerify::Invalid
+            };
+        }
+
+        let hash = match PasswordHash::new(stored) {
+            Ok(hash) => hash,
+            Err(err) => {
+                log::error!("parse password hash error: {}", err);
+                return PasswordVerify::Invalid;
+            }
+        };
+
+        // 使用哈希中记录的算法和参数进行校验
+        if Argon2::default().verify_password(password.as_bytes(), &hash).is_err() {
+            return PasswordVerify::Invalid;
+        }
+
+        if self.needs_rehash(&hash) {
+            PasswordVerify::NeedsRehash
+        } else {
+            PasswordVerify::Valid
+        }
+    }
+
+    fn needs_rehash(&self, hash: &PasswordHash) -> bool {
+        if hash.algorithm != Algorithm::Argon2id.ident() || hash.version != Some(Version::V0x13.into()) {
+            return true;
+        }
+
+        match Params::try_from(hash) {
+            Ok(p) => p.m_cost() < self.params.m_cost() || p.t_cost() < self.params.t_cost() || p.p_cost() < self.params.p_cost(),
+            Err(_) => true,
+        }
+    }
+}
+
+/// 比较两个字节串是否相同,耗时和内容无关,防止通过时间差猜测
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b.iter()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::password_util::{PasswordConfig, PasswordUtil, PasswordVerify};
+
+    #[test]
+    fn test_password() {
+        let util = PasswordUtil::new(&PasswordConfig::default()).unwrap();
+        let hash = util.hash("123456").unwrap();
+        assert!(hash.starts_with("$argon2id$"));
+        assert_eq!(util.verify("123456", &hash), PasswordVerify::Valid);
+        assert_eq!(util.verify("1234567", &hash), PasswordVerify::Invalid);
+
+        // 历史明文密码
+        assert_eq!(util.verify("123456", "123456"), PasswordVerify::NeedsRehash);
+        assert_eq!(util.verify("654321", "123456"), PasswordVerify::Invalid);
+
+        // 参数较弱的哈希
+        let weak = PasswordUtil::new(&PasswordConfig {
+            memory_cost: 8,
+            time_cost: 1,
+            parallelism: 1,
+        })
+        .unwrap();
+        let weak_hash = weak.hash("123456").unwrap();
+        assert_eq!(util.verify("123456", &weak_hash), PasswordVerify::NeedsRehash);
+    }
+}
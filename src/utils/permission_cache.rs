@@ -0,0 +1,184 @@
+use crate::utils::token_util::now_secs;
+use redis::Commands;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/*
+ *权限缓存配置
+ *author：刘飞华
+ *date：2026/10/18 17:05:36
+ */
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PermissionCacheConfig {
+    pub ttl: u64,          //缓存有效期(秒),超过有效期重新从数据库加载
+    pub redis_url: String, //多实例部署时通过redis同步权限版本,为空时只使用进程内缓存
+}
+
+impl Default for PermissionCacheConfig {
+    fn default() -> Self {
+        PermissionCacheConfig { ttl: 600, redis_url: "".to_string() }
+    }
+}
+
+//redis中保存权限版本的key
+const REDIS_VERSION_KEY: &str = "rust_admin:permission:version";
+//和redis核对权限版本的间隔(秒)
+const REDIS_CHECK_INTERVAL: usize = 1;
+
+/*
+ *用户权限缓存(进程内,可选redis同步版本)
+ *角色,菜单,用户角色变更时使缓存失效,token中只携带签发时的权限版本
+ *author：刘飞华
+ *date：2026/10/18 17:05:36
+ */
+#[derive(Clone)]
+pub struct PermissionCache {
+    ttl: usize,
+    redis: Option<redis::Client>,
+    inner: Arc<Mutex<PermissionCacheInner>>,
+}
+
+#[derive(Default)]
+struct PermissionCacheInner {
+    version: u64,                                        //当前权限版本
+    version_checked_at: usize,                           //最近一次和redis核对版本的时间
+    redis_conn: Option<redis::Connection>,               //redis连接
+    users: HashMap<i64, (u64, usize, Arc<Vec<String>>)>, //用户权限: 用户id -> (权限版本, 加载时间, 权限)
+}
+
+impl PermissionCache {
+    pub fn new(config: &PermissionCacheConfig) -> PermissionCache {
+        let redis = match config.redis_url.as_str() {
+            "" => None,
+            url => match redis::Client::open(url) {
+                Ok(client) => Some(client),
+                Err(err) => {
+                    log::error!("open permission cache redis error: {:?}", err);
+                    None
+                }
+            },
+        };
+
+        PermissionCache {
+            ttl: config.ttl as usize,
+            redis,
+            inner: Arc::new(Mutex::new(PermissionCacheInner::default())),
+        }
+    }
+
+    /// 当前权限版本
+    pub fn version(&self) -> u64 {
+        let mut inner = self.inner.lock().unwrap();
+        self.sync_version(&mut inner);
+        inner.version
+    }
+
+    /// 查询缓存的用户权限,权限版本变化或者超过有效期时返回None
+    pub fn get(&self, user_id: i64) -> Option<Arc<Vec<String>>> {
+        let mut inner = self.inner.lock().unwrap();
+        self.sync_version(&mut inner);
+        let now = now_secs();
+        match inner.users.get(&user_id) {
+            Some((version, loaded_at, permissions)) if *version == inner.version && *loaded_at + self.ttl > now => Some(permissions.clone()),
+            _ => None,
+        }
+    }
+
+    /// 缓存用户权限,version为加载前读取的权限版本(加载期间权限发生变化时,缓存会在下次访问时失效)
+    pub fn put(&self, user_id: i64, version: u64, permissions: Vec<String>) -> Arc<Vec<String>> {
+        let permissions = Arc::new(permissions);
+        let mut inner = self.inner.lock().unwrap();
+        inner.users.insert(user_id, (version, now_secs(), permissions.clone()));
+        permissions
+    }
+
+    /// 角色,菜单变更时使全部用户的权限缓存失效
+    pub fn invalidate_all(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.users.clear();
+        self.bump_version(&mut inner);
+    }
+
+    /// 用户角色变更时使该用户的权限缓存失效
+    /// 启用redis时其他实例无法只清理单个用户,因此同样会升级权限版本
+    pub fn invalidate_user(&self, user_id: i64) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.users.remove(&user_id);
+        if self.redis.is_some() {
+            self.bump_version(&mut inner);
+        }
+    }
+
+    fn bump_version(&self, inner: &mut PermissionCacheInner) {
+        inner.version += 1;
+        if let Some(conn) = self.redis_conn(inner) {
+            match conn.incr::<_, _, u64>(REDIS_VERSION_KEY, 1) {
+                Ok(version) => inner.version = version,
+                Err(err) => {
+                    log::error!("incr permission version error: {:?}", err);
+                    inner.redis_conn = None;
+                }
+            }
+        }
+        inner.version_checked_at = now_secs();
+    }
+
+    // 定期从redis读取其他实例升级后的权限版本
+    fn sync_version(&self, inner: &mut PermissionCacheInner) {
+        let now = now_secs();
+        if self.redis.is_none() || inner.version_checked_at + REDIS_CHECK_INTERVAL > now {
+            return;
+        }
+        inner.version_checked_at = now;
+
+        if let Some(conn) = self.redis_conn(inner) {
+            match conn.get::<_, Option<u64>>(REDIS_VERSION_KEY) {
+                Ok(version) => inner.version = version.unwrap_or_default(),
+                Err(err) => {
+                    log::error!("get permission version error: {:?}", err);
+                    inner.redis_conn = None;
+                }
+            }
+        }
+    }
+
+    fn redis_conn<'a>(&self, inner: &'a mut PermissionCacheInner) -> Option<&'a mut redis::Connection> {
+        let client = self.redis.as_ref()?;
+        if inner.redis_conn.is_none() {
+            match client.get_connection() {
+                Ok(conn) => inner.redis_conn = Some(conn),
+                Err(err) => log::error!("get permission cache redis connection error: {:?}", err),
+            }
+        }
+        inner.redis_conn.as_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::permission_cache::{PermissionCache, PermissionCacheConfig};
+
+    #[test]
+    fn test_permission_cache() {
+        let cache = PermissionCache::new(&PermissionCacheConfig::default());
+
+        let version = cache.version();
+        cache.put(1, version, vec!["/api/system/user/addUser".to_string()]);
+        cache.put(2, version, vec!["/api/system/role/addRole".to_string()]);
+        assert_eq!(cache.get(1).unwrap().len(), 1);
+
+        // 用户角色变更只影响该用户
+        cache.invalidate_user(1);
+        assert!(cache.get(1).is_none());
+        assert!(cache.get(2).is_some());
+
+        // 角色菜单变更后全部失效,之前版本加载的权限不能再写入生效
+        cache.invalidate_all();
+        assert!(cache.get(2).is_none());
+        cache.put(2, version, vec![]);
+        assert!(cache.get(2).is_none());
+        assert_ne!(cache.version(), version);
+    }
+}
@@ -0,0 +1,220 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/*
+ *接口权限配置
+ *author：刘飞华
+ *date：2026/10/18 16:40:12
+ */
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AuthConfig {
+    pub public_routes: Vec<String>,                     //不需要登录即可访问的接口
+    pub login_routes: Vec<String>,                      //登录后即可访问,不需要分配权限的接口
+    pub permission_codes: HashMap<String, Vec<String>>, //权限标识对应的接口,如: system:user:add = ["POST /api/system/user/addUser"]
+    pub impersonate_deny_routes: Vec<String>,           //管理员模拟登录时禁止访问的接口(即使被模拟的用户有权限)
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        AuthConfig {
+            public_routes: vec![
+                "POST /api/system/user/login".to_string(),
+                "POST /api/system/user/refreshToken".to_string(),
+                "GET /api/system/captcha".to_string(),
+                "POST /api/system/user/loginMfa".to_string(),
+                "POST /api/system/user/loginMfaSetup".to_string(),
+                "POST /api/system/user/changeExpiredPassword".to_string(),
+                "GET /api/system/oidc/authorize".to_string(),
+                "GET /api/system/oidc/callback".to_string(),
+            ],
+            login_routes: vec![
+                "POST /api/system/user/logout".to_string(),
+                "POST /api/system/user/updateUserLocale".to_string(),
+                "POST /api/system/mfa/*".to_string(),
+                "POST /api/system/apiToken/*".to_string(),
+            ],
+            permission_codes: HashMap::new(),
+            impersonate_deny_routes: vec![
+                "POST /api/system/user/updateUserPassword".to_string(),
+                "POST /api/system/user/impersonateUser".to_string(),
+                "POST /api/system/mfa/*".to_string(),
+                "POST /api/system/apiToken/*".to_string(),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String), //固定路径
+    Any,             //匹配一级路径: * 或者 {id}
+    Rest,            //匹配剩余的任意级路径: **
+}
+
+/*
+ *接口匹配规则,格式为: [请求方式] 路径,如: POST /api/system/user/addUser
+ *请求方式省略时匹配所有请求方式; 路径中 * 和 {param} 匹配一级路径, ** 匹配剩余的任意级路径
+ *author：刘飞华
+ *date：2026/10/18 16:40:12
+ */
+#[derive(Debug, Clone)]
+pub struct RoutePattern {
+    method: Option<String>,
+    segments: Vec<Segment>,
+}
+
+impl RoutePattern {
+    pub fn parse(pattern: &str) -> RoutePattern {
+        let pattern = pattern.trim();
+        let (method, path) = match pattern.split_once(char::is_whitespace) {
+            Some((method, path)) if !method.starts_with('/') => (Some(method.to_uppercase()), path.trim()),
+            _ => (None, pattern),
+        };
+
+        let segments = split_path(path)
+            .map(|s| match s {
+                "**" => Segment::Rest,
+                "*" => Segment::Any,
+                s if s.starts_with('{') && s.ends_with('}') => Segment::Any,
+                s => Segment::Literal(s.to_string()),
+            })
+            .collect();
+
+        RoutePattern { method, segments }
+    }
+
+    pub fn matches(&self, method: &str, path: &str) -> bool {
+        if let Some(m) = &self.method {
+            if m != "*" && !m.eq_ignore_ascii_case(method) {
+                return false;
+            }
+        }
+
+        let parts = split_path(path).collect::<Vec<&str>>();
+        let mut i = 0;
+        for segment in &self.segments {
+            match segment {
+                Segment::Rest => return true,
+                Segment::Any if i < parts.len() => {}
+                Segment::Literal(s) if i < parts.len() && s == parts[i] => {}
+                _ => return false,
+            }
+            i += 1;
+        }
+        i == parts.len()
+    }
+}
+
+fn split_path(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|s| !s.is_empty())
+}
+
+/*
+ *接口权限匹配
+ *author：刘飞华
+ *date：2026/10/18 16:40:12
+ */
+#[derive(Debug, Clone)]
+pub struct PermissionMatcher {
+    public_routes: Vec<RoutePattern>,
+    login_routes: Vec<RoutePattern>,
+    permission_codes: HashMap<String, Vec<RoutePattern>>,
+    impersonate_deny_routes: Vec<RoutePattern>,
+}
+
+impl PermissionMatcher {
+    pub fn new(config: &AuthConfig) -> PermissionMatcher {
+        let parse = |list: &Vec<String>| list.iter().map(|x| RoutePattern::parse(x)).collect::<Vec<RoutePattern>>();
+        PermissionMatcher {
+            public_routes: parse(&config.public_routes),
+            login_routes: parse(&config.login_routes),
+            permission_codes: config.permission_codes.iter().map(|(code, routes)| (code.clone(), parse(routes))).collect(),
+            impersonate_deny_routes: parse(&config.impersonate_deny_routes),
+        }
+    }
+
+    /// 不需要登录即可访问的接口
+    pub fn is_public(&self, method: &str, path: &str) -> bool {
+        self.public_routes.iter().any(|x| x.matches(method, path))
+    }
+
+    /// 登录后即可访问的接口
+    pub fn is_login_route(&self, method: &str, path: &str) -> bool {
+        self.login_routes.iter().any(|x| x.matches(method, path))
+    }
+
+    /// 管理员模拟登录时禁止访问的接口
+    pub fn is_impersonate_denied(&self, method: &str, path: &str) -> bool {
+        self.impersonate_deny_routes.iter().any(|x| x.matches(method, path))
+    }
+
+    /// 判断权限(菜单的api_url)是否允许访问接口,权限可以是接口匹配规则,也可以是权限标识(system:user:add, system:user:*)
+    pub fn is_permitted(&self, permissions: &[String], method: &str, path: &str) -> bool {
+        permissions.iter().any(|permission| self.permission_matches(permission, method, path))
+    }
+
+    fn permission_matches(&self, permission: &str, method: &str, path: &str) -> bool {
+        let permission = permission.trim();
+        if permission.is_empty() {
+            return false;
+        }
+        if permission.contains('/') {
+            return RoutePattern::parse(permission).matches(method, path);
+        }
+
+        match permission.strip_suffix('*') {
+            Some(prefix) => self
+                .permission_codes
+                .iter()
+                .filter(|(code, _)| code.starts_with(prefix))
+                .any(|(_, routes)| routes.iter().any(|x| x.matches(method, path))),
+            None => match self.permission_codes.get(permission) {
+                Some(routes) => routes.iter().any(|x| x.matches(method, path)),
+                None => false,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::permission_util::{AuthConfig, PermissionMatcher, RoutePattern};
+
+    #[test]
+    fn test_route_pattern() {
+        assert!(RoutePattern::parse("/api/system/user/addUser").matches("POST", "/api/system/user/addUser"));
+        assert!(!RoutePattern::parse("/api/system/user/addUser").matches("POST", "/api/system/user/addUser/1"));
+        assert!(RoutePattern::parse("/api/system/user/*").matches("GET", "/api/system/user/queryUserMenu"));
+        assert!(!RoutePattern::parse("/api/system/user/*").matches("GET", "/api/system/user"));
+        assert!(RoutePattern::parse("/api/system/**").matches("POST", "/api/system/role/addRole"));
+        assert!(RoutePattern::parse("/api/system/user/{id}/detail").matches("GET", "/api/system/user/12/detail"));
+        assert!(RoutePattern::parse("post /api/system/user/*").matches("POST", "/api/system/user/addUser"));
+        assert!(!RoutePattern::parse("POST /api/system/user/*").matches("GET", "/api/system/user/queryUserMenu"));
+    }
+
+    #[test]
+    fn test_permission_matcher() {
+        let mut config = AuthConfig::default();
+        config.permission_codes.insert("system:user:add".to_string(), vec!["POST /api/system/user/addUser".to_string()]);
+        config.permission_codes.insert("system:user:query".to_string(), vec!["/api/system/user/queryUserList".to_string()]);
+        let matcher = PermissionMatcher::new(&config);
+
+        assert!(matcher.is_public("POST", "/api/system/user/login"));
+        assert!(!matcher.is_public("POST", "/api/system/user/loginLog"));
+        assert!(matcher.is_login_route("POST", "/api/system/user/logout"));
+        assert!(matcher.is_impersonate_denied("POST", "/api/system/mfa/enableMfa"));
+        assert!(!matcher.is_impersonate_denied("GET", "/api/system/user/queryUserMenu"));
+
+        let permissions = vec!["system:user:add".to_string()];
+        assert!(matcher.is_permitted(&permissions, "POST", "/api/system/user/addUser"));
+        assert!(!matcher.is_permitted(&permissions, "GET", "/api/system/user/addUser"));
+        assert!(!matcher.is_permitted(&permissions, "POST", "/api/system/user/deleteUser"));
+
+        let permissions = vec!["system:user:*".to_string()];
+        assert!(matcher.is_permitted(&permissions, "POST", "/api/system/user/queryUserList"));
+
+        let permissions = vec!["/api/system/role/addRole".to_string()];
+        assert!(matcher.is_permitted(&permissions, "POST", "/api/system/role/addRole"));
+    }
+}
@@ -1,6 +1,11 @@
+use crate::common::error::{AppError, AppResult};
+use crate::utils::settings::RedisConfig;
 use redis::Connection;
 
-pub async fn init_redis() -> Connection {
-    let client = redis::Client::open("redis://:r-wz9wop62956dh5k9ed@139.159.180.129:6379").unwrap();
-    client.get_connection().unwrap()
+pub async fn init_redis(config: &RedisConfig) -> AppResult<Connection> {
+    if config.url.is_empty() {
+        return Err(AppError::ConfigError("没有配置redis.url".to_string()));
+    }
+    let client = redis::Client::open(config.url.as_str()).map_err(|e| AppError::ConfigError(format!("redis.url 不正确: {}", e)))?;
+    client.get_connection().map_err(|e| AppError::ConfigError(format!("连接redis失败: {}", e)))
 }
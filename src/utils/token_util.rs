@@ -0,0 +1,147 @@
+use crate::utils::jwt_util::JwtToken;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/*
+ *token吊销记录(进程内),refresh token的轮换状态保存在会话表(sys_user_session.refresh_jti)中,这里只缓存被吊销的token和会话
+ *author：刘飞华
+ *date：2026/10/18 14:20:05
+ */
+#[derive(Clone, Default)]
+pub struct TokenStore {
+    inner: Arc<Mutex<TokenStoreInner>>,
+}
+
+#[derive(Default)]
+struct TokenStoreInner {
+    max_ttl: usize,                           //签发的token最长的有效期(秒),超过后吊销用户的记录可以删除
+    revoked_tokens: HashMap<String, usize>,   //被吊销的token: jti -> 过期时间
+    revoked_users: HashMap<i64, usize>,       //用户在该时间之前签发的token全部失效
+    revoked_sessions: HashMap<String, usize>, //被强制下线的会话: 会话编号 -> 过期时间
+    checked_sessions: HashMap<String, usize>, //会话最近一次和数据库核对的时间
+}
+
+//会话和数据库核对(同时刷新最后访问时间)的间隔(秒)
+const SESSION_CHECK_INTERVAL: usize = 60;
+
+pub fn now_secs() -> usize {
+    SystemTime::now().duration_since(UNIX_EPOCH).expect("获取系统时间失败").as_secs() as usize
+}
+
+impl TokenStoreInner {
+    // 清理已经过期的记录,避免内存无限增长
+    fn purge(&mut self, now: usize) {
+        let max_ttl = self.max_ttl;
+        self.revoked_tokens.retain(|_, exp| *exp > now);
+        self.revoked_users.retain(|_, revoked_at| *revoked_at + max_ttl > now);
+        self.revoked_sessions.retain(|_, exp| *exp > now);
+        self.checked_sessions.retain(|_, checked_at| *checked_at + SESSION_CHECK_INTERVAL > now);
+    }
+}
+
+impl TokenStore {
+    /// max_ttl: 签发的token中最长的有效期(秒)
+    pub fn new(max_ttl: u64) -> TokenStore {
+        let inner = TokenStoreInner {
+            max_ttl: max_ttl as usize,
+            ..TokenStoreInner::default()
+        };
+        TokenStore { inner: Arc::new(Mutex::new(inner)) }
+    }
+
+    /// 吊销单个token
+    pub fn revoke(&self, jti: &str, exp: usize) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.purge(now_secs());
+        inner.revoked_tokens.insert(jti.to_string(), exp);
+    }
+
+    /// 吊销用户当前已签发的全部token(禁用用户,重置密码等),同一秒内重新登录签发的token仍然有效
+    pub fn revoke_user(&self, user_id: i64) {
+        let now = now_secs();
+        let mut inner = self.inner.lock().unwrap();
+        inner.purge(now);
+        inner.revoked_users.insert(user_id, now);
+    }
+
+    /// 强制下线会话,该会话签发的token全部失效
+    pub fn revoke_session(&self, session_id: &str, exp: usize) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.purge(now_secs());
+        inner.revoked_sessions.insert(session_id.to_string(), exp);
+        inner.checked_sessions.remove(session_id);
+    }
+
+    /// 会话是否需要和数据库核对,每个会话每分钟最多核对一次
+    pub fn need_check_session(&self, session_id: &str) -> bool {
+        let now = now_secs();
+        let mut inner = self.inner.lock().unwrap();
+        match inner.checked_sessions.get(session_id) {
+            Some(checked_at) if *checked_at + SESSION_CHECK_INTERVAL > now => false,
+            _ => {
+                inner.checked_sessions.insert(session_id.to_string(), now);
+                true
+            }
+        }
+    }
+
+    /// 判断token是否已经被吊销
+    pub fn is_revoked(&self, token: &JwtToken) -> bool {
+        let inner = self.inner.lock().unwrap();
+        if inner.revoked_tokens.contains_key(&token.jti) || inner.revoked_sessions.contains_key(&token.sid) {
+            return true;
+        }
+        match inner.revoked_users.get(&token.id) {
+            Some(revoked_at) => token.iat < *revoked_at,
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::jwt_util::{JwtConfig, JwtKeyConfig, JwtKeys, JwtToken};
+    use crate::utils::token_util::{now_secs, TokenStore};
+
+    #[test]
+    fn test_revoke_user() {
+        let keys = JwtKeys::from_config(&JwtConfig {
+            algorithm: "HS256".to_string(),
+            kid: "k1".to_string(),
+            ttl: 1800,
+            refresh_ttl: 3600,
+            impersonate_ttl: 1800,
+            issuer: "koobe".to_string(),
+            audience: "rust_admin".to_string(),
+            secret: "".to_string(),
+            keys: vec![JwtKeyConfig {
+                kid: "k1".to_string(),
+                algorithm: None,
+                secret: Some("k1-0123456789abcdef0123456789abcdef".to_string()),
+                secret_env: None,
+                private_key_file: None,
+                private_key_env: None,
+                public_key_file: None,
+                public_key_env: None,
+            }],
+        })
+        .unwrap();
+        let store = TokenStore::new(3600);
+
+        // 吊销用户之前签发的token失效,吊销之后(包括同一秒内)重新登录签发的token有效
+        let mut access = JwtToken::new(1, "koobe", "s1", 0, &keys);
+        access.iat -= 1;
+        store.revoke_user(1);
+        assert!(store.is_revoked(&access));
+        assert!(!store.is_revoked(&JwtToken::new(1, "koobe", "s2", 0, &keys)));
+        assert!(!store.is_revoked(&JwtToken::new(2, "koobe", "s3", 0, &keys)));
+
+        // 超过token最长有效期后删除吊销用户的记录
+        let mut inner = store.inner.lock().unwrap();
+        inner.purge(now_secs() + 3599);
+        assert!(inner.revoked_users.contains_key(&1));
+        inner.purge(now_secs() + 3601);
+        assert!(inner.revoked_users.is_empty());
+    }
+}
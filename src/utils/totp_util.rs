@@ -0,0 +1,201 @@
+use crate::common::error::{AppError, AppResult};
+use crate::utils::captcha_util::encode_png;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use qrcode::{Color, QrCode};
+use rand::Rng;
+use serde::Deserialize;
+use sha1::Sha1;
+
+/*
+ *两步验证(TOTP)配置
+ *author：刘飞华
+ *date：2026/10/18 20:03:44
+ */
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MfaConfig {
+    pub issuer: String,                  //身份验证器中显示的签发方名称
+    pub required_role_keys: Vec<String>, //必须启用两步验证的角色(角色权限字符串),用户拥有其中任意一个角色时,未绑定的用户登录时需要先绑定
+    pub challenge_ttl: u64,              //登录第二步的挑战token有效期(秒)
+    pub skew: u64,                       //允许前后偏差的时间步数(每步30秒),用于兼容手机时间误差
+    pub recovery_codes: usize,           //恢复码数量
+}
+
+impl Default for MfaConfig {
+    fn default() -> Self {
+        MfaConfig {
+            issuer: "rust_admin".to_string(),
+            required_role_keys: vec![],
+            challenge_ttl: 300,
+            skew: 1,
+            recovery_codes: 10,
+        }
+    }
+}
+
+//时间步长(秒)
+const TIME_STEP: u64 = 30;
+//动态码位数
+const DIGITS: u32 = 6;
+//密钥长度(字节),RFC 4226推荐160位
+const SECRET_LEN: usize = 20;
+//base32字母表(RFC 4648)
+const BASE32_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+//恢复码字符(去掉了容易混淆的0,o,1,l,i)
+const RECOVERY_CHARS: &[u8] = b"23456789abcdefghjkmnpqrstuvwxyz";
+
+/// 生成base32编码的随机密钥
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; SECRET_LEN];
+    rand::thread_rng().fill(&mut bytes);
+    base32_encode(&bytes)
+}
+
+/// 生成身份验证器使用的otpauth地址,如: otpauth://totp/rust_admin:admin?secret=...&issuer=rust_admin
+pub fn otpauth_uri(issuer: &str, account: &str, secret: &str) -> String {
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        url_encode(issuer),
+        url_encode(account),
+        secret,
+        url_encode(issuer),
+        DIGITS,
+        TIME_STEP
+    )
+}
+
+/// 把otpauth地址生成二维码png图片(data url)
+pub fn qr_code(uri: &str) -> AppResult<String> {
+    const QUIET_ZONE: usize = 4;
+    const SCALE: usize = 6;
+
+    let code = QrCode::new(uri.as_bytes()).map_err(|e| AppError::ImageError(e.to_string()))?;
+    let colors = code.to_colors();
+    let width = code.width();
+    let size = (width + QUIET_ZONE * 2) * SCALE;
+
+    let mut pixels = vec![255u8; size * size];
+    for (i, color) in colors.iter().enumerate() {
+        if *color == Color::Light {
+            continue;
+        }
+        let (left, top) = ((i % width + QUIET_ZONE) * SCALE, (i / width + QUIET_ZONE) * SCALE);
+        for y in top..top + SCALE {
+            pixels[y * size + left..y * size + left + SCALE].fill(0);
+        }
+    }
+
+    let png = encode_png(&pixels, size as u32, size as u32, png::ColorType::Grayscale)?;
+    Ok(format!("data:image/png;base64,{}", STANDARD.encode(png)))
+}
+
+/// 校验动态码,成功时返回动态码对应的时间步
+/// 调用方需要记录最后使用的时间步,拒绝小于等于该时间步的动态码,防止动态码被重放
+pub fn verify_code(secret: &str, code: &str, now: u64, skew: u64) -> Option<u64> {
+    let code = code.trim();
+    if code.len() != DIGITS as usize || !code.bytes().all(|x| x.is_ascii_digit()) {
+        return None;
+    }
+    let key = base32_decode(secret)?;
+    let current = now / TIME_STEP;
+    (current.saturating_sub(skew)..=current + skew).find(|step| format!("{:0width$}", hotp(&key, *step), width = DIGITS as usize) == code)
+}
+
+/// 生成恢复码(明文只在生成时返回给用户一次,数据库中保存哈希)
+pub fn generate_recovery_codes(count: usize) -> Vec<String> {
+    let mut rng = rand::thread_rng();
+    (0..count)
+        .map(|_| {
+            let code = (0..10).map(|_| RECOVERY_CHARS[rng.gen_range(0..RECOVERY_CHARS.len())] as char).collect::<String>();
+            format!("{}-{}", &code[..5], &code[5..])
+        })
+        .collect()
+}
+
+/// 是否是恢复码的格式(用于区分用户输入的是动态码还是恢复码)
+pub fn is_recovery_code(code: &str) -> bool {
+    let code = code.trim();
+    code.len() == 11 && code.as_bytes()[5] == b'-'
+}
+
+// RFC 4226 HOTP(HMAC-SHA1动态截断)
+fn hotp(key: &[u8], counter: u64) -> u32 {
+    let mut mac = Hmac::<Sha1>::new_from_slice(key).expect("hmac accepts keys of any size");
+    mac.update(&counter.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([hash[offset] & 0x7f, hash[offset + 1], hash[offset + 2], hash[offset + 3]]);
+    binary % 10u32.pow(DIGITS)
+}
+
+fn base32_encode(data: &[u8]) -> String {
+    let mut result = String::new();
+    for chunk in data.chunks(5) {
+        let mut buf = [0u8; 5];
+        buf[..chunk.len()].copy_from_slice(chunk);
+        let bits = buf.iter().fold(0u64, |acc, x| (acc << 8) | *x as u64);
+        let chars = (chunk.len() * 8).div_ceil(5);
+        for i in 0..chars {
+            result.push(BASE32_CHARS[((bits >> (35 - i * 5)) & 0x1f) as usize] as char);
+        }
+    }
+    result
+}
+
+// 解码时忽略空格,填充字符和大小写,兼容用户手动输入的密钥
+fn base32_decode(s: &str) -> Option<Vec<u8>> {
+    let mut result = Vec::new();
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for c in s.bytes().filter(|x| *x != b' ' && *x != b'=') {
+        let value = BASE32_CHARS.iter().position(|x| *x == c.to_ascii_uppercase())? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            result.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(result)
+}
+
+fn url_encode(s: &str) -> String {
+    s.bytes()
+        .map(|x| match x {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (x as char).to_string(),
+            _ => format!("%{:02X}", x),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::totp_util::{base32_decode, base32_encode, generate_recovery_codes, generate_secret, hotp, is_recovery_code, otpauth_uri, qr_code, verify_code};
+
+    #[test]
+    fn test_totp() {
+        // RFC 6238附录B的测试数据(SHA1, 取后6位)
+        let secret = base32_encode(b"12345678901234567890");
+        assert_eq!(secret, "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ");
+        assert_eq!(base32_decode(&secret.to_lowercase()).unwrap(), b"12345678901234567890");
+        assert_eq!(hotp(b"12345678901234567890", 59 / 30), 287082);
+        assert_eq!(verify_code(&secret, "081804", 1111111109, 0), Some(1111111109 / 30));
+        assert_eq!(verify_code(&secret, "005924", 1234567890, 1), Some(1234567890 / 30));
+
+        // 超出允许的时间偏差
+        assert_eq!(verify_code(&secret, "081804", 1111111109 + 90, 1), None);
+        assert_eq!(verify_code(&secret, "08180", 1111111109, 1), None);
+
+        assert_eq!(base32_decode(&generate_secret()).unwrap().len(), 20);
+        assert!(generate_recovery_codes(10).iter().all(|x| is_recovery_code(x)));
+
+        let uri = otpauth_uri("rust admin", "koobe", &secret);
+        assert_eq!(
+            uri,
+            "otpauth://totp/rust%20admin:koobe?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&issuer=rust%20admin&algorithm=SHA1&digits=6&period=30"
+        );
+        assert!(qr_code(&uri).unwrap().starts_with("data:image/png;base64,"));
+    }
+}
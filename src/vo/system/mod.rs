@@ -1,9 +1,14 @@
+pub mod sys_api_token_vo;
+pub mod sys_captcha_vo;
 pub mod sys_dept_vo;
 pub mod sys_dict_data_vo;
 pub mod sys_dict_type_vo;
 pub mod sys_login_log_vo;
 pub mod sys_menu_vo;
+pub mod sys_mfa_vo;
 pub mod sys_notice_vo;
+pub mod sys_oidc_vo;
+pub mod sys_online_vo;
 pub mod sys_operate_log_vo;
 pub mod sys_post_vo;
 pub mod sys_role_vo;
@@ -0,0 +1,75 @@
+// author：刘飞华
+// createTime：2026/10/18 22:48:15
+
+use crate::common::result::serialize_datetime;
+use crate::common::validate::validate_ids;
+use rbatis::rbdc::DateTime;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/*
+创建API令牌请求参数
+*/
+#[derive(Debug, Validate, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddApiTokenReq {
+    #[validate(length(min = 1, max = 50))]
+    pub name: String, //令牌名称
+    #[validate(length(min = 1, max = 1000))]
+    pub permissions: Vec<String>, //令牌的权限(只能选择自己拥有的权限)
+    #[validate(range(min = 0, max = 3650))]
+    pub expire_days: Option<u64>, //有效期(天),为空或者0表示使用允许的最长有效期
+}
+
+/*
+创建API令牌响应参数
+*/
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddApiTokenResp {
+    pub id: i64,              //主键
+    pub token: String,        //令牌明文(只返回这一次,请妥善保存)
+    pub token_prefix: String, //令牌标识
+    #[serde(serialize_with = "serialize_datetime")]
+    pub expire_time: Option<DateTime>, //过期时间
+}
+
+/*
+吊销API令牌请求参数
+*/
+#[derive(Debug, Validate, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevokeApiTokenReq {
+    #[validate(custom(function = "validate_ids"))]
+    pub ids: Vec<i64>, //令牌ID
+}
+
+/*
+吊销用户全部API令牌请求参数
+*/
+#[derive(Debug, Validate, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevokeUserApiTokenReq {
+    #[validate(range(min = 1))]
+    pub user_id: i64, //用户ID
+}
+
+/*
+查询API令牌列表响应参数
+*/
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTokenResp {
+    pub id: Option<i64>,          //主键
+    pub name: String,             //令牌名称
+    pub token_prefix: String,     //令牌标识
+    pub permissions: Vec<String>, //令牌的权限
+    pub status: i8,               //状态(0:已吊销,1:正常)
+    #[serde(serialize_with = "serialize_datetime")]
+    pub expire_time: Option<DateTime>, //过期时间
+    #[serde(serialize_with = "serialize_datetime")]
+    pub last_used_time: Option<DateTime>, //最后使用时间
+    pub last_used_ip: String,     //最后使用IP
+    #[serde(serialize_with = "serialize_datetime")]
+    pub create_time: Option<DateTime>, //创建时间
+}
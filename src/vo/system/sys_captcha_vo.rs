@@ -0,0 +1,14 @@
+// author：刘飞华
+// createTime：2026/10/18 19:26:08
+
+use serde::Serialize;
+
+/*
+图片验证码响应参数
+*/
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptchaResp {
+    pub captcha_id: String, //验证码编号(登录时和验证码一起提交)
+    pub image: String,      //验证码图片(data:image/png;base64,...)
+}
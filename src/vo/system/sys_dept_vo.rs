@@ -2,62 +2,81 @@
 // createTime：2024/12/25 10:01:11
 
 use crate::common::result::serialize_datetime;
+use crate::common::validate::{validate_email, validate_ids, validate_mobile};
 use rbatis::rbdc::DateTime;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 /*
 删除部门表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct DeleteDeptReq {
+    #[validate(range(min = 1))]
     pub id: i64,
 }
 
 /*
 更新部门表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DeptReq {
-    pub id: Option<i64>,           //部门id
-    pub parent_id: i64,            //父部门id
-    pub dept_name: String,         //部门名称
+    #[validate(range(min = 1))]
+    pub id: Option<i64>, //部门id
+    #[validate(range(min = 0))]
+    pub parent_id: i64, //父部门id
+    #[validate(length(min = 1, max = 30))]
+    pub dept_name: String, //部门名称
+    #[validate(length(max = 50))]
     pub ancestors: Option<String>, //祖级列表
-    pub sort: i32,                 //显示顺序
-    pub leader: String,            //负责人
-    pub phone: String,             //联系电话
-    pub email: String,             //邮箱
-    pub status: i8,                //部状态（0：停用，1:正常）
+    pub sort: i32, //显示顺序
+    #[validate(length(max = 20))]
+    pub leader: String, //负责人
+    #[validate(custom(function = "validate_mobile"))]
+    pub phone: String, //联系电话
+    #[validate(custom(function = "validate_email"))]
+    pub email: String, //邮箱
+    #[validate(range(min = 0, max = 1))]
+    pub status: i8, //部状态（0：停用，1:正常）
 }
 
 /*
 更新部门表状态请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct UpdateDeptStatusReq {
+    #[validate(custom(function = "validate_ids"))]
     pub ids: Vec<i64>,
+    #[validate(range(min = 0, max = 1))]
     pub status: i8,
 }
 
 /*
 查询部门表详情请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct QueryDeptDetailReq {
+    #[validate(range(min = 1))]
     pub id: i64,
 }
 
 /*
 查询部门表列表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryDeptListReq {
+    #[validate(length(max = 30))]
     pub dept_name: Option<String>, //部门名称
-    pub leader: Option<String>,    //负责人
-    pub phone: Option<String>,     //联系电话
-    pub email: Option<String>,     //邮箱
+    #[validate(length(max = 20))]
+    pub leader: Option<String>, //负责人
+    #[validate(length(max = 11))]
+    pub phone: Option<String>, //联系电话
+    #[validate(length(max = 50))]
+    pub email: Option<String>, //邮箱
     #[serde(default = "default_status")]
+    #[validate(range(min = 0, max = 2))]
     pub status: Option<i8>, //部状态（0：停用，1:正常）
 }
 fn default_status() -> Option<i8> {
@@ -83,3 +102,35 @@ pub struct DeptResp {
     #[serde(serialize_with = "serialize_datetime")]
     pub update_time: Option<DateTime>, //修改时间
 }
+
+/*
+查询已删除部门列表(回收站)请求参数
+*/
+#[derive(Debug, Validate, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryDeletedDeptListReq {
+    #[validate(range(min = 1))]
+    pub page_no: u64,
+    #[validate(range(min = 1, max = 1000))]
+    pub page_size: u64,
+    #[validate(length(max = 30))]
+    pub dept_name: Option<String>, //部门名称
+}
+
+/*
+恢复已删除部门请求参数
+*/
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct RestoreDeptReq {
+    #[validate(custom(function = "validate_ids"))]
+    pub ids: Vec<i64>,
+}
+
+/*
+彻底删除部门请求参数(只能删除回收站中的数据)
+*/
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct PurgeDeptReq {
+    #[validate(custom(function = "validate_ids"))]
+    pub ids: Vec<i64>,
+}
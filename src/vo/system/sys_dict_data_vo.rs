@@ -2,64 +2,88 @@
 // createTime：2024/12/25 10:01:11
 
 use crate::common::result::serialize_datetime;
+use crate::common::validate::{validate_ids, validate_label_i18n};
 use rbatis::rbdc::DateTime;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 /*
 删除字典数据表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct DeleteDictDataReq {
+    #[validate(custom(function = "validate_ids"))]
     pub ids: Vec<i64>,
 }
 
 /*
 更新字典数据表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DictDataReq {
-    pub id: Option<i64>,        //字典编码
-    pub dict_sort: i32,         //字典排序
-    pub dict_label: String,     //字典标签
-    pub dict_value: String,     //字典键值
-    pub dict_type: String,      //字典类型
-    pub css_class: String,      //样式属性（其他样式扩展）
-    pub list_class: String,     //表格回显样式
-    pub is_default: String,     //是否默认（Y是 N否）
-    pub status: i8,             //状态（0：停用，1:正常）
+    #[validate(range(min = 1))]
+    pub id: Option<i64>, //字典编码
+    pub dict_sort: i32, //字典排序
+    #[validate(length(min = 1, max = 100))]
+    pub dict_label: String, //字典标签
+    #[validate(length(min = 1, max = 100))]
+    pub dict_value: String, //字典键值
+    #[validate(length(min = 1, max = 100))]
+    pub dict_type: String, //字典类型
+    #[validate(length(max = 100))]
+    pub css_class: String, //样式属性（其他样式扩展）
+    #[validate(length(max = 100))]
+    pub list_class: String, //表格回显样式
+    #[validate(length(min = 1, max = 1))]
+    pub is_default: String, //是否默认（Y是 N否）
+    #[validate(range(min = 0, max = 1))]
+    pub status: i8, //状态（0：停用，1:正常）
+    #[validate(length(max = 500))]
     pub remark: Option<String>, //备注
+    #[serde(default)]
+    #[validate(length(max = 500), custom(function = "validate_label_i18n"))]
+    pub label_i18n: String, //字典标签的多语言翻译(json,如: {"en-US":"Male"}),没有对应语言时使用dict_label
 }
 
 /*
 更新字典数据表状态请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct UpdateDictDataStatusReq {
+    #[validate(custom(function = "validate_ids"))]
     pub ids: Vec<i64>,
+    #[validate(range(min = 0, max = 1))]
     pub status: i8,
 }
 
 /*
 查询字典数据表详情请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct QueryDictDataDetailReq {
+    #[validate(range(min = 1))]
     pub id: i64,
 }
 
 /*
 查询字典数据表列表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryDictDataListReq {
+    #[validate(range(min = 1))]
     pub page_no: u64,
+    #[validate(range(min = 1, max = 1000))]
     pub page_size: u64,
+    #[validate(length(max = 100))]
     pub dict_label: Option<String>, //字典标签
+    #[validate(length(max = 100))]
     pub dict_value: Option<String>, //字典键值
-    pub dict_type: Option<String>,  //字典类型
+    #[validate(length(max = 100))]
+    pub dict_type: Option<String>, //字典类型
     #[serde(default = "default_status")]
+    #[validate(range(min = 0, max = 2))]
     pub status: Option<i8>, //状态（0：停用，1:正常）
 }
 fn default_status() -> Option<i8> {
@@ -81,6 +105,8 @@ pub struct DictDataResp {
     pub is_default: String,     //是否默认（Y是 N否）
     pub status: i8,             //状态（0：停用，1:正常）
     pub remark: Option<String>, //备注
+    pub label_i18n: String,     //字典标签的多语言翻译
+    pub locale_label: String,   //当前语言的字典标签(前端展示字典时使用)
     #[serde(serialize_with = "serialize_datetime")]
     pub create_time: Option<DateTime>, //创建时间
     #[serde(serialize_with = "serialize_datetime")]
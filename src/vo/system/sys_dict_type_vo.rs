@@ -2,58 +2,74 @@
 // createTime：2024/12/25 10:01:11
 
 use crate::common::result::serialize_datetime;
+use crate::common::validate::validate_ids;
 use rbatis::rbdc::DateTime;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 /*
 删除字典类型表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct DeleteDictTypeReq {
+    #[validate(custom(function = "validate_ids"))]
     pub ids: Vec<i64>,
 }
 
 /*
 更新字典类型表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DictTypeReq {
-    pub id: Option<i64>,        //字典主键
-    pub dict_name: String,      //字典名称
-    pub dict_type: String,      //字典类型
-    pub status: i8,             //状态（0：停用，1:正常）
+    #[validate(range(min = 1))]
+    pub id: Option<i64>, //字典主键
+    #[validate(length(min = 1, max = 100))]
+    pub dict_name: String, //字典名称
+    #[validate(length(min = 1, max = 100))]
+    pub dict_type: String, //字典类型
+    #[validate(range(min = 0, max = 1))]
+    pub status: i8, //状态（0：停用，1:正常）
+    #[validate(length(max = 500))]
     pub remark: Option<String>, //备注
 }
 
 /*
 更新字典类型表状态请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct UpdateDictTypeStatusReq {
+    #[validate(custom(function = "validate_ids"))]
     pub ids: Vec<i64>,
+    #[validate(range(min = 0, max = 1))]
     pub status: i8,
 }
 
 /*
 查询字典类型表详情请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct QueryDictTypeDetailReq {
+    #[validate(range(min = 1))]
     pub id: i64,
 }
 
 /*
 查询字典类型表列表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryDictTypeListReq {
+    #[validate(range(min = 1))]
     pub page_no: u64,
+    #[validate(range(min = 1, max = 1000))]
     pub page_size: u64,
+    #[validate(length(max = 100))]
     pub dict_name: Option<String>, //字典名称
+    #[validate(length(max = 100))]
     pub dict_type: Option<String>, //字典类型
     #[serde(default = "default_status")]
+    #[validate(range(min = 0, max = 2))]
     pub status: Option<i8>, //状态（0：停用，1:正常）
 }
 fn default_status() -> Option<i8> {
@@ -1,39 +1,51 @@
 // author：刘飞华
 // createTime：2024/12/25 10:01:11
 use crate::common::result::serialize_datetime;
+use crate::common::validate::validate_ids;
 use rbatis::rbdc::DateTime;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 /*
 删除系统访问记录请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct DeleteLoginLogReq {
+    #[validate(custom(function = "validate_ids"))]
     pub ids: Vec<i64>,
 }
 
 /*
 查询系统访问记录详情请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct QueryLoginLogDetailReq {
+    #[validate(range(min = 1))]
     pub id: i64,
 }
 
 /*
 查询系统访问记录列表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryLoginLogListReq {
+    #[validate(range(min = 1))]
     pub page_no: u64,
+    #[validate(range(min = 1, max = 1000))]
     pub page_size: u64,
-    pub login_name: Option<String>,     //登录账号
-    pub ipaddr: Option<String>,         //登录IP地址
+    #[validate(length(max = 50))]
+    pub login_name: Option<String>, //登录账号
+    #[validate(length(max = 128))]
+    pub ipaddr: Option<String>, //登录IP地址
+    #[validate(length(max = 255))]
     pub login_location: Option<String>, //登录地点
-    pub browser: Option<String>,        //浏览器类型
-    pub os: Option<String>,             //操作系统
+    #[validate(length(max = 50))]
+    pub browser: Option<String>, //浏览器类型
+    #[validate(length(max = 50))]
+    pub os: Option<String>, //操作系统
     #[serde(default = "default_status")]
+    #[validate(range(min = 0, max = 2))]
     pub status: Option<i8>, //登录状态(0:失败,1:成功)
 }
 fn default_status() -> Option<i8> {
@@ -2,59 +2,76 @@
 // createTime：2024/12/12 14:41:44
 
 use crate::common::result::serialize_datetime;
+use crate::common::validate::validate_ids;
 use rbatis::rbdc::DateTime;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 /*
 删除菜单信息请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct DeleteMenuReq {
+    #[validate(range(min = 1))]
     pub id: i64,
 }
 
 /*
 更新菜单信息请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MenuReq {
-    pub id: Option<i64>,           //主键
-    pub menu_name: String,         //菜单名称
-    pub menu_type: i8,             //菜单类型(1：目录   2：菜单   3：按钮)
-    pub visible: i8,               //菜单状态（0:隐藏, 显示:1）
-    pub status: i8,                //状态(1:正常，0:禁用)
-    pub sort: i32,                 //排序
-    pub parent_id: Option<i64>,    //父ID
-    pub menu_url: Option<String>,  //路由路径
-    pub api_url: Option<String>,   //接口URL
+    #[validate(range(min = 1))]
+    pub id: Option<i64>, //主键
+    #[validate(length(min = 1, max = 50))]
+    pub menu_name: String, //菜单名称
+    #[validate(range(min = 1, max = 3))]
+    pub menu_type: i8, //菜单类型(1：目录   2：菜单   3：按钮)
+    #[validate(range(min = 0, max = 1))]
+    pub visible: i8, //菜单状态（0:隐藏, 显示:1）
+    #[validate(range(min = 0, max = 1))]
+    pub status: i8, //状态(1:正常，0:禁用)
+    pub sort: i32, //排序
+    #[validate(range(min = 0))]
+    pub parent_id: Option<i64>, //父ID
+    #[validate(length(max = 255))]
+    pub menu_url: Option<String>, //路由路径
+    #[validate(length(max = 255))]
+    pub api_url: Option<String>, //接口URL
+    #[validate(length(max = 255))]
     pub menu_icon: Option<String>, //菜单图标
-    pub remark: Option<String>,    //备注
+    #[validate(length(max = 255))]
+    pub remark: Option<String>, //备注
 }
 
 /*
 更新菜单信息状态请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct UpdateMenuStatusReq {
+    #[validate(custom(function = "validate_ids"))]
     pub ids: Vec<i64>,
+    #[validate(range(min = 0, max = 1))]
     pub status: i8,
 }
 
 /*
 查询菜单信息详情请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct QueryMenuDetailReq {
+    #[validate(range(min = 1))]
     pub id: i64,
 }
 
 /*
 查询菜单信息列表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryMenuListReq {
+    #[validate(length(max = 50))]
     pub menu_name: Option<String>, //菜单名称
 }
 
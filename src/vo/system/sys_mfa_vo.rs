@@ -0,0 +1,91 @@
+// author：刘飞华
+// createTime：2026/10/18 20:03:44
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/*
+两步验证状态响应参数
+*/
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaStatusResp {
+    pub enabled: bool,              //是否已启用两步验证
+    pub required: bool,             //用户的角色是否要求必须启用两步验证
+    pub recovery_codes_left: usize, //剩余可用的恢复码数量
+}
+
+/*
+绑定两步验证响应参数
+*/
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaSetupResp {
+    pub secret: String,              //TOTP密钥(无法扫码时手动输入)
+    pub otpauth_uri: String,         //otpauth地址
+    pub qr_code: String,             //二维码图片(data:image/png;base64,...)
+    pub recovery_codes: Vec<String>, //恢复码(只返回一次,请妥善保存)
+}
+
+/*
+提交动态码请求参数(确认绑定,关闭两步验证,重新生成恢复码)
+*/
+#[derive(Debug, Validate, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaCodeReq {
+    #[validate(length(min = 1, max = 32))]
+    pub code: String, //动态码或者恢复码
+}
+
+/*
+重新生成恢复码响应参数
+*/
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryCodesResp {
+    pub recovery_codes: Vec<String>, //恢复码(只返回一次,请妥善保存)
+}
+
+/*
+重置用户两步验证请求参数
+*/
+#[derive(Debug, Validate, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetUserMfaReq {
+    #[validate(range(min = 1))]
+    pub user_id: i64, //用户id
+}
+
+/*
+登录需要两步验证时的响应参数
+*/
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MfaChallengeResp {
+    pub mfa_required: bool,      //需要两步验证
+    pub setup_required: bool,    //需要先绑定身份验证器(角色要求必须启用两步验证,但用户还没有绑定)
+    pub challenge_token: String, //挑战token,提交动态码时使用
+    pub expires_in: u64,         //挑战token有效期(秒)
+}
+
+/*
+登录第二步请求参数
+*/
+#[derive(Debug, Validate, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginMfaReq {
+    #[validate(length(min = 1, max = 2048))]
+    pub challenge_token: String, //挑战token
+    #[validate(length(min = 1, max = 32))]
+    pub code: String, //动态码或者恢复码
+}
+
+/*
+登录时绑定身份验证器请求参数
+*/
+#[derive(Debug, Validate, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginMfaSetupReq {
+    #[validate(length(min = 1, max = 2048))]
+    pub challenge_token: String, //挑战token
+}
@@ -2,59 +2,76 @@
 // createTime：2024/12/25 10:01:11
 
 use crate::common::result::serialize_datetime;
+use crate::common::validate::validate_ids;
 use rbatis::rbdc::DateTime;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 /*
 删除通知公告表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct DeleteNoticeReq {
+    #[validate(custom(function = "validate_ids"))]
     pub ids: Vec<i64>,
 }
 
 /*
 更新通知公告表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NoticeReq {
-    pub id: Option<i64>,        //公告ID
-    pub notice_title: String,   //公告标题
-    pub notice_type: i8,        //公告类型（1:通知,2:公告）
+    #[validate(range(min = 1))]
+    pub id: Option<i64>, //公告ID
+    #[validate(length(min = 1, max = 50))]
+    pub notice_title: String, //公告标题
+    #[validate(range(min = 1, max = 2))]
+    pub notice_type: i8, //公告类型（1:通知,2:公告）
+    #[validate(length(max = 255))]
     pub notice_content: String, //公告内容
-    pub status: i8,             //公告状态（0:关闭,1:正常 ）
+    #[validate(range(min = 0, max = 1))]
+    pub status: i8, //公告状态（0:关闭,1:正常 ）
+    #[validate(length(max = 255))]
     pub remark: Option<String>, //备注
 }
 
 /*
 更新通知公告表状态请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct UpdateNoticeStatusReq {
+    #[validate(custom(function = "validate_ids"))]
     pub ids: Vec<i64>,
+    #[validate(range(min = 0, max = 1))]
     pub status: i8,
 }
 
 /*
 查询通知公告表详情请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct QueryNoticeDetailReq {
+    #[validate(range(min = 1))]
     pub id: i64,
 }
 
 /*
 查询通知公告表列表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryNoticeListReq {
+    #[validate(range(min = 1))]
     pub page_no: u64,
+    #[validate(range(min = 1, max = 1000))]
     pub page_size: u64,
+    #[validate(length(max = 50))]
     pub notice_title: Option<String>, //公告标题
-    pub notice_type: Option<i8>,      //公告类型（1:通知,2:公告）
+    #[validate(range(min = 1, max = 2))]
+    pub notice_type: Option<i8>, //公告类型（1:通知,2:公告）
     #[serde(default = "default_status")]
+    #[validate(range(min = 0, max = 2))]
     pub status: Option<i8>, //公告状态（0:关闭,1:正常 ）
 }
 fn default_status() -> Option<i8> {
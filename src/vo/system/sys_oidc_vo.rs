@@ -0,0 +1,15 @@
+// author：刘飞华
+// createTime：2026/10/18 21:25:36
+
+use serde::Deserialize;
+
+/*
+单点登录回调请求参数(身份提供方跳转时带在地址上)
+*/
+#[derive(Debug, Deserialize)]
+pub struct OidcCallbackReq {
+    pub code: Option<String>,              //授权码
+    pub state: Option<String>,             //登录请求编号
+    pub error: Option<String>,             //身份提供方返回的错误码(用户取消登录等)
+    pub error_description: Option<String>, //错误描述
+}
@@ -0,0 +1,76 @@
+// author：刘飞华
+// createTime：2026/10/18 15:31:20
+
+use crate::common::result::serialize_datetime;
+use rbatis::rbdc::DateTime;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/*
+强制下线会话请求参数
+*/
+#[derive(Debug, Validate, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForceLogoutReq {
+    #[validate(length(min = 1, max = 1000))]
+    pub session_ids: Vec<String>, //会话编号
+}
+
+/*
+强制下线用户全部会话请求参数
+*/
+#[derive(Debug, Validate, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForceLogoutUserReq {
+    #[validate(range(min = 1))]
+    pub user_id: i64, //用户ID
+}
+
+/*
+查询在线用户会话详情请求参数
+*/
+#[derive(Debug, Validate, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryOnlineDetailReq {
+    #[validate(length(min = 1, max = 64))]
+    pub session_id: String, //会话编号
+}
+
+/*
+查询在线用户列表请求参数
+*/
+#[derive(Debug, Validate, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryOnlineListReq {
+    #[validate(range(min = 1))]
+    pub page_no: u64,
+    #[validate(range(min = 1, max = 1000))]
+    pub page_size: u64,
+    #[validate(length(max = 50))]
+    pub user_name: Option<String>, //用户账号
+    #[validate(length(max = 128))]
+    pub ipaddr: Option<String>, //登录IP地址
+}
+
+/*
+查询在线用户列表响应参数
+*/
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnlineResp {
+    pub id: Option<i64>,        //主键
+    pub session_id: String,     //会话编号
+    pub user_id: i64,           //用户ID
+    pub user_name: String,      //用户账号
+    pub ipaddr: String,         //登录IP地址
+    pub login_location: String, //登录地点
+    pub browser: String,        //浏览器类型
+    pub os: String,             //操作系统
+    pub status: i8,             //会话状态(0:已下线,1:在线)
+    #[serde(serialize_with = "serialize_datetime")]
+    pub login_time: Option<DateTime>, //登录时间
+    #[serde(serialize_with = "serialize_datetime")]
+    pub last_access_time: Option<DateTime>, //最后访问时间
+    #[serde(serialize_with = "serialize_datetime")]
+    pub expire_time: Option<DateTime>, //过期时间
+}
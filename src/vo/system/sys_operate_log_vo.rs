@@ -2,43 +2,60 @@
 // createTime：2024/12/25 10:01:11
 
 use crate::common::result::serialize_datetime;
+use crate::common::validate::validate_ids;
 use rbatis::rbdc::DateTime;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 /*
 删除操作日志记录请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct DeleteOperateLogReq {
+    #[validate(custom(function = "validate_ids"))]
     pub ids: Vec<i64>,
 }
 
 /*
 查询操作日志记录详情请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct QueryOperateLogDetailReq {
+    #[validate(range(min = 1))]
     pub id: i64,
 }
 
 /*
 查询操作日志记录列表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryOperateLogListReq {
+    #[validate(range(min = 1))]
     pub page_no: u64,
+    #[validate(range(min = 1, max = 1000))]
     pub page_size: u64,
-    pub title: Option<String>,            //模块标题
-    pub business_type: Option<i8>,        //业务类型（0其它 1新增 2修改 3删除）
-    pub method: Option<String>,           //方法名称
-    pub request_method: Option<String>,   //请求方式
-    pub operator_type: Option<i8>,        //操作类别（0其它 1后台用户 2手机端用户）
-    pub operate_name: Option<String>,     //操作人员
-    pub dept_name: Option<String>,        //部门名称
-    pub operate_url: Option<String>,      //请求URL
-    pub operate_ip: Option<String>,       //主机地址
+    #[validate(length(max = 50))]
+    pub title: Option<String>, //模块标题
+    #[validate(range(min = 0, max = 3))]
+    pub business_type: Option<i8>, //业务类型（0其它 1新增 2修改 3删除）
+    #[validate(length(max = 100))]
+    pub method: Option<String>, //方法名称
+    #[validate(length(max = 10))]
+    pub request_method: Option<String>, //请求方式
+    #[validate(range(min = 0, max = 2))]
+    pub operator_type: Option<i8>, //操作类别（0其它 1后台用户 2手机端用户）
+    #[validate(length(max = 50))]
+    pub operate_name: Option<String>, //操作人员
+    #[validate(length(max = 50))]
+    pub dept_name: Option<String>, //部门名称
+    #[validate(length(max = 255))]
+    pub operate_url: Option<String>, //请求URL
+    #[validate(length(max = 128))]
+    pub operate_ip: Option<String>, //主机地址
+    #[validate(length(max = 255))]
     pub operate_location: Option<String>, //操作地点
     #[serde(default = "default_status")]
+    #[validate(range(min = 0, max = 2))]
     pub status: Option<i8>, //操作状态(0:异常,正常)
 }
 fn default_status() -> Option<i8> {
@@ -58,6 +75,7 @@ pub struct OperateLogResp {
     pub operator_type: Option<i8>,        //操作类别（0其它 1后台用户 2手机端用户）
     pub operate_name: Option<String>,     //操作人员
     pub dept_name: Option<String>,        //部门名称
+    pub impersonate_name: Option<String>, //模拟登录的管理员
     pub operate_url: Option<String>,      //请求URL
     pub operate_ip: Option<String>,       //主机地址
     pub operate_location: Option<String>, //操作地点
@@ -2,59 +2,75 @@
 // createTime：2024/12/25 10:01:11
 
 use crate::common::result::serialize_datetime;
+use crate::common::validate::validate_ids;
 use rbatis::rbdc::DateTime;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 /*
 删除岗位信息表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct DeletePostReq {
+    #[validate(custom(function = "validate_ids"))]
     pub ids: Vec<i64>,
 }
 
 /*
 更新岗位信息表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PostReq {
-    pub id: Option<i64>,        //岗位id
-    pub post_code: String,      //岗位编码
-    pub post_name: String,      //岗位名称
-    pub sort: i32,              //显示顺序
-    pub status: i8,             //部状态（0：停用，1:正常）
+    #[validate(range(min = 1))]
+    pub id: Option<i64>, //岗位id
+    #[validate(length(min = 1, max = 64))]
+    pub post_code: String, //岗位编码
+    #[validate(length(min = 1, max = 50))]
+    pub post_name: String, //岗位名称
+    pub sort: i32, //显示顺序
+    #[validate(range(min = 0, max = 1))]
+    pub status: i8, //部状态（0：停用，1:正常）
+    #[validate(length(max = 500))]
     pub remark: Option<String>, //备注
 }
 
 /*
 更新岗位信息表状态请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct UpdatePostStatusReq {
+    #[validate(custom(function = "validate_ids"))]
     pub ids: Vec<i64>,
+    #[validate(range(min = 0, max = 1))]
     pub status: i8,
 }
 
 /*
 查询岗位信息表详情请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct QueryPostDetailReq {
+    #[validate(range(min = 1))]
     pub id: i64,
 }
 
 /*
 查询岗位信息表列表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryPostListReq {
+    #[validate(range(min = 1))]
     pub page_no: u64,
+    #[validate(range(min = 1, max = 1000))]
     pub page_size: u64,
+    #[validate(length(max = 64))]
     pub post_code: Option<String>, //岗位编码
+    #[validate(length(max = 50))]
     pub post_name: Option<String>, //岗位名称
     #[serde(default = "default_status")]
+    #[validate(range(min = 0, max = 2))]
     pub status: Option<i8>, //部状态（0：停用，1:正常）
 }
 fn default_status() -> Option<i8> {
@@ -2,60 +2,78 @@
 // createTime：2024/12/12 14:41:44
 
 use crate::common::result::serialize_datetime;
+use crate::common::validate::validate_ids;
+use crate::vo::system::sys_dept_vo::DeptResp;
 use rbatis::rbdc::DateTime;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 /*
 删除角色信息请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct DeleteRoleReq {
+    #[validate(custom(function = "validate_ids"))]
     pub ids: Vec<i64>,
 }
 
 /*
 更新角色信息请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RoleReq {
-    pub id: Option<i64>,        //主键
-    pub role_name: String,      //名称
-    pub role_key: String,       //角色权限字符串
-    pub data_scope: i8,         //数据范围（1：全部数据权限 2：自定数据权限 3：本部门数据权限 4：本部门及以下数据权限）
-    pub status: i8,             //状态(1:正常，0:禁用)
+    #[validate(range(min = 1))]
+    pub id: Option<i64>, //主键
+    #[validate(length(min = 1, max = 50))]
+    pub role_name: String, //名称
+    #[validate(length(min = 1, max = 100))]
+    pub role_key: String, //角色权限字符串
+    #[validate(range(min = 1, max = 5))]
+    pub data_scope: i8, //数据范围（1：全部数据权限 2：自定数据权限 3：本部门数据权限 4：本部门及以下数据权限 5：仅本人数据权限）
+    #[validate(range(min = 0, max = 1))]
+    pub status: i8, //状态(1:正常，0:禁用)
+    #[validate(length(max = 255))]
     pub remark: Option<String>, //备注
 }
 
 /*
 更新角色信息状态请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct UpdateRoleStatusReq {
+    #[validate(custom(function = "validate_ids"))]
     pub ids: Vec<i64>,
+    #[validate(range(min = 0, max = 1))]
     pub status: i8,
 }
 
 /*
 查询角色信息详情请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct QueryRoleDetailReq {
+    #[validate(range(min = 1))]
     pub id: i64,
 }
 
 /*
 查询角色信息列表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryRoleListReq {
+    #[validate(range(min = 1))]
     pub page_no: u64,
+    #[validate(range(min = 1, max = 1000))]
     pub page_size: u64,
+    #[validate(length(max = 50))]
     pub role_name: Option<String>, //名称
     #[serde(default = "default_status")]
+    #[validate(range(min = 0, max = 2))]
     pub status: Option<i8>, //状态(1:正常，0:禁用)
-    pub role_key: Option<String>,  //角色权限字符串
+    #[validate(length(max = 100))]
+    pub role_key: Option<String>, //角色权限字符串
 }
 fn default_status() -> Option<i8> {
     Some(2)
@@ -69,7 +87,7 @@ pub struct RoleResp {
     pub id: Option<i64>,        //主键
     pub role_name: String,      //名称
     pub role_key: String,       //角色权限字符串
-    pub data_scope: i8,         //数据范围（1：全部数据权限 2：自定数据权限 3：本部门数据权限 4：本部门及以下数据权限）
+    pub data_scope: i8,         //数据范围（1：全部数据权限 2：自定数据权限 3：本部门数据权限 4：本部门及以下数据权限 5：仅本人数据权限）
     pub status: i8,             //状态(1:正常，0:禁用)
     pub remark: Option<String>, //备注
     #[serde(serialize_with = "serialize_datetime")]
@@ -81,9 +99,10 @@ pub struct RoleResp {
 /*
 查询角色菜单信息参数
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Validate, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryRoleMenuReq {
+    #[validate(range(min = 1))]
     pub role_id: i64, //角色id
 }
 
@@ -114,65 +133,150 @@ pub struct MenuDataList {
 /*
 更新用户角色信息
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Validate, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateRoleMenuReq {
+    #[validate(length(max = 1000))]
     pub menu_ids: Vec<i64>,
+    #[validate(range(min = 1))]
     pub role_id: i64,
 }
 
+/*
+查询角色数据权限参数
+*/
+#[derive(Debug, Validate, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryRoleDeptReq {
+    #[validate(range(min = 1))]
+    pub role_id: i64, //角色id
+}
+
+/*
+角色数据权限信息
+*/
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryRoleDeptData {
+    pub data_scope: i8,           //数据范围（1：全部数据权限 2：自定数据权限 3：本部门数据权限 4：本部门及以下数据权限 5：仅本人数据权限）
+    pub dept_ids: Vec<i64>,       //自定数据权限的部门Ids
+    pub dept_list: Vec<DeptResp>, //部门列表
+}
+
+/*
+更新角色数据权限参数
+*/
+#[derive(Debug, Validate, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRoleDeptReq {
+    #[validate(range(min = 1))]
+    pub role_id: i64, //角色id
+    #[validate(range(min = 1, max = 5))]
+    pub data_scope: i8, //数据范围（1：全部数据权限 2：自定数据权限 3：本部门数据权限 4：本部门及以下数据权限 5：仅本人数据权限）
+    #[validate(length(max = 1000))]
+    pub dept_ids: Vec<i64>, //自定数据权限的部门Ids(数据范围为2时有效)
+}
+
 /*
 查询已分配用户角色列表
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Validate, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AllocatedListReq {
+    #[validate(range(min = 1))]
     pub page_no: u64,
+    #[validate(range(min = 1, max = 1000))]
     pub page_size: u64,
+    #[validate(range(min = 1))]
     pub role_id: i64,
+    #[validate(length(max = 11))]
     pub mobile: Option<String>,
+    #[validate(length(max = 50))]
     pub user_name: Option<String>,
 }
 
 /*
 查询未分配用户角色列表
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Validate, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UnallocatedListReq {
+    #[validate(range(min = 1))]
     pub page_no: u64,
+    #[validate(range(min = 1, max = 1000))]
     pub page_size: u64,
+    #[validate(range(min = 1))]
     pub role_id: i64,
+    #[validate(length(max = 11))]
     pub mobile: Option<String>,
+    #[validate(length(max = 50))]
     pub user_name: Option<String>,
 }
 
 /*
 取消授权用户
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Validate, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CancelAuthUserReq {
+    #[validate(range(min = 1))]
     pub user_id: i64,
+    #[validate(range(min = 1))]
     pub role_id: i64,
 }
 
 /*
 批量取消授权用户
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Validate, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CancelAuthUserAllReq {
+    #[validate(custom(function = "validate_ids"))]
     pub user_ids: Vec<i64>,
+    #[validate(range(min = 1))]
     pub role_id: i64,
 }
 
 /*
 批量选择用户授权
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Validate, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SelectAuthUserAllReq {
+    #[validate(custom(function = "validate_ids"))]
     pub user_ids: Vec<i64>,
+    #[validate(range(min = 1))]
     pub role_id: i64,
 }
+
+/*
+查询已删除角色信息列表(回收站)请求参数
+*/
+#[derive(Debug, Validate, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryDeletedRoleListReq {
+    #[validate(range(min = 1))]
+    pub page_no: u64,
+    #[validate(range(min = 1, max = 1000))]
+    pub page_size: u64,
+    #[validate(length(max = 50))]
+    pub role_name: Option<String>, //名称
+}
+
+/*
+恢复已删除角色信息请求参数
+*/
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct RestoreRoleReq {
+    #[validate(custom(function = "validate_ids"))]
+    pub ids: Vec<i64>,
+}
+
+/*
+彻底删除角色信息请求参数(只能删除回收站中的数据)
+*/
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct PurgeRoleReq {
+    #[validate(custom(function = "validate_ids"))]
+    pub ids: Vec<i64>,
+}
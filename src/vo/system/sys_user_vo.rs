@@ -2,36 +2,55 @@
 // createTime：2024/12/12 14:41:44
 
 use crate::common::result::serialize_datetime;
+use crate::common::validate::{validate_email, validate_ids, validate_locale, validate_mobile};
 use crate::vo::system::sys_dept_vo::DeptResp;
 use crate::vo::system::sys_role_vo::RoleResp;
 use rbatis::rbdc::DateTime;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
 /*
 删除用户信息请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct DeleteUserReq {
+    #[validate(custom(function = "validate_ids"))]
     pub ids: Vec<i64>,
 }
 
 /*
 更新用户信息请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UserReq {
-    pub id: Option<i64>,          //主键
-    pub mobile: String,           //手机
-    pub user_name: String,        //用户账号
-    pub nick_name: String,        //用户昵称
+    #[validate(range(min = 1))]
+    pub id: Option<i64>, //主键
+    #[validate(custom(function = "validate_mobile"))]
+    pub mobile: String, //手机
+    #[validate(length(min = 1, max = 50))]
+    pub user_name: String, //用户账号
+    #[validate(length(min = 1, max = 30))]
+    pub nick_name: String, //用户昵称
+    #[validate(length(min = 1, max = 128))]
     pub password: Option<String>, //用户密码
-    pub email: String,            //用户邮箱
+    #[validate(custom(function = "validate_email"))]
+    pub email: String, //用户邮箱
     #[serde(default = "default_avatar")]
+    #[validate(length(max = 100))]
     pub avatar: Option<String>, //头像路径
-    pub status: i8,               //状态(1:正常，0:禁用)
-    pub dept_id: i64,             //部门ID
-    pub remark: Option<String>,   //备注
-    pub post_ids: Vec<i64>,       //岗位ids
+    #[serde(default)]
+    #[validate(length(max = 20))]
+    pub auth_source: String, //认证方式(local:本地密码,ldap:LDAP),为空时使用配置的认证链
+    #[validate(range(min = 0, max = 1))]
+    pub status: i8, //状态(1:正常，0:禁用)
+    #[validate(range(min = 1))]
+    pub dept_id: i64, //部门ID
+    #[validate(length(max = 255))]
+    pub remark: Option<String>, //备注
+    #[validate(custom(function = "validate_locale"))]
+    pub locale: Option<String>, //语言偏好(zh-CN, en-US),不传时不修改
+    #[validate(length(max = 1000))]
+    pub post_ids: Vec<i64>, //岗位ids
 }
 fn default_avatar() -> Option<String> {
     Some("https://gw.alipayobjects.com/zos/antfincdn/XAosXuNZyF/BiazfanxmamNRoxxVxka.png".to_string())
@@ -39,33 +58,42 @@ fn default_avatar() -> Option<String> {
 /*
 更新用户信息状态请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct UpdateUserStatusReq {
+    #[validate(custom(function = "validate_ids"))]
     pub ids: Vec<i64>,
+    #[validate(range(min = 0, max = 1))]
     pub status: i8,
 }
 
 /*
 查询用户信息详情请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 pub struct QueryUserDetailReq {
+    #[validate(range(min = 1))]
     pub id: i64,
 }
 
 /*
 查询用户信息列表请求参数
 */
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Validate, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryUserListReq {
+    #[validate(range(min = 1))]
     pub page_no: u64,
+    #[validate(range(min = 1, max = 1000))]
     pub page_size: u64,
-    pub mobile: Option<String>,    //手机
+    #[validate(length(max = 11))]
+    pub mobile: Option<String>, //手机
+    #[validate(length(max = 50))]
     pub user_name: Option<String>, //姓名
     #[serde(default = "default_status")]
+    #[validate(range(min = 0, max = 2))]
     pub status: Option<i8>, //状态(1:正常，0:禁用)
-    pub dept_id: Option<i64>,      //部门ID
+    #[validate(range(min = 1))]
+    pub dept_id: Option<i64>, //部门ID
 }
 fn default_status() -> Option<i8> {
     Some(2)
@@ -83,6 +111,7 @@ pub struct UserResp {
     pub user_type: String,      //用户类型（00系统用户）
     pub email: String,          //用户邮箱
     pub avatar: Option<String>, //头像路径
+    pub auth_source: String,    //认证方式(local:本地密码,ldap:LDAP),为空时使用配置的认证链
     pub status: i8,             //状态(1:正常，0:禁用)
     pub dept_id: i64,           //部门ID
     pub login_ip: String,       //最后登录IP
@@ -93,6 +122,7 @@ pub struct UserResp {
     #[serde(serialize_with = "serialize_datetime")]
     pub pwd_update_date: Option<DateTime>, //密码最后更新时间
     pub remark: Option<String>, //备注
+    pub locale: String,         //语言偏好
     #[serde(serialize_with = "serialize_datetime")]
     pub create_time: Option<DateTime>, //创建时间
     #[serde(serialize_with = "serialize_datetime")]
@@ -103,10 +133,109 @@ pub struct UserResp {
 /*
 登录请求参数
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Validate, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct UserLoginReq {
-    pub mobile: String,   //手机
+    #[serde(alias = "mobile")]
+    #[validate(length(min = 1, max = 50))]
+    pub account: String, //登录账号(用户账号,邮箱或者手机号)
+    #[validate(length(min = 1, max = 128))]
     pub password: String, //密码
+    #[validate(length(max = 64))]
+    pub captcha_id: Option<String>, //验证码编号(登录失败多次后必填)
+    #[validate(length(max = 16))]
+    pub captcha_code: Option<String>, //验证码
+}
+
+/*
+解锁用户请求参数(按用户或者IP解锁)
+*/
+#[derive(Debug, Validate, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnlockUserReq {
+    #[validate(range(min = 1))]
+    pub user_id: Option<i64>, //用户id
+    #[validate(length(min = 1, max = 128))]
+    pub ipaddr: Option<String>, //IP地址
+}
+
+/*
+登录响应参数
+*/
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserLoginResp {
+    pub token: String,         //access token
+    pub refresh_token: String, //refresh token
+    pub expires_in: u64,       //access token有效期(秒)
+}
+
+/*
+登录时密码已过期的响应参数
+*/
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordExpiredResp {
+    pub password_expired: bool, //密码已过期,需要先修改密码再重新登录
+    pub change_token: String,   //修改密码token,修改过期密码时使用
+    pub expires_in: u64,        //修改密码token有效期(秒)
+}
+
+/*
+模拟登录请求参数
+*/
+#[derive(Debug, Validate, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImpersonateUserReq {
+    #[validate(range(min = 1))]
+    pub user_id: i64, //被模拟的用户id
+    #[validate(length(min = 1, max = 255))]
+    pub reason: String, //模拟登录的原因(记录到登录日志)
+}
+
+/*
+模拟登录响应参数(不签发refresh token,到期后需要重新模拟登录)
+*/
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImpersonateUserResp {
+    pub token: String,           //access token
+    pub expires_in: u64,         //access token有效期(秒)
+    pub user_id: i64,            //被模拟的用户id
+    pub user_name: String,       //被模拟的用户账号
+    pub impersonated_by: String, //实际操作的管理员账号
+}
+
+/*
+修改过期密码请求参数
+*/
+#[derive(Debug, Validate, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeExpiredPasswordReq {
+    #[validate(length(min = 1, max = 2048))]
+    pub change_token: String, //修改密码token
+    #[validate(length(min = 1, max = 128))]
+    pub password: String, //新密码
+}
+
+/*
+刷新token请求参数
+*/
+#[derive(Debug, Validate, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshTokenReq {
+    #[validate(length(min = 1, max = 2048))]
+    pub refresh_token: String, //refresh token
+}
+
+/*
+退出登录请求参数
+*/
+#[derive(Debug, Validate, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogoutReq {
+    #[validate(length(max = 2048))]
+    pub refresh_token: Option<String>, //refresh token
 }
 
 /*
@@ -119,6 +248,9 @@ pub struct QueryUserMenuResp {
     pub btn_menu: Vec<String>,
     pub avatar: Option<String>,
     pub name: String,
+    pub locale: String, //语言偏好,为空时根据请求头Accept-Language选择语言
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub impersonated_by: Option<String>, //模拟登录时实际操作的管理员账号
 }
 
 /*
@@ -139,9 +271,10 @@ pub struct MenuList {
 /*
 查询用户关联角色请求参数
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Validate, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct QueryUserRoleReq {
+    #[validate(range(min = 1))]
     pub user_id: i64,
 }
 
@@ -158,28 +291,75 @@ pub struct QueryUserRoleResp {
 /*
 更新用户关联角色请求参数
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Validate, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateUserRoleReq {
-    pub user_id: i64,       //用户主键
+    #[validate(range(min = 1))]
+    pub user_id: i64, //用户主键
+    #[validate(length(max = 1000))]
     pub role_ids: Vec<i64>, //角色主键
 }
 
 /*
 重置密码
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Validate, Deserialize)]
 pub struct ResetUserPwdReq {
-    pub id: i64,          //用户主键
+    #[validate(range(min = 1))]
+    pub id: i64, //用户主键
+    #[validate(length(min = 1, max = 128))]
     pub password: String, //用户密码
 }
 
 /*
 重置密码
 */
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Validate, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateUserPwdReq {
-    pub pwd: String,    //用户密码
+    #[validate(length(min = 1, max = 128))]
+    pub pwd: String, //用户密码
+    #[validate(length(min = 1, max = 128))]
     pub re_pwd: String, //用户密码
 }
+
+/*
+修改语言偏好请求参数
+*/
+#[derive(Debug, Validate, Deserialize)]
+pub struct UpdateUserLocaleReq {
+    #[validate(custom(function = "validate_locale"))]
+    pub locale: String, //语言偏好(zh-CN, en-US),为空时根据请求头Accept-Language选择语言
+}
+
+/*
+查询已删除用户信息列表(回收站)请求参数
+*/
+#[derive(Debug, Validate, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryDeletedUserListReq {
+    #[validate(range(min = 1))]
+    pub page_no: u64,
+    #[validate(range(min = 1, max = 1000))]
+    pub page_size: u64,
+    #[validate(length(max = 50))]
+    pub user_name: Option<String>, //姓名
+}
+
+/*
+恢复已删除用户信息请求参数
+*/
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct RestoreUserReq {
+    #[validate(custom(function = "validate_ids"))]
+    pub ids: Vec<i64>,
+}
+
+/*
+彻底删除用户信息请求参数(只能删除回收站中的数据)
+*/
+#[derive(Debug, Validate, Serialize, Deserialize)]
+pub struct PurgeUserReq {
+    #[validate(custom(function = "validate_ids"))]
+    pub ids: Vec<i64>,
+}